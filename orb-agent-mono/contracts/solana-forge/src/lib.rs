@@ -1,204 +1,10110 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Burn, Token, TokenAccount, Mint};
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use anchor_lang::solana_program::{ed25519_program, keccak};
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token::spl_token::native_mint::ID as NATIVE_MINT;
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::{transfer_hook, StateWithExtensions};
+use anchor_spl::token_interface::{
+    self, Burn, CloseAccount, Mint, MintTo, SyncNative, TokenAccount, TokenInterface,
+    TransferChecked,
+};
 use mpl_token_metadata::accounts::Metadata;
+use mpl_token_metadata::instructions::{
+    FreezeDelegatedAccountCpi, FreezeDelegatedAccountCpiAccounts, ThawDelegatedAccountCpi,
+    ThawDelegatedAccountCpiAccounts,
+};
 use wormhole_anchor_sdk::wormhole;
 
 declare_id!("FoRGe11111111111111111111111111111111111111");
 
+/// Seed prefix for the Wormhole message accounts posted by `feed_orb`.
+pub const SEED_PREFIX_SENT: &[u8; 4] = b"sent";
+
+/// Basis point denominator for fee splits.
+pub const MAX_BPS: u16 = 10_000;
+
+/// `ForgeState::pause_flags` bits.
+pub const PAUSE_FEED: u8 = 1 << 0;
+pub const PAUSE_CROSS_CHAIN_FEED: u8 = 1 << 1;
+pub const PAUSE_CLOSE: u8 = 1 << 2;
+
+/// Metaplex Bubblegum, which derives compressed Orb asset ids.
+pub const BUBBLEGUM_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+
+/// SPL account compression, which owns compressed Orb Merkle trees.
+pub const SPL_ACCOUNT_COMPRESSION_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KSNpi");
+
+/// Anchor discriminator of the account compression `verify_leaf` instruction.
+const VERIFY_LEAF_DISCRIMINATOR: [u8; 8] = [124, 220, 22, 223, 104, 10, 250, 224];
+
+/// Anchor discriminator of the `log_event(data: Vec<u8>)` entrypoint an event
+/// sink program exposes.
+const LOG_EVENT_DISCRIMINATOR: [u8; 8] = [5, 9, 90, 141, 223, 134, 57, 217];
+
+/// Maximum number of Orbs accepted by `feed_orbs_batch`.
+pub const MAX_BATCH_SIZE: usize = 10;
+
+/// Maximum number of chain configs `batch_set_chain_configs` updates at once.
+pub const MAX_CHAIN_CONFIG_BATCH_SIZE: usize = 10;
+
+/// Layout version carried as the first field of every event. Bump it whenever
+/// an event struct changes so indexers can tell layouts apart.
+pub const EVENT_SCHEMA_VERSION: u8 = 12;
+
+/// `ForgeState::layout_version` written by `initialize` and `migrate_state`.
+/// Bump it whenever `ForgeState` gains fields.
+pub const FORGE_LAYOUT_VERSION: u8 = 37;
+
+/// `ClaimRecord::version` written by every feed and by `migrate_claim`.
+/// Bump it whenever `ClaimRecord` gains fields.
+pub const CLAIM_RECORD_VERSION: u8 = 5;
+
+/// `ClaimRecord::LEN` of the last layout without a `version` byte. Every
+/// versioned layout is larger, so a record's size tells the two apart.
+const UNVERSIONED_CLAIM_RECORD_LEN: usize = 151;
+
+/// Minimum time an emergency halt stays in force before it can be cleared.
+pub const EMERGENCY_HALT_TIMELOCK: i64 = 48 * 60 * 60;
+
+/// Largest `ForgeState::clock_skew_tolerance`, so the grace it grants stays
+/// small next to every cooldown and timelock it shortens.
+pub const MAX_CLOCK_SKEW_TOLERANCE: i64 = 60;
+
+/// Length of the window `ForgeState::feeds_this_minute` counts feeds over.
+pub const ANOMALY_WINDOW_SECONDS: i64 = 60;
+
+/// Capacity of `ForgeState::supported_chains`.
+pub const MAX_SUPPORTED_CHAINS: usize = 16;
+
+/// Target chain id of Solana itself. Feeds to it settle in place; every
+/// other target is reached through a Wormhole message.
+pub const SOLANA_CHAIN_ID: u16 = 1;
+
+/// Target chains accepted out of the box: Solana, Optimism and Base.
+pub const DEFAULT_SUPPORTED_CHAINS: [u16; 3] = [SOLANA_CHAIN_ID, 10, 8453];
+
+/// Capacity of `ForgeState::accepted_update_authorities`.
+pub const MAX_UPDATE_AUTHORITIES: usize = 5;
+
+/// Capacity of `ForgeState::governance_signers`, bounded by the width of
+/// `ProposalAccount::approvals`.
+pub const MAX_GOVERNANCE_SIGNERS: usize = 8;
+
+/// Size of the `(user, orb_mint, expiry, rarity_tier)` message `feed_signer` signs.
+pub const FEED_AUTHORIZATION_LEN: usize = 32 + 32 + 8 + 1;
+
+/// Highest rarity tier; tier `n > 0` is charged `ForgeState::tier_thresholds[n - 1]`.
+pub const MAX_RARITY_TIER: u8 = 4;
+
+/// Allowance for source-chain finality, guardian signing and relaying between
+/// a VAA's timestamp and its redemption, on top of `ForgeState::redeemed_ttl`.
+pub const VAA_FINALITY_WINDOW: i64 = 60 * 60;
+
+/// Largest `InitializeParams::pending_queue_size`, keeping the queue within
+/// the 10 KiB an account can be created with.
+pub const MAX_PENDING_QUEUE_SIZE: u16 = 256;
+
+/// Size of `ChainConfig::disabled_reason`.
+pub const MAX_DISABLED_REASON_LEN: usize = 64;
+
+/// Size of the `feed_orb` memo stored on `ClaimRecord::memo`.
+pub const MEMO_LEN: usize = 32;
+
+/// Program release carried in `OrbFedEvent` for traceability.
+pub const FORGE_VERSION: u16 = 1;
+
+/// `OrbFedEvent::route` values.
+pub const ROUTE_SAME_CHAIN: u8 = 0;
+pub const ROUTE_CROSS_CHAIN: u8 = 1;
+
+/// Logs an operational message as `[FORGE][<tag>] ...`, the prefix the log
+/// pipeline greps for. `tag` names the area, e.g. `"FEED"` or `"PAUSE"`.
+macro_rules! forge_log {
+    ($tag:literal, $fmt:literal $(, $arg:expr)* $(,)?) => {
+        msg!(concat!("[FORGE][", $tag, "] ", $fmt) $(, $arg)*)
+    };
+}
+
+/// Seed following the prefix of every forge PDA, so one program can host
+/// several forges. The all-zero namespace adds none, keeping the addresses of
+/// the original single forge.
+pub fn namespace_seed(namespace: &[u8; 16]) -> &[u8] {
+    if *namespace == [0; 16] {
+        &[]
+    } else {
+        namespace
+    }
+}
+
+/// Address and bump of the `[b"forge_state", namespace]` PDA holding `ForgeState`.
+pub fn derive_forge_state_pda(namespace: &[u8; 16]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"forge_state", namespace_seed(namespace)], &ID)
+}
+
+/// Address and bump of the `[b"claim", namespace, orb_mint]` claim record;
+/// compressed Orbs pass their asset id. The record exists once the Orb has
+/// been fed (or seeded by `seed_claim`), and its `claimer` is the user who fed it.
+pub fn derive_claim_pda(namespace: &[u8; 16], orb_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"claim", namespace_seed(namespace), orb_mint.as_ref()],
+        &ID,
+    )
+}
+
+/// Address and bump of the `[b"feeder", namespace, user]` `FeederStats` PDA.
+pub fn derive_feeder_pda(namespace: &[u8; 16], user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"feeder", namespace_seed(namespace), user.as_ref()], &ID)
+}
+
+/// Address and bump of the `[b"deny", namespace, address]` `DenyEntry` PDA,
+/// which exists only while `deny_address` blocks `address`.
+pub fn derive_deny_pda(namespace: &[u8; 16], address: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"deny", namespace_seed(namespace), address.as_ref()], &ID)
+}
+
+/// Address and bump of the `[b"pending_queue", namespace]` `PendingQueue` PDA.
+pub fn derive_pending_queue_pda(namespace: &[u8; 16]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"pending_queue", namespace_seed(namespace)], &ID)
+}
+
+/// Address and bump of the `[b"chain", namespace, chain_id]` `ChainConfig` PDA.
+pub fn derive_chain_config_pda(namespace: &[u8; 16], chain_id: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"chain", namespace_seed(namespace), &chain_id.to_le_bytes()],
+        &ID,
+    )
+}
+
+/// Address and bump of the `[b"escrow", namespace, mint]` escrow token account.
+pub fn derive_escrow_pda(namespace: &[u8; 16], mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"escrow", namespace_seed(namespace), mint.as_ref()], &ID)
+}
+
 #[program]
 pub mod orb_forge {
     use super::*;
 
     pub fn initialize(ctx: Context<Initialize>, params: InitializeParams) -> Result<()> {
+        require!(
+            !ctx.accounts.forge_state.is_initialized,
+            ErrorCode::AlreadyInitialized
+        );
+        require!(params.burn_bps <= MAX_BPS, ErrorCode::InvalidBps);
+        require!(
+            params.rari_threshold > 0 || params.allow_free_feed,
+            ErrorCode::ZeroThreshold
+        );
+        require_valid_window(params.start_ts, params.end_ts)?;
+        require!(
+            params.pending_queue_size > 0 && params.pending_queue_size <= MAX_PENDING_QUEUE_SIZE,
+            ErrorCode::InvalidPendingQueueSize
+        );
+
+        let pending_queue = &mut ctx.accounts.pending_queue;
+        pending_queue.capacity = params.pending_queue_size;
+        pending_queue.claims = Vec::new();
+
         let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.is_initialized = true;
         forge_state.authority = ctx.accounts.authority.key();
         forge_state.wormhole_bridge = params.wormhole_bridge;
         forge_state.rari_mint = params.rari_mint;
         forge_state.rari_threshold = params.rari_threshold;
+        forge_state.approved_collection = params.approved_collection;
+        forge_state.treasury = params.treasury;
+        forge_state.burn_bps = params.burn_bps;
+        forge_state.required_creator = params.required_creator;
+        forge_state.allow_free_feed = params.allow_free_feed;
+        forge_state.start_ts = params.start_ts;
+        forge_state.end_ts = params.end_ts;
         forge_state.total_claimed = 0;
-        forge_state.paused = false;
+        forge_state.total_rari_burned = 0;
+        forge_state.pause_flags = 0;
+        forge_state.pending_authority = None;
+        forge_state.max_feeds_per_window = 0;
+        forge_state.window_seconds = 0;
+        forge_state.close_delay = 0;
+        forge_state.epoch_seconds = 0;
+        forge_state.epoch_cap = 0;
+        forge_state.current_epoch_start = 0;
+        forge_state.current_epoch_count = 0;
+        forge_state.refeed_cooldown = 0;
+        forge_state.merkle_root = [0; 32];
+        forge_state.expiry_seconds = 0;
+        forge_state.rent_recipient = ctx.accounts.authority.key();
+        forge_state.sol_fee_lamports = 0;
+        forge_state.fee_recipient = ctx.accounts.authority.key();
+        forge_state.freeze_orb = false;
+        forge_state.max_total_claims = 0;
+        forge_state.operator = Pubkey::default();
+        forge_state.layout_version = FORGE_LAYOUT_VERSION;
+        forge_state.supported_chains = DEFAULT_SUPPORTED_CHAINS.to_vec();
+        forge_state.escrow_mode = false;
+        forge_state.halted = false;
+        forge_state.halt_cleared_at = 0;
+        forge_state.emergency_authority = Pubkey::default();
+        forge_state.receipt_mint = Pubkey::default();
+        forge_state.user_cooldown_seconds = 0;
+        forge_state.next_claim_index = 0;
+        forge_state.accepted_update_authorities = Vec::new();
+        forge_state.pending_claims = 0;
+        forge_state.redeemed_ttl = 0;
+        forge_state.pruned_through = 0;
+        forge_state.governance_signers = Vec::new();
+        forge_state.governance_threshold = 0;
+        forge_state.governance_nonce = 0;
+        forge_state.next_proposal_id = 0;
+        forge_state.rari_decimals = ctx.accounts.rari_mint.decimals;
+        forge_state.feed_signer = Pubkey::default();
+        forge_state.require_signed_feed = false;
+        forge_state.tier_thresholds = [0; MAX_RARITY_TIER as usize];
+        forge_state.active_claims = 0;
+        forge_state.emitter_seed_version = 0;
+        forge_state.max_feeds_per_orb = 0;
+        forge_state.discount_collection = Pubkey::default();
+        forge_state.discount_bps = 0;
+        forge_state.timelock_seconds = 0;
+        forge_state.pending_threshold = 0;
+        forge_state.threshold_effective_at = 0;
+        forge_state.event_sink = None;
+        forge_state.sink_fatal = false;
+        forge_state.early_bonus_count = 0;
+        forge_state.early_bonus_mint = Pubkey::default();
+        forge_state.early_bonus_amount = 0;
+        forge_state.pause_expires_at = 0;
+        forge_state.namespace = params.namespace;
+        forge_state.charge_sol_fee_same_chain = true;
+        forge_state.clock_skew_tolerance = 0;
+        forge_state.orb_burn_amount = 0;
+        forge_state.orb_burn_replaces_rari = false;
+        forge_state.event_seq = 0;
+        forge_state.require_frozen_orb = false;
+        forge_state.partner = Pubkey::default();
+        forge_state.partner_bps = 0;
+        forge_state.partner_reward_mint = Pubkey::default();
+        forge_state.anomaly_threshold = 0;
+        forge_state.feeds_this_minute = 0;
+        forge_state.minute_start = 0;
+        forge_state.memo_is_text = false;
+        forge_state.counters_seeded = false;
+        forge_state.points_per_feed = 0;
+        forge_state.denied_count = 0;
+        forge_state.require_immutable_metadata = false;
+        forge_state.relayer_fee = 0;
+        forge_state.relayer_fee_vault = Pubkey::default();
+        let seq = forge_state.next_event_seq()?;
+
+        forge_log!(
+            "INIT",
+            "authority={} rari_mint={} layout={}",
+            forge_state.authority,
+            forge_state.rari_mint,
+            FORGE_LAYOUT_VERSION
+        );
+        emit!(SchemaVersionEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+        });
+
         Ok(())
     }
 
-    pub fn feed_orb(ctx: Context<FeedOrb>, chain_id: u16) -> Result<()> {
-        require!(!ctx.accounts.forge_state.paused, ErrorCode::ProgramPaused);
-        
-        // Validate Orb ownership via Metaplex metadata
-        let metadata = &ctx.accounts.orb_metadata;
+    /// `proof` links the Orb mint to `merkle_root`; it is ignored for open drops.
+    /// The feed fails with `BurnExceedsMax` if the threshold it would charge
+    /// exceeds `max_burn`, e.g. after the authority raised it while the
+    /// transaction was in flight; `u64::MAX` disables the guard.
+    /// A nonzero `rarity_tier` must be asserted by a `feed_signer`
+    /// authorization and selects that tier's RARI threshold.
+    /// The claim is credited to `beneficiary`, so an Orb can be fed as a gift;
+    /// the signer still pays the burn and fees and is the one rate limited.
+    /// Passing the signer's own key is an ordinary feed.
+    /// A Token-2022 payment mint with a transfer hook needs the hook program,
+    /// its `[b"extra-account-metas", mint]` PDA and the extra accounts listed
+    /// there in `remaining_accounts`.
+    /// An optional `memo`, e.g. a campaign tag, is stored on the claim record
+    /// and emitted in `OrbFedEvent`; it is zero-filled when absent and must be
+    /// zero-padded UTF-8 while `memo_is_text` is set.
+    pub fn feed_orb<'info>(
+        ctx: Context<'_, '_, '_, 'info, FeedOrb<'info>>,
+        chain_id: u16,
+        proof: Vec<[u8; 32]>,
+        max_burn: u64,
+        rarity_tier: u8,
+        beneficiary: Pubkey,
+        memo: Option<[u8; MEMO_LEN]>,
+    ) -> Result<()> {
+        let user = ctx.accounts.user.key();
+        let orb_mint = ctx.accounts.orb_mint.key();
+        // The rejected feed reverts its own increment, so report the last kept
+        // one. Only handler errors are reported; Anchor rejects failed account
+        // constraints before this runs.
+        let seq = ctx.accounts.forge_state.event_seq;
+        let memo = memo.unwrap_or_default();
+        process_feed_orb(
+            ctx,
+            chain_id,
+            proof,
+            max_burn,
+            rarity_tier,
+            beneficiary,
+            memo,
+        )
+        .inspect_err(|error| emit_feed_rejected(error, seq, user, orb_mint, chain_id))
+    }
+
+    /// Feeds an Orb paying in native SOL, for a forge accepting the native mint
+    /// through `add_payment_mint`. The threshold is wrapped into a temporary
+    /// `[b"wsol", user]` token account, paid from it as any payment mint would
+    /// be, and the account is then closed back to the user, refunding its rent.
+    pub fn feed_orb_with_sol(
+        ctx: Context<FeedOrbWithSol>,
+        chain_id: u16,
+        proof: Vec<[u8; 32]>,
+        max_burn: u64,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.next_event_seq()?;
+        // Freezing needs the Orb token accounts only `feed_orb` carries
         require!(
-            metadata.mint == ctx.accounts.orb_mint.key(),
-            ErrorCode::InvalidOrbMetadata
+            !ctx.accounts.forge_state.freeze_orb,
+            ErrorCode::FreezeNeedsSingleFeed
         );
-        
-        // Burn required $RARI tokens
-        let cpi_accounts = Burn {
-            mint: ctx.accounts.rari_mint.to_account_info(),
-            from: ctx.accounts.user_rari_account.to_account_info(),
-            authority: ctx.accounts.user.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::burn(cpi_ctx, ctx.accounts.forge_state.rari_threshold)?;
-        
-        // Update claim record
-        let claim_record = &mut ctx.accounts.claim_record;
-        claim_record.orb_mint = ctx.accounts.orb_mint.key();
-        claim_record.claimer = ctx.accounts.user.key();
-        claim_record.claimed_at = Clock::get()?.unix_timestamp;
-        claim_record.target_chain = chain_id;
-        
-        // Emit event for indexing
-        emit!(OrbFedEvent {
-            orb_mint: ctx.accounts.orb_mint.key(),
-            claimer: ctx.accounts.user.key(),
-            target_chain: chain_id,
-            rari_burned: ctx.accounts.forge_state.rari_threshold,
-        });
-        
-        // If targeting non-Solana chain, prepare Wormhole message
-        if chain_id != 1 {
-            // Wormhole message emission would go here
-            // This is simplified - actual implementation would use wormhole CPI
-            msg!("Preparing Wormhole message for chain {}", chain_id);
+        require!(
+            ctx.accounts.forge_state.orb_burn_amount == 0,
+            ErrorCode::OrbBurnNeedsSingleFeed
+        );
+        // Nor can it show the Orb is staked
+        require!(
+            !ctx.accounts.forge_state.require_frozen_orb,
+            ErrorCode::OrbNotStaked
+        );
+        require_not_denied(&ctx.accounts.deny_entry)?;
+        require_allowlisted(
+            &ctx.accounts.forge_state,
+            &ctx.accounts.orb_mint.key(),
+            &proof,
+        )?;
+        require_feed_signature(
+            &ctx.accounts.forge_state,
+            None,
+            &ctx.accounts.user.key(),
+            &ctx.accounts.orb_mint.key(),
+            0,
+        )?;
+        create_claim_record(
+            &ctx.accounts.user,
+            &ctx.accounts.system_program,
+            &ctx.accounts.claim_record,
+            &ctx.accounts.forge_state.namespace,
+            &ctx.accounts.orb_mint.key(),
+        )?;
+
+        let threshold = ctx.accounts.payment_mint_config.threshold;
+        ctx.accounts.wrap_sol(threshold)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let emitter_bump = ctx.bumps.wormhole_emitter;
+        let (mut shared, orb) = ctx.accounts.split(emitter_bump, chain_id, max_burn);
+        let mut claim_record = ClaimRecord::default();
+        shared.feed(orb, &mut claim_record, now)?;
+        write_claim_record(&ctx.accounts.claim_record, &claim_record)?;
+
+        ctx.accounts.close_wsol_account()
+    }
+
+    /// Feeds a compressed Orb. Its Bubblegum leaf is rebuilt from `orb`, with
+    /// the user as owner, and verified against `orb.root` in `merkle_tree`
+    /// using the proof nodes passed as remaining accounts. The asset id stands
+    /// in for the Orb mint, seeding the claim record.
+    pub fn feed_compressed_orb<'info>(
+        ctx: Context<'_, '_, '_, 'info, FeedCompressedOrb<'info>>,
+        asset_id: Pubkey,
+        chain_id: u16,
+        proof: Vec<[u8; 32]>,
+        orb: CompressedOrb,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.next_event_seq()?;
+        // Compressed Orbs have no token account to freeze or burn from
+        require!(
+            !ctx.accounts.forge_state.freeze_orb,
+            ErrorCode::FreezeUnsupportedForCompressed
+        );
+        require!(
+            ctx.accounts.forge_state.orb_burn_amount == 0,
+            ErrorCode::OrbBurnUnsupportedForCompressed
+        );
+        // Nor one a staking program could have frozen
+        require!(
+            !ctx.accounts.forge_state.require_frozen_orb,
+            ErrorCode::OrbNotStaked
+        );
+        require_not_denied(&ctx.accounts.deny_entry)?;
+        require_keys_eq!(
+            asset_id,
+            compressed_asset_id(&ctx.accounts.merkle_tree.key(), orb.nonce),
+            ErrorCode::InvalidCompressedOrb
+        );
+        // Seeded records are fed through `feed_orb` only
+        require_unclaimed(&ctx.accounts.claim_record)?;
+        require!(
+            !ctx.accounts.claim_record.prefunded,
+            ErrorCode::OrbAlreadyClaimed
+        );
+        require_allowlisted(&ctx.accounts.forge_state, &asset_id, &proof)?;
+        require_feed_signature(
+            &ctx.accounts.forge_state,
+            None,
+            &ctx.accounts.user.key(),
+            &asset_id,
+            0,
+        )?;
+
+        let leaf = orb.leaf_hash(
+            &asset_id,
+            &ctx.accounts.user.key(),
+            &ctx.accounts.leaf_delegate.key(),
+        )?;
+        verify_compressed_leaf(
+            &ctx.accounts.compression_program,
+            &ctx.accounts.merkle_tree,
+            ctx.remaining_accounts,
+            orb.root,
+            leaf,
+            orb.index,
+        )?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let emitter_bump = ctx.bumps.wormhole_emitter;
+        let (mut shared, orb, claim_record) =
+            ctx.accounts
+                .split(emitter_bump, chain_id, asset_id, &orb.metadata);
+        shared.feed(orb, claim_record, now)?;
+        Ok(())
+    }
+
+    /// Feeds an already-claimed Orb again once `refeed_cooldown` has passed
+    /// since its last feed, reusing its claim record.
+    pub fn refeed_orb(ctx: Context<RefeedOrb>, chain_id: u16) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.next_event_seq()?;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            ctx.accounts.forge_state.is_after(
+                ctx.accounts
+                    .claim_record
+                    .last_fed_at
+                    .checked_add(ctx.accounts.forge_state.refeed_cooldown)
+                    .ok_or(ErrorCode::MathOverflow)?,
+                now
+            ),
+            ErrorCode::RefeedCooldownActive
+        );
+        require!(
+            ctx.accounts
+                .forge_state
+                .allows_orb_feed(ctx.accounts.claim_record.feed_count),
+            ErrorCode::OrbFeedLimitReached
+        );
+        require_not_denied(&ctx.accounts.deny_entry)?;
+
+        let emitter_bump = ctx.bumps.wormhole_emitter;
+        let (mut shared, orb, claim_record) = ctx.accounts.split(emitter_bump, chain_id);
+        shared.feed(orb, claim_record, now)?;
+        Ok(())
+    }
+
+    /// Feeds several Orbs in one instruction. For each entry in
+    /// `target_chains`, `remaining_accounts` carries the Orb mint, its
+    /// metadata, its claim record PDA, the target chain's config PDA and the
+    /// user's deny entry PDA, followed by the Wormhole message PDA when the
+    /// target is not Solana.
+    /// `proofs` holds one allowlist proof per entry.
+    pub fn feed_orbs_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FeedOrbsBatch<'info>>,
+        target_chains: Vec<u16>,
+        proofs: Vec<Vec<[u8; 32]>>,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.next_event_seq()?;
+        require!(
+            target_chains.len() <= MAX_BATCH_SIZE,
+            ErrorCode::BatchTooLarge
+        );
+        require!(
+            proofs.len() == target_chains.len(),
+            ErrorCode::InvalidBatchAccounts
+        );
+
+        // Freezing and burning need per-Orb token accounts the batch layout
+        // doesn't carry
+        require!(
+            !ctx.accounts.forge_state.freeze_orb,
+            ErrorCode::FreezeNeedsSingleFeed
+        );
+        require!(
+            ctx.accounts.forge_state.orb_burn_amount == 0,
+            ErrorCode::OrbBurnNeedsSingleFeed
+        );
+        // Nor can it show the Orb is staked
+        require!(
+            !ctx.accounts.forge_state.require_frozen_orb,
+            ErrorCode::OrbNotStaked
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let emitter_bump = ctx.bumps.wormhole_emitter;
+        let mut shared = ctx.accounts.shared(emitter_bump);
+        let mut remaining = ctx.remaining_accounts.iter();
+
+        for (batch_index, (&chain_id, proof)) in target_chains.iter().zip(&proofs).enumerate() {
+            let batch_index = batch_index as u16;
+            let orb_mint_info = remaining.next().ok_or(ErrorCode::InvalidBatchAccounts)?;
+            let orb_metadata = remaining.next().ok_or(ErrorCode::InvalidBatchAccounts)?;
+            let claim_info = remaining.next().ok_or(ErrorCode::InvalidBatchAccounts)?;
+            let chain_config = remaining.next().ok_or(ErrorCode::InvalidBatchAccounts)?;
+            let deny_entry = remaining.next().ok_or(ErrorCode::InvalidBatchAccounts)?;
+            let wormhole_message = if is_cross_chain(chain_id) {
+                Some(remaining.next().ok_or(ErrorCode::InvalidBatchAccounts)?)
+            } else {
+                None
+            };
+
+            let orb_mint = InterfaceAccount::<Mint>::try_from(orb_mint_info)?.key();
+            require_keys_eq!(
+                deny_entry.key(),
+                derive_deny_pda(&shared.forge_state.namespace, &shared.user.key()).0,
+                ErrorCode::InvalidBatchAccounts
+            );
+            require_not_denied(deny_entry)?;
+            require_allowlisted(shared.forge_state, &orb_mint, proof)?;
+            require_feed_signature(shared.forge_state, None, &shared.user.key(), &orb_mint, 0)?;
+            create_claim_record(
+                shared.payer,
+                shared.system_program,
+                claim_info,
+                &shared.forge_state.namespace,
+                &orb_mint,
+            )?;
+
+            let orb = OrbFeed {
+                orb_mint,
+                claim: claim_info.key(),
+                claimer: shared.user.key(),
+                orb_metadata: OrbMetadataSource::Account(orb_metadata),
+                chain_config,
+                wormhole_message,
+                chain_id,
+                max_burn: u64::MAX,
+                rarity_tier: 0,
+                discount_bps: 0,
+                memo: [0; MEMO_LEN],
+                batch_index,
+            };
+            let mut claim_record = ClaimRecord::default();
+            shared.feed(orb, &mut claim_record, now)?;
+            write_claim_record(claim_info, &claim_record)?;
         }
-        
-        ctx.accounts.forge_state.total_claimed += 1;
-        
+
+        require!(remaining.next().is_none(), ErrorCode::InvalidBatchAccounts);
+
         Ok(())
     }
 
+    /// Flips the `PAUSE_FEED` bit, kept for clients predating `set_pause_flags`.
     pub fn toggle_pause(ctx: Context<TogglePause>) -> Result<()> {
-        ctx.accounts.forge_state.paused = !ctx.accounts.forge_state.paused;
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.pause_flags ^= PAUSE_FEED;
+        forge_state.pause_expires_at = 0;
+        forge_log!("PAUSE", "flags={:#05b}", forge_state.pause_flags);
         Ok(())
     }
 
-    pub fn update_threshold(ctx: Context<UpdateThreshold>, new_threshold: u64) -> Result<()> {
-        ctx.accounts.forge_state.rari_threshold = new_threshold;
+    /// Sets the operator, who may pause and unpause but nothing else.
+    /// `Pubkey::default()` removes the role.
+    pub fn set_operator(ctx: Context<SetOperator>, operator: Pubkey) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.operator = operator;
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + ForgeState::LEN,
-        seeds = [b"forge_state"],
-        bump
-    )]
-    pub forge_state: Account<'info, ForgeState>,
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
+    /// Switches `freeze_orb` mode, in which `feed_orb` also freezes the fed Orb.
+    pub fn set_freeze_orb(ctx: Context<SetFreezeOrb>, freeze_orb: bool) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.freeze_orb = freeze_orb;
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct FeedOrb<'info> {
-    #[account(mut, seeds = [b"forge_state"], bump)]
-    pub forge_state: Account<'info, ForgeState>,
-    
-    #[account(
-        init,
-        payer = user,
-        space = 8 + ClaimRecord::LEN,
-        seeds = [b"claim", orb_mint.key().as_ref()],
-        bump
-    )]
-    pub claim_record: Account<'info, ClaimRecord>,
-    
-    pub orb_mint: Account<'info, Mint>,
-    /// CHECK: Validated via CPI to Metaplex
-    pub orb_metadata: UncheckedAccount<'info>,
-    
-    #[account(mut)]
-    pub rari_mint: Account<'info, Mint>,
-    
-    #[account(
-        mut,
-        constraint = user_rari_account.owner == user.key(),
-        constraint = user_rari_account.mint == rari_mint.key(),
-    )]
-    pub user_rari_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-}
+    /// Blocks `address` from being credited with feeds by creating its
+    /// `[b"deny", address]` entry, which every feed path checks for its
+    /// claimer.
+    pub fn deny_address(ctx: Context<DenyAddress>, address: Pubkey) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let deny_entry = &mut ctx.accounts.deny_entry;
+        deny_entry.address = address;
+        deny_entry.denied_at = Clock::get()?.unix_timestamp;
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.denied_count = forge_state
+            .denied_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        forge_log!(
+            "DENY",
+            "address={} denied={}",
+            address,
+            forge_state.denied_count
+        );
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct TogglePause<'info> {
-    #[account(
-        mut,
-        seeds = [b"forge_state"],
-        bump,
-        has_one = authority
-    )]
-    pub forge_state: Account<'info, ForgeState>,
-    pub authority: Signer<'info>,
-}
+    /// Lifts a `deny_address` block, closing the entry back to the authority.
+    pub fn allow_address(ctx: Context<AllowAddress>, address: Pubkey) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.denied_count = forge_state.denied_count.saturating_sub(1);
+        forge_log!(
+            "DENY",
+            "allowed address={} denied={}",
+            address,
+            forge_state.denied_count
+        );
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct UpdateThreshold<'info> {
-    #[account(
-        mut,
-        seeds = [b"forge_state"],
-        bump,
-        has_one = authority
-    )]
-    pub forge_state: Account<'info, ForgeState>,
-    pub authority: Signer<'info>,
-}
+    /// Switches whether `feed_orb` only accepts Orbs whose token account is
+    /// frozen, as staking programs leave them.
+    pub fn set_require_frozen_orb(
+        ctx: Context<SetRequireFrozenOrb>,
+        require_frozen_orb: bool,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.require_frozen_orb = require_frozen_orb;
+        Ok(())
+    }
 
-#[account]
-pub struct ForgeState {
-    pub authority: Pubkey,
-    pub wormhole_bridge: Pubkey,
-    pub rari_mint: Pubkey,
-    pub rari_threshold: u64,
-    pub total_claimed: u64,
-    pub paused: bool,
-}
+    /// Makes `feed_orb` burn `orb_burn_amount` of the fed Orb's tokens from the
+    /// user, for semi-fungible Orbs with a supply above 1; 0 burns none. With
+    /// `orb_burn_replaces_rari` the Orb burn is the whole payment and no RARI
+    /// threshold is charged.
+    pub fn set_orb_burn(
+        ctx: Context<SetOrbBurn>,
+        orb_burn_amount: u64,
+        orb_burn_replaces_rari: bool,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        // Replacing RARI with a burn of nothing would make feeds free
+        require!(
+            orb_burn_amount > 0 || !orb_burn_replaces_rari,
+            ErrorCode::InvalidOrbBurn
+        );
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.orb_burn_amount = orb_burn_amount;
+        forge_state.orb_burn_replaces_rari = orb_burn_replaces_rari;
+        Ok(())
+    }
 
-impl ForgeState {
-    pub const LEN: usize = 32 + 32 + 32 + 8 + 8 + 1;
-}
+    /// Sets the mint `feed_orb` issues cross-chain receipts from. The mint must
+    /// have `forge_state` as its mint authority; a Token-2022 mint with the
+    /// NonTransferable extension keeps receipts soulbound. Omitting the mint
+    /// stops issuing receipts.
+    pub fn set_receipt_mint(ctx: Context<SetReceiptMint>) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.receipt_mint = ctx
+            .accounts
+            .receipt_mint
+            .as_ref()
+            .map_or(Pubkey::default(), |mint| mint.key());
+        Ok(())
+    }
 
-#[account]
-pub struct ClaimRecord {
-    pub orb_mint: Pubkey,
-    pub claimer: Pubkey,
-    pub claimed_at: i64,
-    pub target_chain: u16,
-}
+    /// Burns the receipt for a claim whose return leg was redeemed through
+    /// `redeem_claim`. Each redeemed VAA consumes at most one receipt.
+    pub fn burn_receipt(ctx: Context<BurnReceipt>, _vaa_hash: [u8; 32]) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        let inbound_claim = &mut ctx.accounts.inbound_claim;
+        require!(
+            !inbound_claim.receipt_burned,
+            ErrorCode::ReceiptAlreadyBurned
+        );
 
-impl ClaimRecord {
-    pub const LEN: usize = 32 + 32 + 8 + 2;
-}
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.receipt_mint.to_account_info(),
+            from: ctx.accounts.claimer_receipt_account.to_account_info(),
+            authority: ctx.accounts.claimer.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token_interface::burn(CpiContext::new(cpi_program, cpi_accounts), 1)?;
+        inbound_claim.receipt_burned = true;
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
-pub struct InitializeParams {
-    pub wormhole_bridge: Pubkey,
-    pub rari_mint: Pubkey,
-    pub rari_threshold: u64,
-}
+        emit!(ReceiptBurned {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            orb_mint: inbound_claim.orb_mint,
+            claimer: inbound_claim.claimer,
+        });
 
-#[event]
-pub struct OrbFedEvent {
-    pub orb_mint: Pubkey,
-    pub claimer: Pubkey,
-    pub target_chain: u16,
-    pub rari_burned: u64,
-}
+        Ok(())
+    }
 
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Program is currently paused")]
-    ProgramPaused,
-    #[msg("Invalid Orb metadata")]
-    InvalidOrbMetadata,
-    #[msg("Insufficient RARI balance")]
-    InsufficientRariBalance,
-}
\ No newline at end of file
+    /// Thaws an Orb frozen by `freeze_orb` mode.
+    pub fn unfreeze_orb(ctx: Context<UnfreezeOrb>) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let accounts = &ctx.accounts;
+        OrbFreezeAccounts {
+            namespace_seed: accounts.forge_state.namespace_seed(),
+            metadata_program: accounts.token_metadata_program.as_ref(),
+            freeze_authority: accounts.orb_freeze_authority.as_ref(),
+            token_account: accounts.orb_token_account.as_ref(),
+            edition: accounts.orb_edition.as_ref(),
+            mint: accounts.orb_mint.as_ref(),
+            token_program: accounts.orb_token_program.as_ref(),
+        }
+        .invoke(false, ctx.bumps.orb_freeze_authority)
+    }
+
+    pub fn set_pause_flags(ctx: Context<SetPauseFlags>, flags: u8) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.pause_flags = flags;
+        forge_state.pause_expires_at = 0;
+        forge_log!("PAUSE", "flags={:#05b}", flags);
+        Ok(())
+    }
+
+    /// Pauses feeding for `duration` seconds, after which feeds resume even
+    /// though the `PAUSE_FEED` bit is still set. A duration of 0 pauses until
+    /// unpaused by hand.
+    pub fn pause_for(ctx: Context<SetPauseFlags>, duration: i64) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require!(duration >= 0, ErrorCode::InvalidPauseDuration);
+        let now = Clock::get()?.unix_timestamp;
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.pause_flags |= PAUSE_FEED;
+        forge_state.pause_expires_at = if duration == 0 {
+            0
+        } else {
+            now.checked_add(duration).ok_or(ErrorCode::MathOverflow)?
+        };
+        forge_log!(
+            "PAUSE",
+            "flags={:#05b} expires_at={}",
+            forge_state.pause_flags,
+            forge_state.pause_expires_at
+        );
+        Ok(())
+    }
+
+    /// Sets how many feeds a minute trip the circuit breaker, which sets the
+    /// `PAUSE_FEED` bit until cleared by hand. 0 disables it.
+    pub fn set_anomaly_threshold(
+        ctx: Context<SetAnomalyThreshold>,
+        anomaly_threshold: u64,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.anomaly_threshold = anomaly_threshold;
+        Ok(())
+    }
+
+    /// Switches whether feeds only accept Orbs whose metadata is immutable, so
+    /// the collection and creators checked can't be changed after the feed.
+    pub fn set_require_immutable_metadata(
+        ctx: Context<SetRequireImmutableMetadata>,
+        require_immutable_metadata: bool,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.require_immutable_metadata = require_immutable_metadata;
+        Ok(())
+    }
+
+    /// Switches whether `feed_orb` memos must be zero-padded UTF-8 text or are
+    /// stored as opaque bytes.
+    pub fn set_memo_is_text(ctx: Context<SetMemoIsText>, memo_is_text: bool) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.memo_is_text = memo_is_text;
+        Ok(())
+    }
+
+    /// Carries the counters of a legacy forge over to this one, so claim
+    /// numbering continues where the old deployment left off. Usable once.
+    pub fn set_counters(
+        ctx: Context<SetCounters>,
+        total_claimed: u64,
+        total_rari_burned: u64,
+        next_claim_index: u64,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        ctx.accounts.forge_state.seed_counters(
+            total_claimed,
+            total_rari_burned,
+            next_claim_index,
+        )?;
+        emit!(CountersSeeded {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            total_claimed,
+            total_rari_burned,
+            next_claim_index,
+        });
+        Ok(())
+    }
+
+    /// Sets the loyalty points `feed_orb` accrues to the payer's
+    /// `[b"points", user]` account per feed; 0 stops accruing.
+    pub fn set_points_per_feed(ctx: Context<SetPointsPerFeed>, points_per_feed: u64) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.points_per_feed = points_per_feed;
+        Ok(())
+    }
+
+    /// Zeroes the user's points balance and emits `PointsRedeemed` for
+    /// off-chain fulfillment; nothing is paid out on-chain.
+    pub fn redeem_points(ctx: Context<RedeemPoints>) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        let points = std::mem::take(&mut ctx.accounts.points_account.points);
+        require!(points > 0, ErrorCode::NothingToRedeem);
+        emit!(PointsRedeemed {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            user: ctx.accounts.user.key(),
+            points,
+        });
+        Ok(())
+    }
+
+    pub fn update_threshold(ctx: Context<UpdateThreshold>, new_threshold: u64) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require_no_governance(&ctx.accounts.forge_state)?;
+        require!(
+            new_threshold > 0 || ctx.accounts.forge_state.allow_free_feed,
+            ErrorCode::ZeroThreshold
+        );
+        require!(
+            ctx.accounts.rari_mint.decimals == ctx.accounts.forge_state.rari_decimals,
+            ErrorCode::RariDecimalsMismatch
+        );
+        // With a timelock set, changes go through `propose_threshold`
+        require!(
+            ctx.accounts.forge_state.timelock_seconds == 0,
+            ErrorCode::ThresholdTimelocked
+        );
+        ctx.accounts.forge_state.rari_threshold = new_threshold;
+        Ok(())
+    }
+
+    /// Queues `new_threshold` to take effect `timelock_seconds` from now,
+    /// replacing any change already queued. Feeds keep paying the current
+    /// threshold until `apply_threshold` promotes it.
+    pub fn propose_threshold(ctx: Context<ProposeThreshold>, new_threshold: u64) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        require_no_governance(&ctx.accounts.forge_state)?;
+        require!(
+            new_threshold > 0 || ctx.accounts.forge_state.allow_free_feed,
+            ErrorCode::ZeroThreshold
+        );
+        require!(
+            ctx.accounts.rari_mint.decimals == ctx.accounts.forge_state.rari_decimals,
+            ErrorCode::RariDecimalsMismatch
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.propose_threshold(new_threshold, now)?;
+        emit!(ThresholdProposed {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            current_threshold: forge_state.rari_threshold,
+            pending_threshold: new_threshold,
+            effective_at: forge_state.threshold_effective_at,
+        });
+        Ok(())
+    }
+
+    /// Promotes the threshold queued by `propose_threshold` once its timelock
+    /// has passed.
+    pub fn apply_threshold(ctx: Context<ApplyThreshold>) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        let now = Clock::get()?.unix_timestamp;
+        let forge_state = &mut ctx.accounts.forge_state;
+        let previous_threshold = forge_state.rari_threshold;
+        forge_state.apply_threshold(now)?;
+        emit!(ThresholdApplied {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            previous_threshold,
+            rari_threshold: forge_state.rari_threshold,
+        });
+        Ok(())
+    }
+
+    /// Sets how long a proposed threshold waits before it can be applied;
+    /// while nonzero, `update_threshold` is refused.
+    pub fn update_timelock(ctx: Context<UpdateTimelock>, timelock_seconds: i64) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require!(timelock_seconds >= 0, ErrorCode::InvalidTimelock);
+        ctx.accounts.forge_state.timelock_seconds = timelock_seconds;
+        Ok(())
+    }
+
+    /// Opts in to (or out of) a zero `rari_threshold`. Free feeding cannot be
+    /// turned off while the threshold is still zero.
+    pub fn set_free_feed(ctx: Context<SetFreeFeed>, allow_free_feed: bool) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let forge_state = &mut ctx.accounts.forge_state;
+        require!(
+            forge_state.rari_threshold > 0 || allow_free_feed,
+            ErrorCode::ZeroThreshold
+        );
+        forge_state.allow_free_feed = allow_free_feed;
+        Ok(())
+    }
+
+    /// Sets the per-user feed limit. A `max_feeds_per_window` of 0 means unlimited.
+    pub fn update_rate_limit(
+        ctx: Context<UpdateRateLimit>,
+        max_feeds_per_window: u64,
+        window_seconds: i64,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require!(window_seconds >= 0, ErrorCode::InvalidDuration);
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.max_feeds_per_window = max_feeds_per_window;
+        forge_state.window_seconds = window_seconds;
+        Ok(())
+    }
+
+    /// Sets the minimum time between any two feeds by the same user. 0 disables it.
+    pub fn update_user_cooldown(
+        ctx: Context<UpdateUserCooldown>,
+        user_cooldown_seconds: i64,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require!(user_cooldown_seconds >= 0, ErrorCode::InvalidDuration);
+        ctx.accounts.forge_state.user_cooldown_seconds = user_cooldown_seconds;
+        Ok(())
+    }
+
+    /// Sets the drop window feeds are accepted in. 0 disables either bound.
+    pub fn update_window(ctx: Context<UpdateWindow>, start_ts: i64, end_ts: i64) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require_valid_window(start_ts, end_ts)?;
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.start_ts = start_ts;
+        forge_state.end_ts = end_ts;
+        Ok(())
+    }
+
+    /// Sets the global feed cap per epoch. An `epoch_cap` of 0 means unlimited.
+    pub fn update_epoch_config(
+        ctx: Context<UpdateEpochConfig>,
+        epoch_seconds: i64,
+        epoch_cap: u64,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require!(epoch_seconds >= 0, ErrorCode::InvalidDuration);
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.epoch_seconds = epoch_seconds;
+        forge_state.epoch_cap = epoch_cap;
+        Ok(())
+    }
+
+    /// Sets the lifetime cap on `total_claimed`; 0 removes it.
+    pub fn set_max_total_claims(
+        ctx: Context<SetMaxTotalClaims>,
+        max_total_claims: u64,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.max_total_claims = max_total_claims;
+        Ok(())
+    }
+
+    /// Replaces the set of target chains `feed_orb` accepts.
+    pub fn update_supported_chains(
+        ctx: Context<UpdateSupportedChains>,
+        chains: Vec<u16>,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require!(
+            chains.len() <= MAX_SUPPORTED_CHAINS,
+            ErrorCode::TooManySupportedChains
+        );
+        ctx.accounts.forge_state.supported_chains = chains;
+        Ok(())
+    }
+
+    /// Accepts Orbs whose metadata has `update_authority`. While any are set,
+    /// `feed_orb` rejects Orbs under any other update authority.
+    pub fn add_update_authority(
+        ctx: Context<AddUpdateAuthority>,
+        update_authority: Pubkey,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts
+            .forge_state
+            .add_update_authority(update_authority)
+    }
+
+    /// Stops accepting `update_authority`. Removing the last one disables the check.
+    pub fn remove_update_authority(
+        ctx: Context<RemoveUpdateAuthority>,
+        update_authority: Pubkey,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts
+            .forge_state
+            .remove_update_authority(&update_authority)
+    }
+
+    pub fn update_fee_split(ctx: Context<UpdateFeeSplit>, burn_bps: u16) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require_no_governance(&ctx.accounts.forge_state)?;
+        require!(burn_bps <= MAX_BPS, ErrorCode::InvalidBps);
+        ctx.accounts.forge_state.burn_bps = burn_bps;
+        Ok(())
+    }
+
+    /// Gives holders of an NFT verified into `discount_collection` a
+    /// `discount_bps` cut of the threshold on `feed_orb`.
+    /// `Pubkey::default()` disables the discount.
+    pub fn update_discount(
+        ctx: Context<UpdateDiscount>,
+        discount_collection: Pubkey,
+        discount_bps: u16,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require_no_governance(&ctx.accounts.forge_state)?;
+        require!(discount_bps <= MAX_BPS, ErrorCode::InvalidBps);
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.discount_collection = discount_collection;
+        forge_state.discount_bps = discount_bps;
+        Ok(())
+    }
+
+    /// Sets the creator every Orb must carry as verified. `Pubkey::default()` disables the check.
+    pub fn update_required_creator(
+        ctx: Context<UpdateRequiredCreator>,
+        required_creator: Pubkey,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.required_creator = required_creator;
+        Ok(())
+    }
+
+    /// Sets the SOL fee charged per fed Orb and who receives it. A fee of 0 disables it.
+    pub fn update_sol_fee(
+        ctx: Context<UpdateSolFee>,
+        sol_fee_lamports: u64,
+        fee_recipient: Pubkey,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require_no_governance(&ctx.accounts.forge_state)?;
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.sol_fee_lamports = sol_fee_lamports;
+        forge_state.fee_recipient = fee_recipient;
+        Ok(())
+    }
+
+    /// Sets the grace, in seconds, every time-window check allows for validator
+    /// clock skew. See `ForgeState::is_after` for what it weakens.
+    pub fn set_clock_skew_tolerance(
+        ctx: Context<SetClockSkewTolerance>,
+        clock_skew_tolerance: i64,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require!(
+            (0..=MAX_CLOCK_SKEW_TOLERANCE).contains(&clock_skew_tolerance),
+            ErrorCode::InvalidClockSkewTolerance
+        );
+        ctx.accounts.forge_state.clock_skew_tolerance = clock_skew_tolerance;
+        Ok(())
+    }
+
+    /// Sets whether same-chain feeds pay the SOL fee, which funds relaying
+    /// that only cross-chain feeds need.
+    pub fn set_charge_sol_fee_same_chain(
+        ctx: Context<SetChargeSolFeeSameChain>,
+        charge_sol_fee_same_chain: bool,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.charge_sol_fee_same_chain = charge_sol_fee_same_chain;
+        Ok(())
+    }
+
+    /// Hands threshold and fee changes to `threshold`-of-`signers` governance.
+    /// From then on they, and the signer set itself, only change through
+    /// `propose_change` and `approve_change`. Chain and tier thresholds,
+    /// payment mints and the holder discount, which also set what feeds
+    /// charge, stay locked while governance is on.
+    pub fn set_governance(
+        ctx: Context<SetGovernance>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require_no_governance(&ctx.accounts.forge_state)?;
+        require!(threshold > 0, ErrorCode::InvalidGovernance);
+        ctx.accounts.forge_state.apply_change(&ProposedChange::Governance {
+            signers,
+            threshold,
+        })
+    }
+
+    /// Opens proposal `next_proposal_id` for `change`, counting the proposer's
+    /// approval. The change applies at once if that meets the threshold.
+    pub fn propose_change(ctx: Context<ProposeChange>, change: ProposedChange) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        let forge_state = &mut ctx.accounts.forge_state;
+        require!(
+            forge_state.governance_threshold > 0,
+            ErrorCode::GovernanceDisabled
+        );
+        change.validate()?;
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.proposal_id = forge_state.next_proposal_id;
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.change = change;
+        proposal.approvals = 0;
+        proposal.governance_nonce = forge_state.governance_nonce;
+        proposal.executed = false;
+        proposal.approved_at = 0;
+        forge_state.next_proposal_id = forge_state
+            .next_proposal_id
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        proposal.approve(forge_state, &ctx.accounts.proposer.key(), now)?;
+        emit_proposal_approved(proposal, seq);
+        Ok(())
+    }
+
+    /// Adds a governance signer's approval to a proposal, applying its change
+    /// once `governance_threshold` approvals are in. A threshold change then
+    /// waits out `timelock_seconds` for `execute_change` instead.
+    pub fn approve_change(ctx: Context<ApproveChange>, _proposal_id: u64) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        let now = Clock::get()?.unix_timestamp;
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.approve(
+            &mut ctx.accounts.forge_state,
+            &ctx.accounts.signer.key(),
+            now,
+        )?;
+        emit_proposal_approved(proposal, seq);
+        Ok(())
+    }
+
+    /// Applies an approved threshold change once `timelock_seconds` have
+    /// passed since its approval. Permissionless.
+    pub fn execute_change(ctx: Context<ExecuteChange>, _proposal_id: u64) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        let now = Clock::get()?.unix_timestamp;
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.execute(&mut ctx.accounts.forge_state, now)?;
+        emit_proposal_approved(proposal, seq);
+        Ok(())
+    }
+
+    pub fn update_refeed_cooldown(
+        ctx: Context<UpdateRefeedCooldown>,
+        refeed_cooldown: i64,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require!(refeed_cooldown >= 0, ErrorCode::InvalidDuration);
+        ctx.accounts.forge_state.refeed_cooldown = refeed_cooldown;
+        Ok(())
+    }
+
+    /// Caps how many times a single Orb may be fed, re-feeds included, past
+    /// which `refeed_orb` refuses it; 0 removes the cap.
+    pub fn update_max_feeds_per_orb(
+        ctx: Context<UpdateMaxFeedsPerOrb>,
+        max_feeds_per_orb: u16,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.max_feeds_per_orb = max_feeds_per_orb;
+        Ok(())
+    }
+
+    /// Restricts feeds to Orbs under `merkle_root`. An all-zero root opens feeding to any Orb.
+    pub fn update_merkle_root(ctx: Context<UpdateMerkleRoot>, merkle_root: [u8; 32]) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.merkle_root = merkle_root;
+        Ok(())
+    }
+
+    /// Sets the backend key that signs feed authorizations, and whether
+    /// `feed_orb` requires one. Other feed paths are closed while it does.
+    pub fn set_feed_signer(
+        ctx: Context<SetFeedSigner>,
+        feed_signer: Pubkey,
+        require_signed_feed: bool,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require!(
+            !require_signed_feed || feed_signer != Pubkey::default(),
+            ErrorCode::InvalidFeedSigner
+        );
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.feed_signer = feed_signer;
+        forge_state.require_signed_feed = require_signed_feed;
+        Ok(())
+    }
+
+    /// Sets the RARI thresholds of rarity tiers 1 to `MAX_RARITY_TIER`. A zero
+    /// entry charges that tier the base threshold.
+    pub fn update_tier_thresholds(
+        ctx: Context<UpdateTierThresholds>,
+        tier_thresholds: [u64; MAX_RARITY_TIER as usize],
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require_no_governance(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.tier_thresholds = tier_thresholds;
+        Ok(())
+    }
+
+    /// Moves outbound messages to a fresh Wormhole emitter derived from the
+    /// next `emitter_seed_version`. The core bridge needs no registration and
+    /// creates the new emitter's sequence tracker on its first post, but
+    /// receiving chains must trust the new emitter before its messages land.
+    pub fn rotate_emitter(ctx: Context<RotateEmitter>) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        let forge_state = &mut ctx.accounts.forge_state;
+        let previous_emitter = forge_state.emitter_address();
+        forge_state.emitter_seed_version = forge_state
+            .emitter_seed_version
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        forge_log!(
+            "EMITTER",
+            "version={} emitter={}",
+            forge_state.emitter_seed_version,
+            forge_state.emitter_address()
+        );
+        emit!(EmitterRotated {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            emitter_seed_version: forge_state.emitter_seed_version,
+            previous_emitter,
+            emitter: forge_state.emitter_address(),
+        });
+
+        Ok(())
+    }
+
+    pub fn update_close_delay(ctx: Context<UpdateCloseDelay>, close_delay: i64) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require!(close_delay >= 0, ErrorCode::InvalidDuration);
+        ctx.accounts.forge_state.close_delay = close_delay;
+        Ok(())
+    }
+
+    /// Sets how long `[b"redeemed", vaa_hash]` markers outlive
+    /// `VAA_FINALITY_WINDOW` before `prune_redeemed` may close them. 0 keeps
+    /// them forever.
+    pub fn update_redeemed_ttl(ctx: Context<UpdateRedeemedTtl>, redeemed_ttl: i64) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require!(redeemed_ttl >= 0, ErrorCode::InvalidRedeemedTtl);
+        ctx.accounts.forge_state.redeemed_ttl = redeemed_ttl;
+        Ok(())
+    }
+
+    /// Sets the threshold override for a target chain, or disables feeding to it.
+    pub fn set_chain_config(
+        ctx: Context<SetChainConfig>,
+        chain_id: u16,
+        threshold: u64,
+        enabled: bool,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require_no_governance(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        let chain_config = &mut ctx.accounts.chain_config;
+        chain_config.apply(chain_id, threshold, enabled)?;
+        emit!(chain_config.updated_event(seq));
+        Ok(())
+    }
+
+    /// Applies `set_chain_config` to every entry of `updates` in one
+    /// instruction, so no partial reconfiguration is ever visible. Remaining
+    /// accounts are the writable `[b"chain", chain_id]` PDAs, one per entry
+    /// in order; missing configs are created. Any bad entry fails them all.
+    pub fn batch_set_chain_configs<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchSetChainConfigs<'info>>,
+        updates: Vec<ChainConfigUpdate>,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require_no_governance(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        require!(
+            updates.len() <= MAX_CHAIN_CONFIG_BATCH_SIZE,
+            ErrorCode::BatchTooLarge
+        );
+        require!(
+            ctx.remaining_accounts.len() == updates.len(),
+            ErrorCode::InvalidBatchAccounts
+        );
+
+        let namespace = ctx.accounts.forge_state.namespace;
+        for (update, info) in updates.iter().zip(ctx.remaining_accounts) {
+            let (expected, bump) = derive_chain_config_pda(&namespace, update.chain_id);
+            require_keys_eq!(info.key(), expected, ErrorCode::InvalidChainConfig);
+            require!(info.is_writable, ErrorCode::InvalidBatchAccounts);
+
+            let mut chain_config = if info.data_is_empty() {
+                create_chain_config(
+                    &ctx.accounts.authority,
+                    &ctx.accounts.system_program,
+                    info,
+                    &namespace,
+                    update.chain_id,
+                    bump,
+                )?;
+                ChainConfig {
+                    chain_id: update.chain_id,
+                    threshold: 0,
+                    enabled: false,
+                    disabled_reason: [0; MAX_DISABLED_REASON_LEN],
+                    destination_mint: [0; 32],
+                }
+            } else {
+                Account::<ChainConfig>::try_from(info)?.into_inner()
+            };
+            chain_config.apply(update.chain_id, update.threshold, update.enabled)?;
+            let mut data = info.try_borrow_mut_data()?;
+            let mut writer: &mut [u8] = &mut data;
+            chain_config.try_serialize(&mut writer)?;
+            emit!(chain_config.updated_event(seq));
+        }
+        Ok(())
+    }
+
+    /// Registers the canonical token on `chain_id` that cross-chain feeds ask
+    /// the receiving side to mint or unlock. A cross-chain target needs one
+    /// before `set_chain_config` can enable it.
+    pub fn set_destination_mint(
+        ctx: Context<SetDestinationMint>,
+        chain_id: u16,
+        destination_mint: [u8; 32],
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        let chain_config = &mut ctx.accounts.chain_config;
+        chain_config.chain_id = chain_id;
+        chain_config.destination_mint = destination_mint;
+        chain_config.require_destination_mint()?;
+        emit!(chain_config.updated_event(seq));
+        Ok(())
+    }
+
+    /// Disables feeding to `chain_id`, recording a UTF-8 `reason` of up to
+    /// `MAX_DISABLED_REASON_LEN` bytes for clients to show. `set_chain_config`
+    /// re-enables the chain.
+    pub fn pause_chain(ctx: Context<PauseChain>, chain_id: u16, reason: Vec<u8>) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let disabled_reason = encode_disabled_reason(&reason)?;
+        let chain_config = &mut ctx.accounts.chain_config;
+        chain_config.chain_id = chain_id;
+        chain_config.enabled = false;
+        chain_config.disabled_reason = disabled_reason;
+        forge_log!(
+            "CHAIN",
+            "chain {} disabled: {}",
+            chain_id,
+            chain_config.disabled_reason()
+        );
+        Ok(())
+    }
+
+    /// Removes a chain's override so it falls back to the global threshold.
+    pub fn remove_chain_config(ctx: Context<RemoveChainConfig>, _chain_id: u16) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        Ok(())
+    }
+
+    /// Pre-creates the claim record of `orb_mint` for `claimer`, e.g. for an
+    /// airdrop. The claimer's first `feed_orb` of the Orb is prefunded and
+    /// charges no threshold.
+    pub fn seed_claim(ctx: Context<SeedClaim>, orb_mint: Pubkey, claimer: Pubkey) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        let claim_record = &mut ctx.accounts.claim_record;
+        claim_record.version = CLAIM_RECORD_VERSION;
+        claim_record.orb_mint = orb_mint;
+        claim_record.claimer = claimer;
+        claim_record.prefunded = true;
+
+        forge_log!("CLAIM", "seeded orb={} claimer={}", orb_mint, claimer);
+        emit!(ClaimSeeded {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            orb_mint,
+            claimer,
+        });
+
+        Ok(())
+    }
+
+    /// Recreates the claim record of a still pending cross-chain claim that was
+    /// closed by mistake, from its historical values. The record is marked
+    /// `reissued` and counted as pending and active again, as before its close.
+    /// `burn_proof` must commit to the restored feed.
+    pub fn reissue_claim(ctx: Context<ReissueClaim>, params: ReissueClaimParams) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let ReissueClaimParams {
+            orb_mint,
+            claimer,
+            claimed_at,
+            target_chain,
+            wormhole_sequence,
+            feed_count,
+            last_fed_at,
+            name_hash,
+            claim_index,
+            rari_burned,
+            burn_proof,
+            memo,
+            rarity_tier,
+            discount_bps,
+        } = params;
+        require!(
+            is_cross_chain(target_chain),
+            ErrorCode::ReissueNeedsCrossChain
+        );
+        require!(feed_count > 0, ErrorCode::ClaimNotPending);
+        require!(rarity_tier <= MAX_RARITY_TIER, ErrorCode::InvalidRarityTier);
+        require!(
+            burn_proof
+                == burn_proof_for(&orb_mint, &claimer, rari_burned, claimed_at, target_chain),
+            ErrorCode::BurnProofMismatch
+        );
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        let claim_record = &mut ctx.accounts.claim_record;
+        claim_record.version = CLAIM_RECORD_VERSION;
+        claim_record.orb_mint = orb_mint;
+        claim_record.claimer = claimer;
+        claim_record.claimed_at = claimed_at;
+        claim_record.target_chain = target_chain;
+        claim_record.wormhole_sequence = wormhole_sequence;
+        claim_record.feed_count = feed_count;
+        claim_record.last_fed_at = last_fed_at;
+        claim_record.name_hash = name_hash;
+        claim_record.status = ClaimStatus::Pending;
+        claim_record.claim_index = claim_index;
+        claim_record.rari_burned = rari_burned;
+        claim_record.burn_proof = burn_proof;
+        claim_record.memo = memo;
+        claim_record.reissued = true;
+        claim_record.rarity_tier = rarity_tier;
+        claim_record.discount_bps = discount_bps;
+
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.add_pending_claim()?;
+        forge_state.add_active_claim()?;
+        ctx.accounts.pending_queue.push(claim_record.key())?;
+
+        forge_log!(
+            "CLAIM",
+            "reissued orb={} claimer={} chain={}",
+            orb_mint,
+            claimer,
+            target_chain
+        );
+        emit!(ClaimReissued {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            orb_mint,
+            claimer,
+            claimed_at,
+            target_chain,
+            burn_proof,
+        });
+
+        Ok(())
+    }
+
+    /// Closes a claim record once `close_delay` has elapsed, refunding rent to the claimer.
+    pub fn close_claim(ctx: Context<CloseClaim>) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        require!(
+            !ctx.accounts.forge_state.is_paused(PAUSE_CLOSE),
+            ErrorCode::ProgramPaused
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            ctx.accounts.forge_state.is_after(
+                ctx.accounts
+                    .claim_record
+                    .claimed_at
+                    .checked_add(ctx.accounts.forge_state.close_delay)
+                    .and_then(|ts| ts.checked_add(1))
+                    .ok_or(ErrorCode::MathOverflow)?,
+                now
+            ),
+            ErrorCode::CloseDelayActive
+        );
+        let claim_record = &ctx.accounts.claim_record;
+        let forge_state = &mut ctx.accounts.forge_state;
+        if claim_record.counts_as_pending() {
+            forge_state.release_pending_claim();
+            ctx.accounts.pending_queue.remove(&claim_record.key());
+        }
+        // A seeded record that was never fed was never counted
+        if claim_record.feed_count > 0 {
+            forge_state.release_active_claim()?;
+        }
+
+        forge_log!(
+            "CLAIM",
+            "closed orb={} active_claims={}",
+            claim_record.orb_mint,
+            forge_state.active_claims
+        );
+        emit!(ForgeStatsUpdated {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            total_claimed: forge_state.total_claimed,
+            total_rari_burned: forge_state.total_rari_burned,
+            active_claims: forge_state.active_claims,
+        });
+
+        Ok(())
+    }
+
+    /// Sets how long a cross-chain claim may stay `Pending` before the
+    /// authority can expire it, and who receives the reclaimed rent.
+    pub fn update_expiry_config(
+        ctx: Context<UpdateExpiryConfig>,
+        expiry_seconds: i64,
+        rent_recipient: Pubkey,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require!(expiry_seconds >= 0, ErrorCode::InvalidDuration);
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.expiry_seconds = expiry_seconds;
+        forge_state.rent_recipient = rent_recipient;
+        Ok(())
+    }
+
+    /// Switches escrow mode, in which feeds hold their RARI in the forge escrow
+    /// until the claimer settles or cancels them.
+    pub fn set_escrow_mode(ctx: Context<SetEscrowMode>, escrow_mode: bool) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.escrow_mode = escrow_mode;
+        Ok(())
+    }
+
+    /// Points `feed_orb` at a program whose `log_event` entrypoint receives
+    /// every `OrbFedEvent`; `None` stops forwarding. With `sink_fatal` unset,
+    /// a sink that cannot be called is skipped instead of failing the feed.
+    pub fn set_event_sink(
+        ctx: Context<SetEventSink>,
+        event_sink: Option<Pubkey>,
+        sink_fatal: bool,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.event_sink = event_sink;
+        forge_state.sink_fatal = sink_fatal;
+        Ok(())
+    }
+
+    /// Grants `early_bonus_amount` of the bonus mint to the claimer of each of
+    /// the first `early_bonus_count` Orbs fed. The mint must have `forge_state`
+    /// as its mint authority; omitting it stops the bonus.
+    pub fn set_early_bonus(
+        ctx: Context<SetEarlyBonus>,
+        early_bonus_count: u64,
+        early_bonus_amount: u64,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let early_bonus_mint = ctx
+            .accounts
+            .early_bonus_mint
+            .as_ref()
+            .map_or(Pubkey::default(), |mint| mint.key());
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.early_bonus_count = early_bonus_count;
+        forge_state.early_bonus_mint = early_bonus_mint;
+        forge_state.early_bonus_amount = early_bonus_amount;
+        Ok(())
+    }
+
+    /// Shares each `feed_orb` with `partner` by minting it `partner_bps` of the
+    /// RARI threshold in the partner reward mint, which must have `forge_state`
+    /// as its mint authority; omitting the mint stops the share.
+    pub fn set_partner_share(
+        ctx: Context<SetPartnerShare>,
+        partner: Pubkey,
+        partner_bps: u16,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require!(partner_bps <= MAX_BPS, ErrorCode::InvalidBps);
+        let partner_reward_mint = ctx
+            .accounts
+            .partner_reward_mint
+            .as_ref()
+            .map_or(Pubkey::default(), |mint| mint.key());
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.partner = partner;
+        forge_state.partner_bps = partner_bps;
+        forge_state.partner_reward_mint = partner_reward_mint;
+        Ok(())
+    }
+
+    /// Creates the forge-owned RARI escrow token account used in escrow mode.
+    pub fn init_escrow(ctx: Context<InitEscrow>) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)
+    }
+
+    /// Creates the forge-owned RARI vault that collects `relayer_fee`.
+    pub fn init_relayer_fee_vault(ctx: Context<InitRelayerFeeVault>) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.relayer_fee_vault = ctx.accounts.relayer_fee_vault.key();
+        Ok(())
+    }
+
+    /// Sets the RARI each cross-chain feed pays into the relayer fee vault on
+    /// top of its threshold; 0 charges none.
+    pub fn set_relayer_fee(ctx: Context<SetRelayerFee>, relayer_fee: u64) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require!(
+            relayer_fee == 0 || ctx.accounts.forge_state.relayer_fee_vault != Pubkey::default(),
+            ErrorCode::RelayerFeeVaultMissing
+        );
+        ctx.accounts.forge_state.relayer_fee = relayer_fee;
+        Ok(())
+    }
+
+    /// Registers `relayer` as allowed to claim from the relayer fee vault.
+    pub fn register_relayer(ctx: Context<RegisterRelayer>, relayer: Pubkey) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.relayer_entry.relayer = relayer;
+        forge_log!("RELAYER", "registered relayer={}", relayer);
+        Ok(())
+    }
+
+    /// Deregisters `relayer`, closing its entry back to the authority.
+    pub fn remove_relayer(ctx: Context<RemoveRelayer>, relayer: Pubkey) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        forge_log!("RELAYER", "removed relayer={}", relayer);
+        Ok(())
+    }
+
+    /// Pays `amount` of the collected relayer fees out to a registered relayer.
+    pub fn claim_relayer_fee(ctx: Context<ClaimRelayerFee>, amount: u64) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+
+        let bump = ctx.bumps.forge_state;
+        let namespace = ctx.accounts.forge_state.namespace;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"forge_state", namespace_seed(&namespace), &[bump]]];
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.relayer_fee_vault.to_account_info(),
+            mint: ctx.accounts.rari_mint.to_account_info(),
+            to: ctx.accounts.relayer_rari_account.to_account_info(),
+            authority: ctx.accounts.forge_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.rari_mint.decimals)?;
+
+        let relayer_entry = &mut ctx.accounts.relayer_entry;
+        relayer_entry.claimed = relayer_entry
+            .claimed
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        forge_log!(
+            "RELAYER",
+            "claimed relayer={} amount={}",
+            relayer_entry.relayer,
+            amount
+        );
+        emit!(RelayerFeeClaimed {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            relayer: relayer_entry.relayer,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Burns an escrowed feed's RARI, splitting it with the treasury as
+    /// `feed_orb` would have, and confirms the claim.
+    pub fn settle_feed(ctx: Context<SettleFeed>) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        let claim_record = &mut ctx.accounts.claim_record;
+        require!(
+            claim_record.status == ClaimStatus::Escrowed,
+            ErrorCode::ClaimNotEscrowed
+        );
+
+        let bump = ctx.bumps.forge_state;
+        let namespace = ctx.accounts.forge_state.namespace;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"forge_state", namespace_seed(&namespace), &[bump]]];
+        let (burn_amount, treasury_amount) = split_threshold(
+            claim_record.escrowed_amount,
+            ctx.accounts.forge_state.burn_bps,
+        );
+        if burn_amount > 0 {
+            let cpi_accounts = Burn {
+                mint: ctx.accounts.rari_mint.to_account_info(),
+                from: ctx.accounts.escrow_token_account.to_account_info(),
+                authority: ctx.accounts.forge_state.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token_interface::burn(cpi_ctx, burn_amount)?;
+        }
+        if treasury_amount > 0 {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.escrow_token_account.to_account_info(),
+                mint: ctx.accounts.rari_mint.to_account_info(),
+                to: ctx.accounts.treasury_token_account.to_account_info(),
+                authority: ctx.accounts.forge_state.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            token_interface::transfer_checked(
+                cpi_ctx,
+                treasury_amount,
+                ctx.accounts.rari_mint.decimals,
+            )?;
+        }
+
+        claim_record.escrowed_amount = 0;
+        claim_record.rari_burned = burn_amount;
+        claim_record.status = ClaimStatus::Confirmed;
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.total_rari_burned = forge_state
+            .total_rari_burned
+            .checked_add(burn_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        forge_log!(
+            "CLAIM",
+            "settled orb={} burned={}",
+            claim_record.orb_mint,
+            burn_amount
+        );
+        emit!(FeedSettled {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            orb_mint: claim_record.orb_mint,
+            claimer: claim_record.claimer,
+            rari_burned: burn_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Refunds an escrowed feed's RARI to the claimer and closes its claim
+    /// record, uncounting the Orb from `total_claimed`.
+    pub fn cancel_feed(ctx: Context<CancelFeed>) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        let claim_record = &ctx.accounts.claim_record;
+        require!(
+            claim_record.status == ClaimStatus::Escrowed,
+            ErrorCode::ClaimNotEscrowed
+        );
+
+        let bump = ctx.bumps.forge_state;
+        let namespace = ctx.accounts.forge_state.namespace;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"forge_state", namespace_seed(&namespace), &[bump]]];
+        let refunded = claim_record.escrowed_amount;
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.escrow_token_account.to_account_info(),
+            mint: ctx.accounts.rari_mint.to_account_info(),
+            to: ctx.accounts.claimer_rari_account.to_account_info(),
+            authority: ctx.accounts.forge_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_interface::transfer_checked(cpi_ctx, refunded, ctx.accounts.rari_mint.decimals)?;
+
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.total_claimed = forge_state
+            .total_claimed
+            .checked_sub(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        forge_state.release_active_claim()?;
+
+        forge_log!(
+            "CLAIM",
+            "cancelled orb={} refunded={}",
+            claim_record.orb_mint,
+            refunded
+        );
+        emit!(FeedCancelled {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            orb_mint: claim_record.orb_mint,
+            claimer: claim_record.claimer,
+            refunded,
+        });
+        emit!(ForgeStatsUpdated {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            total_claimed: forge_state.total_claimed,
+            total_rari_burned: forge_state.total_rari_burned,
+            active_claims: forge_state.active_claims,
+        });
+
+        Ok(())
+    }
+
+    /// Mints back to the claimer the RARI their latest feed burned beyond what
+    /// the same feed burns now, at the claim's target chain threshold and the
+    /// tier and discount it was charged at, e.g. for a feed that landed just
+    /// as the threshold was lowered. `forge_state` must hold the RARI mint
+    /// authority. The claim then records the lower burn, so it is refunded once.
+    pub fn refund_claim(ctx: Context<RefundClaim>) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        let chain_config = load_chain_config(
+            &ctx.accounts.chain_config,
+            &ctx.accounts.forge_state.namespace,
+            ctx.accounts.claim_record.target_chain,
+        )?;
+        let base_threshold = chain_config
+            .map_or(ctx.accounts.forge_state.rari_threshold, |config| {
+                config.threshold
+            });
+        let refund = ctx
+            .accounts
+            .claim_record
+            .refund_due(&ctx.accounts.forge_state, base_threshold);
+        require!(refund > 0, ErrorCode::NothingToRefund);
+
+        let bump = ctx.bumps.forge_state;
+        let namespace = ctx.accounts.forge_state.namespace;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"forge_state", namespace_seed(&namespace), &[bump]]];
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.rari_mint.to_account_info(),
+            to: ctx.accounts.claimer_rari_account.to_account_info(),
+            authority: ctx.accounts.forge_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_interface::mint_to(cpi_ctx, refund)?;
+
+        let claim_record = &mut ctx.accounts.claim_record;
+        claim_record.rari_burned = claim_record
+            .rari_burned
+            .checked_sub(refund)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let forge_state = &mut ctx.accounts.forge_state;
+        forge_state.total_rari_burned = forge_state
+            .total_rari_burned
+            .checked_sub(refund)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        forge_log!(
+            "CLAIM",
+            "refunded orb={} amount={}",
+            claim_record.orb_mint,
+            refund
+        );
+        emit!(ClaimRefunded {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            orb_mint: claim_record.orb_mint,
+            claimer: claim_record.claimer,
+            amount: refund,
+        });
+
+        Ok(())
+    }
+
+    /// Expires a cross-chain claim that was never relayed, closing its record
+    /// and sending the rent to `rent_recipient`. Measured from the last feed,
+    /// which is `claimed_at` unless the Orb was re-fed.
+    pub fn expire_claim(ctx: Context<ExpireClaim>) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        let claim_record = &mut ctx.accounts.claim_record;
+        require!(
+            claim_record.status == ClaimStatus::Pending,
+            ErrorCode::ClaimNotPending
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            ctx.accounts.forge_state.is_after(
+                claim_record
+                    .last_fed_at
+                    .checked_add(ctx.accounts.forge_state.expiry_seconds)
+                    .and_then(|ts| ts.checked_add(1))
+                    .ok_or(ErrorCode::MathOverflow)?,
+                now
+            ),
+            ErrorCode::ClaimNotExpired
+        );
+        let forge_state = &mut ctx.accounts.forge_state;
+        if claim_record.counts_as_pending() {
+            forge_state.release_pending_claim();
+            ctx.accounts.pending_queue.remove(&claim_record.key());
+        }
+        if claim_record.feed_count > 0 {
+            forge_state.release_active_claim()?;
+        }
+        claim_record.status = ClaimStatus::Expired;
+
+        forge_log!(
+            "CLAIM",
+            "expired orb={} active_claims={}",
+            claim_record.orb_mint,
+            forge_state.active_claims
+        );
+        emit!(ClaimExpired {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            orb_mint: claim_record.orb_mint,
+            claimer: claim_record.claimer,
+            target_chain: claim_record.target_chain,
+        });
+        emit!(ForgeStatsUpdated {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            total_claimed: forge_state.total_claimed,
+            total_rari_burned: forge_state.total_rari_burned,
+            active_claims: forge_state.active_claims,
+        });
+
+        Ok(())
+    }
+
+    /// Trusts `emitter_address` on `chain_id` as the source of inbound claims.
+    pub fn set_trusted_emitter(
+        ctx: Context<SetTrustedEmitter>,
+        chain_id: u16,
+        emitter_address: [u8; 32],
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let trusted_emitter = &mut ctx.accounts.trusted_emitter;
+        trusted_emitter.chain_id = chain_id;
+        trusted_emitter.emitter_address = emitter_address;
+        Ok(())
+    }
+
+    /// Records an inbound claim from a verified Wormhole VAA posted by a
+    /// trusted emitter. The `[b"redeemed", vaa_hash]` PDA makes each VAA
+    /// redeemable once.
+    pub fn redeem_claim(ctx: Context<RedeemClaim>, _vaa_hash: [u8; 32]) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        let posted_vaa = &ctx.accounts.posted_vaa;
+        require!(
+            posted_vaa.emitter_address() == &ctx.accounts.trusted_emitter.emitter_address,
+            ErrorCode::UntrustedEmitter
+        );
+
+        let message = posted_vaa.data();
+        require!(
+            message.target_chain == wormhole::CHAIN_ID_SOLANA,
+            ErrorCode::InvalidVaaPayload
+        );
+
+        // The core bridge keeps posted VAAs forever, so a VAA whose marker may
+        // have been pruned is refused rather than redeemed a second time
+        require!(
+            ctx.accounts
+                .forge_state
+                .accepts_vaa_timestamp(posted_vaa.timestamp() as i64),
+            ErrorCode::VaaTooOld
+        );
+
+        let inbound_claim = &mut ctx.accounts.inbound_claim;
+        inbound_claim.emitter_chain = posted_vaa.emitter_chain();
+        inbound_claim.sequence = posted_vaa.sequence();
+        inbound_claim.orb_mint = message.orb_mint;
+        inbound_claim.claimer = message.claimer;
+        inbound_claim.rari_burned = message.rari_burned;
+        inbound_claim.redeemed_at = Clock::get()?.unix_timestamp;
+        inbound_claim.receipt_burned = false;
+
+        forge_log!(
+            "REDEEM",
+            "chain={} sequence={} orb={}",
+            inbound_claim.emitter_chain,
+            inbound_claim.sequence,
+            inbound_claim.orb_mint
+        );
+        emit!(InboundClaimRedeemed {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            emitter_chain: inbound_claim.emitter_chain,
+            sequence: inbound_claim.sequence,
+            orb_mint: inbound_claim.orb_mint,
+            claimer: inbound_claim.claimer,
+        });
+
+        Ok(())
+    }
+
+    /// Closes a redemption marker once it is older than `VAA_FINALITY_WINDOW`
+    /// plus `redeemed_ttl`, paying its rent to the caller. Permissionless.
+    ///
+    /// Replay protection then rests on `pruned_through`: `redeem_claim` refuses
+    /// every VAA timestamped at or before the newest pruned marker. Pruning
+    /// earlier would be unsafe, as that horizon would then pass VAAs still in
+    /// flight and refuse their first redemption, and `burn_receipt` would lose
+    /// the marker its claimer still needs.
+    pub fn prune_redeemed(ctx: Context<PruneRedeemed>, _vaa_hash: [u8; 32]) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        let inbound_claim = &ctx.accounts.inbound_claim;
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts
+            .forge_state
+            .record_pruned(inbound_claim.redeemed_at, now)?;
+
+        forge_log!(
+            "REDEEM",
+            "pruned chain={} sequence={}",
+            inbound_claim.emitter_chain,
+            inbound_claim.sequence
+        );
+        emit!(RedeemedPruned {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            emitter_chain: inbound_claim.emitter_chain,
+            sequence: inbound_claim.sequence,
+        });
+        Ok(())
+    }
+
+    /// Confirms a pending cross-chain claim from the destination chain's
+    /// acknowledgment VAA, posted by the trusted emitter for the claim's
+    /// `target_chain` and carrying the claim's `burn_proof`. Permissionless;
+    /// the `[b"acked", vaa_hash]` PDA makes each VAA usable once, so an old
+    /// ack cannot confirm a later re-feed with the same proof.
+    pub fn confirm_claim(ctx: Context<ConfirmClaim>, _vaa_hash: [u8; 32]) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        let posted_vaa = &ctx.accounts.posted_vaa;
+        // The trusted emitter is looked up by the claim's target chain, so the
+        // VAA must come from that chain too
+        require!(
+            posted_vaa.emitter_chain() == ctx.accounts.claim_record.target_chain,
+            ErrorCode::UntrustedEmitter
+        );
+        require!(
+            posted_vaa.emitter_address() == &ctx.accounts.trusted_emitter.emitter_address,
+            ErrorCode::UntrustedEmitter
+        );
+        let claim_record = &mut ctx.accounts.claim_record;
+        claim_record.check_ack(posted_vaa.data())?;
+
+        let forge_state = &mut ctx.accounts.forge_state;
+        if claim_record.counts_as_pending() {
+            forge_state.release_pending_claim();
+            ctx.accounts.pending_queue.remove(&claim_record.key());
+        }
+        claim_record.status = ClaimStatus::Confirmed;
+
+        let claim_ack = &mut ctx.accounts.claim_ack;
+        claim_ack.orb_mint = claim_record.orb_mint;
+        claim_ack.emitter_chain = posted_vaa.emitter_chain();
+        claim_ack.sequence = posted_vaa.sequence();
+        claim_ack.confirmed_at = Clock::get()?.unix_timestamp;
+
+        forge_log!(
+            "CLAIM",
+            "confirmed orb={} chain={} sequence={}",
+            claim_record.orb_mint,
+            claim_ack.emitter_chain,
+            claim_ack.sequence
+        );
+        emit!(ClaimConfirmed {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            orb_mint: claim_record.orb_mint,
+            claimer: claim_record.claimer,
+            target_chain: claim_record.target_chain,
+            burn_proof: claim_record.burn_proof,
+            emitter_chain: claim_ack.emitter_chain,
+            sequence: claim_ack.sequence,
+        });
+
+        Ok(())
+    }
+
+    /// Accepts `mint` as an alternative fee token charged at `threshold`.
+    pub fn add_payment_mint(ctx: Context<AddPaymentMint>, threshold: u64) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        require_no_governance(&ctx.accounts.forge_state)?;
+        let payment_mint_config = &mut ctx.accounts.payment_mint_config;
+        payment_mint_config.mint = ctx.accounts.mint.key();
+        payment_mint_config.threshold = threshold;
+        payment_mint_config.enabled = true;
+        Ok(())
+    }
+
+    pub fn disable_payment_mint(ctx: Context<DisablePaymentMint>) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.payment_mint_config.enabled = false;
+        Ok(())
+    }
+
+    /// Moves `amount` of accumulated treasury RARI to `destination`, signing as `forge_state`.
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        require!(
+            ctx.accounts.treasury_token_account.amount >= amount,
+            ErrorCode::InsufficientTreasuryBalance
+        );
+
+        let bump = ctx.bumps.forge_state;
+        let namespace = ctx.accounts.forge_state.namespace;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"forge_state", namespace_seed(&namespace), &[bump]]];
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.treasury_token_account.to_account_info(),
+            mint: ctx.accounts.rari_mint.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.forge_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.rari_mint.decimals)?;
+
+        forge_log!(
+            "TREASURY",
+            "withdrew {} to {}",
+            amount,
+            ctx.accounts.destination.key()
+        );
+        emit!(TreasuryWithdrawn {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            amount,
+            destination: ctx.accounts.destination.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Recovers `amount` of tokens sent to a forge-owned token account by
+    /// mistake, signing as `forge_state`. The treasury and the escrow hold
+    /// feed payments and are moved only by `withdraw_treasury` and settlement.
+    pub fn sweep_tokens(ctx: Context<SweepTokens>, amount: u64) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        let (source, mint) = (ctx.accounts.source.key(), ctx.accounts.mint.key());
+        let forge_state = &ctx.accounts.forge_state;
+        require!(
+            !forge_state.protects_token_account(&source, &mint),
+            ErrorCode::ProtectedAccount
+        );
+
+        let bump = ctx.bumps.forge_state;
+        let namespace = forge_state.namespace;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"forge_state", namespace_seed(&namespace), &[bump]]];
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.source.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.forge_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+
+        forge_log!(
+            "SWEEP",
+            "swept {} of {} from {} to {}",
+            amount,
+            mint,
+            source,
+            ctx.accounts.destination.key()
+        );
+        emit!(TokensSwept {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            mint,
+            source,
+            destination: ctx.accounts.destination.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Runs the read-only checks of `feed_orb` and emits an `EligibilityResult`
+    /// with the error code the feed would fail with, or 0 if it would pass.
+    /// Like `get_stats`, call it via `simulateTransaction`.
+    pub fn check_feed_eligible(
+        ctx: Context<CheckFeedEligible>,
+        chain_id: u16,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let now = Clock::get()?.unix_timestamp;
+        let result = ctx.accounts.check(chain_id, &proof, now);
+
+        emit!(EligibilityResult {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq: ctx.accounts.forge_state.event_seq,
+            orb_mint: ctx.accounts.orb_mint.key(),
+            user: ctx.accounts.user.key(),
+            target_chain: chain_id,
+            eligible: result.is_ok(),
+            reason: match result {
+                Ok(()) => 0,
+                Err(Error::AnchorError(err)) => err.error_code_number.into(),
+                Err(Error::ProgramError(err)) => err.program_error.into(),
+            },
+        });
+
+        Ok(())
+    }
+
+    /// Grows a `ForgeState` written by an older program to the current layout.
+    /// New fields are zeroed; re-running once migrated is a no-op.
+    pub fn migrate_state(ctx: Context<MigrateState>, _namespace: [u8; 16]) -> Result<()> {
+        let info = ctx.accounts.forge_state.to_account_info();
+
+        // Grow to the current size first, so the account deserializes; a
+        // mismatched authority below reverts the realloc with everything else
+        let new_len = 8 + ForgeState::LEN;
+        if info.data_len() < new_len {
+            let rent_due = Rent::get()?
+                .minimum_balance(new_len)
+                .saturating_sub(info.lamports());
+            if rent_due > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.authority.to_account_info(),
+                            to: info.clone(),
+                        },
+                    ),
+                    rent_due,
+                )?;
+            }
+            info.realloc(new_len, true)?;
+        }
+
+        let mut forge_state = ForgeState::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require_keys_eq!(
+            forge_state.authority,
+            ctx.accounts.authority.key(),
+            anchor_lang::error::ErrorCode::ConstraintHasOne
+        );
+
+        if forge_state.layout_version < FORGE_LAYOUT_VERSION {
+            let rari_decimals = match ctx.accounts.rari_mint.as_ref() {
+                Some(rari_mint) => {
+                    require_keys_eq!(
+                        rari_mint.key(),
+                        forge_state.rari_mint,
+                        ErrorCode::InvalidRariMint
+                    );
+                    Some(rari_mint.decimals)
+                }
+                None => None,
+            };
+            forge_log!(
+                "MIGRATE",
+                "layout {} -> {}",
+                forge_state.layout_version,
+                FORGE_LAYOUT_VERSION
+            );
+            forge_state.upgrade_layout(rari_decimals)?;
+            forge_state.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+        }
+        // Checked once upgraded, as forges predating `is_initialized` only
+        // have it set by the upgrade
+        require_initialized(&forge_state)?;
+        require_not_halted(&forge_state)?;
+
+        Ok(())
+    }
+
+    /// Grows a `ClaimRecord` written by an older program to the current
+    /// layout, paid for by its claimer. A record from before `version` has its
+    /// fields shifted past the new byte; new fields are zeroed, and re-running
+    /// once migrated is a no-op.
+    pub fn migrate_claim(ctx: Context<MigrateClaim>, _orb_mint: Pubkey) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let info = ctx.accounts.claim_record.to_account_info();
+
+        // Grow to the current size first, so the account deserializes; a
+        // mismatched claimer below reverts the realloc with everything else
+        let data_len = info.data_len();
+        let new_len = ClaimRecord::migrated_len(data_len);
+        if data_len < new_len {
+            let rent_due = Rent::get()?
+                .minimum_balance(new_len)
+                .saturating_sub(info.lamports());
+            if rent_due > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.claimer.to_account_info(),
+                            to: info.clone(),
+                        },
+                    ),
+                    rent_due,
+                )?;
+            }
+            info.realloc(new_len, true)?;
+        }
+        if ClaimRecord::is_unversioned(data_len) {
+            insert_claim_version(&mut info.try_borrow_mut_data()?, data_len);
+        }
+
+        let mut claim_record = ClaimRecord::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require_keys_eq!(
+            claim_record.claimer,
+            ctx.accounts.claimer.key(),
+            anchor_lang::error::ErrorCode::ConstraintHasOne
+        );
+
+        if claim_record.version < CLAIM_RECORD_VERSION {
+            forge_log!(
+                "MIGRATE",
+                "claim {} version {} -> {}",
+                claim_record.orb_mint,
+                claim_record.version,
+                CLAIM_RECORD_VERSION
+            );
+            claim_record.version = CLAIM_RECORD_VERSION;
+            write_claim_record(&info, &claim_record)?;
+        }
+
+        Ok(())
+    }
+
+    /// Emits a `ForgeStatsSnapshot` of the forge state. Read-only: call it via
+    /// `simulateTransaction` and decode the event from the logs rather than
+    /// sending it.
+    pub fn get_stats(ctx: Context<GetStats>) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let forge_state = &ctx.accounts.forge_state;
+        emit!(ForgeStatsSnapshot {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq: forge_state.event_seq,
+            total_claimed: forge_state.total_claimed,
+            total_rari_burned: forge_state.total_rari_burned,
+            active_claims: forge_state.active_claims,
+            rari_threshold: forge_state.rari_threshold,
+            rari_decimals: forge_state.rari_decimals,
+            paused: forge_state.is_paused(PAUSE_FEED),
+            pending_threshold: forge_state.pending_threshold,
+            threshold_effective_at: forge_state.threshold_effective_at,
+        });
+        Ok(())
+    }
+
+    /// Closes the forge state and its pending queue at end of life, returning
+    /// their rent to the authority. Feeding must be paused, so `total_claimed`
+    /// is final, and no cross-chain claim may still be `Pending`; the final
+    /// totals are emitted as a `ForgeClosed` event.
+    pub fn close_forge(ctx: Context<CloseForge>) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        let now = Clock::get()?.unix_timestamp;
+        let forge_state = &ctx.accounts.forge_state;
+        // The state is closed, so the sequence is not written back
+        let seq = forge_state
+            .event_seq
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            forge_state.is_paused_at(PAUSE_FEED, now),
+            ErrorCode::ForgeNotPaused
+        );
+        require!(
+            forge_state.pending_claims == 0,
+            ErrorCode::PendingClaimsRemain
+        );
+
+        forge_log!("CLOSE", "total_claimed={}", forge_state.total_claimed);
+        emit!(ForgeClosed {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            total_claimed: forge_state.total_claimed,
+            total_rari_burned: forge_state.total_rari_burned,
+        });
+        Ok(())
+    }
+
+    /// Starts a two-step authority handoff. Passing `None` cancels a pending transfer.
+    pub fn transfer_authority(
+        ctx: Context<TransferAuthority>,
+        new_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.pending_authority = new_authority;
+        forge_log!("AUTH", "pending_authority={:?}", new_authority);
+        Ok(())
+    }
+
+    /// Sets the key allowed to raise and clear an emergency halt.
+    /// `Pubkey::default()` removes the role.
+    pub fn set_emergency_authority(
+        ctx: Context<SetEmergencyAuthority>,
+        emergency_authority: Pubkey,
+    ) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        require_not_halted(&ctx.accounts.forge_state)?;
+        ctx.accounts.forge_state.emergency_authority = emergency_authority;
+        Ok(())
+    }
+
+    /// Halts every instruction except `accept_authority` and clearing the
+    /// halt itself, for at least `EMERGENCY_HALT_TIMELOCK`.
+    ///
+    /// `toggle_pause` only stops feeds; a compromised `authority` key could
+    /// still drain the treasury or rewrite the config mid-incident. The halt is
+    /// raised by a separate `emergency_authority` and cannot be lifted early by
+    /// anyone, so a stolen authority key cannot move funds while it is in
+    /// force. `accept_authority` stays open so a handoff started before the
+    /// halt can move control to a fresh key. A stolen emergency key can only
+    /// freeze the forge, never move funds.
+    pub fn emergency_halt(ctx: Context<EmergencyHalt>) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.forge_state.halt(now)?;
+        forge_log!("HALT", "raised at {}", now);
+        Ok(())
+    }
+
+    /// Lifts an emergency halt once its timelock has passed.
+    pub fn clear_emergency_halt(ctx: Context<EmergencyHalt>) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        let now = Clock::get()?.unix_timestamp;
+        ctx.accounts.forge_state.clear_halt(now)?;
+        forge_log!("HALT", "cleared at {}", now);
+        Ok(())
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        require_initialized(&ctx.accounts.forge_state)?;
+        let seq = ctx.accounts.forge_state.next_event_seq()?;
+        let forge_state = &mut ctx.accounts.forge_state;
+        let previous_authority = forge_state.authority;
+        forge_state.authority = ctx.accounts.pending_authority.key();
+        forge_state.pending_authority = None;
+
+        forge_log!(
+            "AUTH",
+            "{} -> {}",
+            previous_authority,
+            forge_state.authority
+        );
+        emit!(AuthorityTransferred {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            previous_authority,
+            new_authority: forge_state.authority,
+        });
+
+        Ok(())
+    }
+}
+
+/// `feed_orb` proper, split out so every error it returns can be reported
+/// through `FeedRejected`.
+fn process_feed_orb<'info>(
+    ctx: Context<'_, '_, '_, 'info, FeedOrb<'info>>,
+    chain_id: u16,
+    proof: Vec<[u8; 32]>,
+    max_burn: u64,
+    rarity_tier: u8,
+    beneficiary: Pubkey,
+    memo: [u8; MEMO_LEN],
+) -> Result<()> {
+    require_initialized(&ctx.accounts.forge_state)?;
+    require_not_halted(&ctx.accounts.forge_state)?;
+    let seq = ctx.accounts.forge_state.next_event_seq()?;
+    require!(rarity_tier <= MAX_RARITY_TIER, ErrorCode::InvalidRarityTier);
+    // A custodial delegate feeds alone; otherwise the user must sign
+    require!(
+        ctx.accounts.user.is_signer || ctx.accounts.delegate.is_some(),
+        ErrorCode::UserSignatureMissing
+    );
+    // Without the user's signature the delegate may only credit the user
+    // and must not freeze its Orb
+    if !ctx.accounts.user.is_signer {
+        require_keys_eq!(
+            beneficiary,
+            ctx.accounts.user.key(),
+            ErrorCode::DelegateBeneficiaryMismatch
+        );
+        require!(
+            !ctx.accounts.forge_state.freeze_orb,
+            ErrorCode::DelegateCannotFreeze
+        );
+    }
+    ctx.accounts.forge_state.require_valid_memo(&memo)?;
+    require_allowlisted(
+        &ctx.accounts.forge_state,
+        &ctx.accounts.orb_mint.key(),
+        &proof,
+    )?;
+    require_feed_signature(
+        &ctx.accounts.forge_state,
+        ctx.accounts
+            .instructions_sysvar
+            .as_ref()
+            .map(|sysvar| sysvar.as_ref()),
+        &ctx.accounts.user.key(),
+        &ctx.accounts.orb_mint.key(),
+        rarity_tier,
+    )?;
+    require_not_denied(&ctx.accounts.deny_entry)?;
+    // A record seeded by `seed_claim` already exists and is fed in place
+    let mut claim_record = if ctx.accounts.claim_record.data_is_empty() {
+        create_claim_record(
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            &ctx.accounts.claim_record,
+            &ctx.accounts.forge_state.namespace,
+            &ctx.accounts.orb_mint.key(),
+        )?;
+        ClaimRecord::default()
+    } else {
+        // A seeded claim can be paid for by anyone, but only for its claimer
+        load_seeded_claim(&ctx.accounts.claim_record, &beneficiary)?
+    };
+
+    require!(
+        !ctx.accounts.forge_state.require_frozen_orb || ctx.accounts.orb_token_account.is_frozen(),
+        ErrorCode::OrbNotStaked
+    );
+    let orb_burn_amount = ctx.accounts.forge_state.orb_burn_amount;
+    require!(
+        ctx.accounts.orb_token_account.amount >= orb_burn_amount,
+        ErrorCode::InsufficientOrbBalance
+    );
+    // Only the user can burn its Orb tokens; a delegate covers the RARI alone
+    require!(
+        orb_burn_amount == 0 || ctx.accounts.user.is_signer,
+        ErrorCode::UserSignatureMissing
+    );
+
+    let discount_bps = ctx.accounts.holder_discount_bps()?;
+    // Decided before the feed counts itself into `total_claimed`
+    let early_bonus = claim_record.feed_count == 0 && ctx.accounts.forge_state.grants_early_bonus();
+
+    let now = Clock::get()?.unix_timestamp;
+    let emitter_bump = ctx.bumps.wormhole_emitter;
+    let (mut shared, mut orb) = ctx.accounts.split(
+        emitter_bump,
+        chain_id,
+        beneficiary,
+        max_burn,
+        rarity_tier,
+        discount_bps,
+    );
+    shared.transfer_hook_accounts = ctx.remaining_accounts;
+    orb.memo = memo;
+    let event = shared.feed(orb, &mut claim_record, now)?;
+    write_claim_record(&ctx.accounts.claim_record, &claim_record)?;
+
+    let chain_counter = &mut ctx.accounts.chain_counter;
+    chain_counter.record(chain_id)?;
+    emit!(ChainCounterUpdated {
+        schema_version: EVENT_SCHEMA_VERSION,
+        seq,
+        chain_id,
+        count: chain_counter.count,
+    });
+
+    // Give the user a wallet-visible receipt for the in-flight claim
+    if is_cross_chain(chain_id) && ctx.accounts.forge_state.receipt_mint != Pubkey::default() {
+        ctx.accounts.mint_receipt(ctx.bumps.forge_state)?;
+    }
+
+    if early_bonus {
+        ctx.accounts
+            .mint_early_bonus(ctx.bumps.forge_state, &event.claimer)?;
+        emit!(BonusGranted {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            orb_mint: event.orb_mint,
+            claimer: event.claimer,
+            bonus_mint: ctx.accounts.forge_state.early_bonus_mint,
+            amount: ctx.accounts.forge_state.early_bonus_amount,
+        });
+    }
+
+    let points_per_feed = ctx.accounts.forge_state.points_per_feed;
+    if points_per_feed > 0 {
+        ctx.accounts
+            .points_account
+            .as_mut()
+            .ok_or(ErrorCode::PointsAccountMissing)?
+            .accrue(points_per_feed)?;
+    }
+
+    let partner_share = ctx.accounts.forge_state.partner_share();
+    if partner_share > 0 {
+        ctx.accounts
+            .mint_partner_share(ctx.bumps.forge_state, partner_share)?;
+        emit!(PartnerShareMinted {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            orb_mint: event.orb_mint,
+            partner: ctx.accounts.forge_state.partner,
+            reward_mint: ctx.accounts.forge_state.partner_reward_mint,
+            amount: partner_share,
+        });
+    }
+
+    if orb_burn_amount > 0 {
+        ctx.accounts.burn_orb_tokens(orb_burn_amount)?;
+        emit!(OrbTokensBurned {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            orb_mint: event.orb_mint,
+            user: ctx.accounts.user.key(),
+            amount: orb_burn_amount,
+        });
+    }
+
+    if ctx.accounts.forge_state.freeze_orb {
+        let bump = ctx
+            .bumps
+            .orb_freeze_authority
+            .ok_or(ErrorCode::FreezeAccountsMissing)?;
+        ctx.accounts.orb_freeze_accounts()?.invoke(true, bump)?;
+    }
+
+    ctx.accounts.forward_to_event_sink(&event)
+}
+
+/// Logs a `FeedRejected` event for a failed `feed_orb`. The transaction still
+/// reverts, but its logs keep the event for monitoring. Account constraint
+/// failures never get this far, so they are neither logged nor emitted.
+fn emit_feed_rejected(
+    error: &anchor_lang::error::Error,
+    seq: u64,
+    user: Pubkey,
+    orb_mint: Pubkey,
+    target_chain: u16,
+) {
+    let error_code = match error {
+        anchor_lang::error::Error::AnchorError(error) => error.error_code_number,
+        anchor_lang::error::Error::ProgramError(error) => {
+            u64::from(error.program_error.clone()) as u32
+        }
+    };
+    forge_log!(
+        "FEED",
+        "rejected orb={} user={} code={}",
+        orb_mint,
+        user,
+        error_code
+    );
+    emit!(FeedRejected {
+        schema_version: EVENT_SCHEMA_VERSION,
+        seq,
+        user,
+        orb_mint,
+        target_chain,
+        error_code,
+    });
+}
+
+/// Whether the Wormhole fee collector, holding `collected` lamports, covers
+/// `fee` on top of the `last_lamports` the bridge recorded at its last post.
+fn wormhole_fee_paid(collected: u64, last_lamports: u64, fee: u64) -> bool {
+    last_lamports
+        .checked_add(fee)
+        .is_some_and(|required| collected >= required)
+}
+
+/// `last_lamports` of the core bridge, read from its data rather than a
+/// snapshot that earlier posts in the instruction may have outdated.
+fn bridge_last_lamports(info: &AccountInfo) -> Result<u64> {
+    let data = info.try_borrow_data()?;
+    Ok(wormhole::BridgeData::try_deserialize_unchecked(&mut &data[..])?.last_lamports)
+}
+
+/// Whether a feed to `chain_id` leaves Solana through a Wormhole message.
+fn is_cross_chain(chain_id: u16) -> bool {
+    chain_id != SOLANA_CHAIN_ID
+}
+
+/// `OrbFedEvent::route` of a feed to `chain_id`.
+fn route_for(chain_id: u16) -> u8 {
+    if is_cross_chain(chain_id) {
+        ROUTE_CROSS_CHAIN
+    } else {
+        ROUTE_SAME_CHAIN
+    }
+}
+
+/// Commitment to a feed, `keccak(orb_mint || claimer || rari_burned ||
+/// claimed_at || target_chain)` with integers little-endian. Verifiers
+/// recompute it from the bridged `OrbFedMessage` and the claim's `claimed_at`.
+fn burn_proof_for(
+    orb_mint: &Pubkey,
+    claimer: &Pubkey,
+    rari_burned: u64,
+    claimed_at: i64,
+    target_chain: u16,
+) -> [u8; 32] {
+    keccak::hashv(&[
+        orb_mint.as_ref(),
+        claimer.as_ref(),
+        &rari_burned.to_le_bytes(),
+        &claimed_at.to_le_bytes(),
+        &target_chain.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// `OrbFedEvent::batch_id` of the instruction that advanced the event
+/// sequence to `seq`: the first 16 bytes of `keccak(namespace || seq)`, with
+/// `seq` little-endian, so it is unique across forges and instructions.
+fn batch_id_for(namespace: &[u8; 16], seq: u64) -> [u8; 16] {
+    let hash = keccak::hashv(&[&namespace[..], &seq.to_le_bytes()]).to_bytes();
+    let mut batch_id = [0; 16];
+    batch_id.copy_from_slice(&hash[..16]);
+    batch_id
+}
+
+/// Bubblegum asset id of the leaf minted into `merkle_tree` with `nonce`.
+fn compressed_asset_id(merkle_tree: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"asset", merkle_tree.as_ref(), &nonce.to_le_bytes()],
+        &BUBBLEGUM_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Verifies `leaf` at `index` under `root` in `merkle_tree` through the
+/// account compression program, which fails the CPI on a bad proof.
+fn verify_compressed_leaf<'info>(
+    compression_program: &AccountInfo<'info>,
+    merkle_tree: &AccountInfo<'info>,
+    proof_nodes: &[AccountInfo<'info>],
+    root: [u8; 32],
+    leaf: [u8; 32],
+    index: u32,
+) -> Result<()> {
+    let mut accounts = vec![AccountMeta::new_readonly(merkle_tree.key(), false)];
+    accounts.extend(
+        proof_nodes
+            .iter()
+            .map(|node| AccountMeta::new_readonly(node.key(), false)),
+    );
+    let mut data = VERIFY_LEAF_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&root);
+    data.extend_from_slice(&leaf);
+    data.extend_from_slice(&index.to_le_bytes());
+    let instruction = anchor_lang::solana_program::instruction::Instruction {
+        program_id: SPL_ACCOUNT_COMPRESSION_ID,
+        accounts,
+        data,
+    };
+
+    let mut account_infos = vec![merkle_tree.clone()];
+    account_infos.extend_from_slice(proof_nodes);
+    account_infos.push(compression_program.clone());
+    anchor_lang::solana_program::program::invoke(&instruction, &account_infos)?;
+    Ok(())
+}
+
+/// `log_event(data)` instruction handing `event`, Borsh-serialized, to the
+/// `event_sink` program, with the forge state as its read-only source account.
+fn log_event_instruction(
+    event_sink: Pubkey,
+    forge_state: Pubkey,
+    event: &OrbFedEvent,
+) -> Result<anchor_lang::solana_program::instruction::Instruction> {
+    let mut data = LOG_EVENT_DISCRIMINATOR.to_vec();
+    event.try_to_vec()?.serialize(&mut data)?;
+    Ok(anchor_lang::solana_program::instruction::Instruction {
+        program_id: event_sink,
+        accounts: vec![AccountMeta::new_readonly(forge_state, false)],
+        data,
+    })
+}
+
+/// CRC-32 (IEEE 802.3, as used by zlib and PNG) of `bytes`.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Zero-pads a chain's disabled reason, rejecting one that is too long or not UTF-8.
+fn encode_disabled_reason(reason: &[u8]) -> Result<[u8; MAX_DISABLED_REASON_LEN]> {
+    require!(
+        reason.len() <= MAX_DISABLED_REASON_LEN && std::str::from_utf8(reason).is_ok(),
+        ErrorCode::InvalidDisabledReason
+    );
+    let mut padded = [0; MAX_DISABLED_REASON_LEN];
+    padded[..reason.len()].copy_from_slice(reason);
+    Ok(padded)
+}
+
+/// Rejects a drop window that closes before it opens.
+fn require_valid_window(start_ts: i64, end_ts: i64) -> Result<()> {
+    require!(
+        start_ts == 0 || end_ts == 0 || start_ts < end_ts,
+        ErrorCode::InvalidForgeWindow
+    );
+    Ok(())
+}
+
+/// Fails with `ProgramHalted` while an emergency halt is in force.
+fn require_not_halted(forge_state: &ForgeState) -> Result<()> {
+    require!(!forge_state.halted, ErrorCode::ProgramHalted);
+    Ok(())
+}
+
+/// Rejects single-authority changes to settings governance has taken over.
+fn require_no_governance(forge_state: &ForgeState) -> Result<()> {
+    require!(
+        forge_state.governance_threshold == 0,
+        ErrorCode::GovernanceRequired
+    );
+    Ok(())
+}
+
+fn emit_proposal_approved(proposal: &ProposalAccount, seq: u64) {
+    emit!(ProposalApproved {
+        schema_version: EVENT_SCHEMA_VERSION,
+        seq,
+        proposal_id: proposal.proposal_id,
+        approvals: proposal.approvals.count_ones() as u8,
+        executed: proposal.executed,
+    });
+}
+
+/// Fails with `NotInitialized` until `initialize` has run.
+fn require_initialized(forge_state: &ForgeState) -> Result<()> {
+    require!(forge_state.is_initialized, ErrorCode::NotInitialized);
+    Ok(())
+}
+
+/// Fails with `OrbNotAllowlisted` unless `proof` links `keccak(orb_mint)` to
+/// `forge_state.merkle_root`. Sibling pairs are hashed in sorted order, so
+/// proofs need no left/right flags. An all-zero root skips the check.
+fn require_allowlisted(
+    forge_state: &ForgeState,
+    orb_mint: &Pubkey,
+    proof: &[[u8; 32]],
+) -> Result<()> {
+    if forge_state.merkle_root == [0u8; 32] {
+        return Ok(());
+    }
+
+    let mut node = keccak::hash(orb_mint.as_ref()).to_bytes();
+    for sibling in proof {
+        node = if node <= *sibling {
+            keccak::hashv(&[&node, sibling])
+        } else {
+            keccak::hashv(&[sibling, &node])
+        }
+        .to_bytes();
+    }
+    require!(
+        node == forge_state.merkle_root,
+        ErrorCode::OrbNotAllowlisted
+    );
+    Ok(())
+}
+
+/// While `require_signed_feed` is set, or a nonzero `rarity_tier` is claimed,
+/// requires the instruction before this one to be an Ed25519 program check
+/// of `feed_signer`'s signature over `user || orb_mint || expiry ||
+/// rarity_tier` (`expiry` a little-endian i64), with `expiry` not yet passed.
+/// Paths without the instructions sysvar pass `None` and are refused.
+fn require_feed_signature(
+    forge_state: &ForgeState,
+    instructions: Option<&AccountInfo>,
+    user: &Pubkey,
+    orb_mint: &Pubkey,
+    rarity_tier: u8,
+) -> Result<()> {
+    if !forge_state.require_signed_feed && rarity_tier == 0 {
+        return Ok(());
+    }
+
+    let instructions = instructions.ok_or(ErrorCode::InvalidFeedSignature)?;
+    let current = instructions_sysvar::load_current_index_checked(instructions)?;
+    require!(current > 0, ErrorCode::InvalidFeedSignature);
+    let ed25519_ix =
+        instructions_sysvar::load_instruction_at_checked(current as usize - 1, instructions)?;
+    require_keys_eq!(
+        ed25519_ix.program_id,
+        ed25519_program::ID,
+        ErrorCode::InvalidFeedSignature
+    );
+
+    let (signer, message) = parse_ed25519_instruction(&ed25519_ix.data)?;
+    require_keys_eq!(
+        signer,
+        forge_state.feed_signer,
+        ErrorCode::InvalidFeedSignature
+    );
+    require!(
+        message.len() == FEED_AUTHORIZATION_LEN
+            && message[..32] == user.to_bytes()
+            && message[32..64] == orb_mint.to_bytes()
+            && message[72] == rarity_tier,
+        ErrorCode::InvalidFeedSignature
+    );
+    let expiry = i64::from_le_bytes(message[64..72].try_into().unwrap());
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        !forge_state.is_after(expiry.saturating_add(1), now),
+        ErrorCode::FeedSignatureExpired
+    );
+    Ok(())
+}
+
+/// Signer and message of an Ed25519 program instruction checking a single
+/// signature. Every offset must point into the instruction itself, so the
+/// verified key and message cannot be swapped for another instruction's bytes.
+fn parse_ed25519_instruction(data: &[u8]) -> Result<(Pubkey, &[u8])> {
+    // [num_signatures, padding] then seven u16 fields: signature offset and
+    // instruction, public key offset and instruction, message offset, size
+    // and instruction
+    const HEADER_LEN: usize = 2 + 14;
+    const THIS_INSTRUCTION: u16 = u16::MAX;
+
+    require!(
+        data.len() >= HEADER_LEN && data[0] == 1,
+        ErrorCode::InvalidFeedSignature
+    );
+    let field = |index: usize| u16::from_le_bytes([data[2 + 2 * index], data[3 + 2 * index]]);
+    require!(
+        [1, 3, 6]
+            .iter()
+            .all(|&index| field(index) == THIS_INSTRUCTION),
+        ErrorCode::InvalidFeedSignature
+    );
+    let (pubkey_offset, message_offset, message_len) = (field(2), field(4), field(5));
+
+    let pubkey = data
+        .get(pubkey_offset as usize..pubkey_offset as usize + 32)
+        .ok_or(ErrorCode::InvalidFeedSignature)?;
+    let message = data
+        .get(message_offset as usize..message_offset as usize + message_len as usize)
+        .ok_or(ErrorCode::InvalidFeedSignature)?;
+    Ok((Pubkey::try_from(pubkey).unwrap(), message))
+}
+
+/// Accounts for freezing or thawing an Orb through Token Metadata.
+struct OrbFreezeAccounts<'a, 'info> {
+    /// `ForgeState::namespace_seed` of the forge owning the freeze delegate
+    namespace_seed: &'a [u8],
+    metadata_program: &'a AccountInfo<'info>,
+    freeze_authority: &'a AccountInfo<'info>,
+    token_account: &'a AccountInfo<'info>,
+    edition: &'a AccountInfo<'info>,
+    mint: &'a AccountInfo<'info>,
+    token_program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> OrbFreezeAccounts<'a, 'info> {
+    /// Freezes (or thaws) the token account, signing as the forge's freeze delegate PDA.
+    fn invoke(&self, freeze: bool, freeze_authority_bump: u8) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"freeze_authority",
+            self.namespace_seed,
+            &[freeze_authority_bump],
+        ]];
+        if freeze {
+            FreezeDelegatedAccountCpi::new(
+                self.metadata_program,
+                FreezeDelegatedAccountCpiAccounts {
+                    delegate: self.freeze_authority,
+                    token_account: self.token_account,
+                    edition: self.edition,
+                    mint: self.mint,
+                    token_program: self.token_program,
+                },
+            )
+            .invoke_signed(signer_seeds)?;
+        } else {
+            ThawDelegatedAccountCpi::new(
+                self.metadata_program,
+                ThawDelegatedAccountCpiAccounts {
+                    delegate: self.freeze_authority,
+                    token_account: self.token_account,
+                    edition: self.edition,
+                    mint: self.mint,
+                    token_program: self.token_program,
+                },
+            )
+            .invoke_signed(signer_seeds)?;
+        }
+        Ok(())
+    }
+}
+
+/// Splits `threshold` into the burned amount and the treasury share.
+fn split_threshold(threshold: u64, burn_bps: u16) -> (u64, u64) {
+    let burn_amount = (threshold as u128 * burn_bps as u128 / MAX_BPS as u128) as u64;
+    (burn_amount, threshold - burn_amount)
+}
+
+/// Current balance of a token account, read from its data rather than a
+/// snapshot that earlier CPIs in the instruction may have outdated.
+fn token_account_balance(info: &AccountInfo) -> Result<u64> {
+    Ok(TokenAccount::try_deserialize(&mut &info.try_borrow_data()?[..])?.amount)
+}
+
+/// Program of the Token-2022 transfer-hook extension on `mint`, if any.
+/// Classic SPL mints have no extensions.
+fn transfer_hook_program(mint: &AccountInfo) -> Result<Option<Pubkey>> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(None);
+    }
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    Ok(transfer_hook::get_program_id(&mint))
+}
+
+/// Accounts shared by every Orb fed in one instruction, so `feed_orb` and
+/// `feed_orbs_batch` run the same validation, burn and bridging logic.
+struct FeedShared<'a, 'info> {
+    forge_state: &'a mut Account<'info, ForgeState>,
+    feeder_stats: &'a mut Account<'info, FeederStats>,
+    pending_queue: &'a mut Account<'info, PendingQueue>,
+    rari_mint: &'a InterfaceAccount<'info, Mint>,
+    payment_mint_config: Option<&'a Account<'info, PaymentMintConfig>>,
+    user_rari_account: &'a mut InterfaceAccount<'info, TokenAccount>,
+    /// Delegate of `user_rari_account` paying in the user's place
+    delegate: Option<&'a Signer<'info>>,
+    treasury_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    escrow_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    /// Vault collecting `relayer_fee`
+    relayer_fee_vault: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    /// RARI paying the relayer fee when the feed pays in another mint; only
+    /// `feed_orb_with_sol` passes it
+    relayer_fee_rari: Option<RelayerFeeRari<'a, 'info>>,
+    /// Accounts a transfer hook on `rari_mint` resolves its extra accounts
+    /// from; only `feed_orb` passes any
+    transfer_hook_accounts: &'a [AccountInfo<'info>],
+    /// Owner of `user_rari_account`; signs unless a `delegate` pays for it
+    user: &'a AccountInfo<'info>,
+    /// Signer paying rent and SOL fees: the user, or whoever feeds in its place
+    payer: &'a AccountInfo<'info>,
+    fee_recipient: &'a UncheckedAccount<'info>,
+    wormhole_bridge: &'a Account<'info, wormhole::BridgeData>,
+    wormhole_fee_collector: &'a Account<'info, wormhole::FeeCollector>,
+    wormhole_emitter: &'a UncheckedAccount<'info>,
+    wormhole_emitter_bump: u8,
+    wormhole_sequence: &'a UncheckedAccount<'info>,
+    wormhole_program: &'a Program<'info, wormhole::program::Wormhole>,
+    token_program: &'a Interface<'info, TokenInterface>,
+    system_program: &'a Program<'info, System>,
+    clock: &'a Sysvar<'info, Clock>,
+    rent: &'a Sysvar<'info, Rent>,
+}
+
+/// RARI accounts of the user paying the relayer fee apart from the payment.
+struct RelayerFeeRari<'a, 'info> {
+    rari_mint: &'a InterfaceAccount<'info, Mint>,
+    user_rari_account: &'a InterfaceAccount<'info, TokenAccount>,
+    token_program: &'a Interface<'info, TokenInterface>,
+}
+
+/// Per-Orb inputs to `FeedShared::feed`. For compressed Orbs `orb_mint` is
+/// the Bubblegum asset id.
+struct OrbFeed<'a, 'info> {
+    orb_mint: Pubkey,
+    /// The `[b"claim", orb_mint]` record, listed in `PendingQueue` while `Pending`
+    claim: Pubkey,
+    /// Wallet credited with the claim; the paying `user` unless gifted
+    claimer: Pubkey,
+    orb_metadata: OrbMetadataSource<'a, 'info>,
+    chain_config: &'a AccountInfo<'info>,
+    wormhole_message: Option<&'a AccountInfo<'info>>,
+    chain_id: u16,
+    /// Most the caller agreed to be charged; `u64::MAX` when unbounded
+    max_burn: u64,
+    /// Signed rarity tier picking the RARI threshold; 0 for the base threshold
+    rarity_tier: u8,
+    /// Governance holder discount off the threshold; 0 for none
+    discount_bps: u16,
+    /// `feed_orb` memo; zero-filled when absent
+    memo: [u8; MEMO_LEN],
+    /// Position of the Orb within `feed_orbs_batch`; 0 for single feeds
+    batch_index: u16,
+}
+
+/// Where an Orb's metadata is read from.
+enum OrbMetadataSource<'a, 'info> {
+    /// Token Metadata account of an SPL Orb
+    Account(&'a AccountInfo<'info>),
+    /// Metadata of a compressed Orb, already verified against its leaf
+    Compressed(&'a CompressedOrbMetadata),
+}
+
+/// The parts of an Orb's metadata the feed validations read.
+struct OrbTraits {
+    name: String,
+    /// Collection the Orb is verified into, if any
+    verified_collection: Option<Pubkey>,
+    verified_creators: Vec<Pubkey>,
+    /// Metadata update authority; compressed Orbs carry none in their leaf
+    update_authority: Option<Pubkey>,
+    /// Whether the update authority can still change the metadata
+    is_mutable: bool,
+}
+
+impl OrbMetadataSource<'_, '_> {
+    fn traits(&self, orb_mint: &Pubkey) -> Result<OrbTraits> {
+        match self {
+            OrbMetadataSource::Account(info) => {
+                // Only the canonical Token Metadata PDA of the mint is trusted,
+                // so a look-alike account with a forged layout is rejected
+                require_keys_eq!(
+                    *info.owner,
+                    mpl_token_metadata::ID,
+                    ErrorCode::InvalidOrbMetadata
+                );
+                require_keys_eq!(
+                    info.key(),
+                    Metadata::find_pda(orb_mint).0,
+                    ErrorCode::InvalidOrbMetadata
+                );
+                let metadata = Metadata::try_from(*info)
+                    .map_err(|_| error!(ErrorCode::InvalidOrbMetadata))?;
+                require!(metadata.mint == *orb_mint, ErrorCode::InvalidOrbMetadata);
+                Ok(OrbTraits {
+                    name: metadata.name,
+                    verified_collection: metadata
+                        .collection
+                        .filter(|collection| collection.verified)
+                        .map(|collection| collection.key),
+                    verified_creators: metadata
+                        .creators
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|creator| creator.verified)
+                        .map(|creator| creator.address)
+                        .collect(),
+                    update_authority: Some(metadata.update_authority),
+                    is_mutable: metadata.is_mutable,
+                })
+            }
+            OrbMetadataSource::Compressed(metadata) => Ok(OrbTraits {
+                name: metadata.name.clone(),
+                verified_collection: metadata
+                    .collection
+                    .as_ref()
+                    .filter(|collection| collection.verified)
+                    .map(|collection| collection.key),
+                verified_creators: metadata
+                    .creators
+                    .iter()
+                    .filter(|creator| creator.verified)
+                    .map(|creator| creator.address)
+                    .collect(),
+                update_authority: None,
+                is_mutable: metadata.is_mutable,
+            }),
+        }
+    }
+}
+
+/// Read-only inputs to the feed validations, shared by `FeedShared::feed` and
+/// `check_feed_eligible` so the two never drift.
+struct FeedCheck<'a, 'info> {
+    forge_state: &'a Account<'info, ForgeState>,
+    feeder_stats: &'a FeederStats,
+    rari_mint: &'a InterfaceAccount<'info, Mint>,
+    payment_mint_config: Option<&'a Account<'info, PaymentMintConfig>>,
+    user_rari_account: &'a InterfaceAccount<'info, TokenAccount>,
+    treasury_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    /// Whether the feed is the prefunded first feed of a seeded claim
+    prefunded: bool,
+}
+
+/// What a validated feed charges.
+struct FeedQuote {
+    threshold: u64,
+    is_rari: bool,
+    name_hash: [u8; 32],
+    /// `ChainConfig::destination_mint` of the target; zero without a config
+    destination_mint: [u8; 32],
+}
+
+impl<'a, 'info> FeedCheck<'a, 'info> {
+    /// Runs every check `feed` makes before it moves funds, without mutating state.
+    fn validate(&self, orb: &OrbFeed<'_, 'info>, now: i64) -> Result<FeedQuote> {
+        let forge_state = self.forge_state;
+        let chain_id = orb.chain_id;
+
+        require!(
+            !forge_state.is_paused_at(PAUSE_FEED, now),
+            ErrorCode::ProgramPaused
+        );
+        forge_state.require_open(now)?;
+        require!(
+            forge_state.supported_chains.contains(&chain_id),
+            ErrorCode::UnsupportedChain
+        );
+        if is_cross_chain(chain_id) {
+            require!(
+                !forge_state.is_paused_at(PAUSE_CROSS_CHAIN_FEED, now),
+                ErrorCode::ProgramPaused
+            );
+        }
+
+        // Throttle per-user feeds within a rolling window
+        if forge_state.max_feeds_per_window > 0 {
+            require!(
+                self.feeder_stats.current_count(forge_state, now)
+                    < forge_state.max_feeds_per_window,
+                ErrorCode::FeedRateLimited
+            );
+        }
+
+        // Space out consecutive feeds by the same user
+        require!(
+            !self.feeder_stats.cooling_down(forge_state, now),
+            ErrorCode::UserCooldownActive
+        );
+
+        // Cap feeds across all users within the current epoch
+        if forge_state.epoch_cap > 0 {
+            require!(
+                forge_state.current_epoch_count_at(now)? < forge_state.epoch_cap,
+                ErrorCode::EpochCapReached
+            );
+        }
+
+        // Lifetime ceiling on fed Orbs
+        require!(forge_state.below_claim_cap(), ErrorCode::ClaimCapReached);
+
+        // Validate Orb ownership via Metaplex metadata
+        let traits = orb.orb_metadata.traits(&orb.orb_mint)?;
+
+        // Only Orbs verified into the approved collection may be fed
+        require!(
+            traits.verified_collection == Some(forge_state.approved_collection),
+            ErrorCode::OrbNotInCollection
+        );
+
+        // Pin the update authority so re-minted look-alikes are rejected
+        if let Some(update_authority) = traits.update_authority {
+            require!(
+                forge_state.accepts_update_authority(&update_authority),
+                ErrorCode::UpdateAuthorityNotAccepted
+            );
+        }
+
+        // Collection and creators could otherwise be rewritten after the check
+        require!(
+            !forge_state.require_immutable_metadata || !traits.is_mutable,
+            ErrorCode::MetadataMutable
+        );
+
+        // Metaplex pads names with trailing nulls; strip them so equal names hash equally
+        let name_hash = keccak::hash(traits.name.trim_end_matches('\0').as_bytes()).to_bytes();
+
+        // Optionally require a specific verified creator
+        let required_creator = forge_state.required_creator;
+        if required_creator != Pubkey::default() {
+            require!(
+                traits.verified_creators.contains(&required_creator),
+                ErrorCode::CreatorNotVerified
+            );
+        }
+
+        // A chain config, when present, overrides the global threshold
+        let chain_config = load_chain_config(orb.chain_config, &forge_state.namespace, chain_id)?;
+        if let Some(config) = &chain_config {
+            if !config.enabled {
+                forge_log!(
+                    "CHAIN",
+                    "chain {} disabled: {}",
+                    chain_id,
+                    config.disabled_reason()
+                );
+                return err!(ErrorCode::ChainDisabled);
+            }
+        }
+        let destination_mint = chain_config
+            .as_ref()
+            .map_or([0; 32], |config| config.destination_mint);
+
+        // Canonical RARI pays the (chain-specific) RARI threshold into the
+        // configured treasury; other mints pay their own threshold into a
+        // treasury account held by the forge. Any other mint, such as one the
+        // user controls, is refused before it can be burned in RARI's place
+        let is_rari = self.rari_mint.key() == forge_state.rari_mint;
+        let threshold = if is_rari {
+            require_keys_eq!(
+                self.treasury_token_account.key(),
+                forge_state.treasury,
+                ErrorCode::InvalidTreasury
+            );
+            let base = chain_config.map_or(forge_state.rari_threshold, |config| config.threshold);
+            forge_state.threshold_for_tier(orb.rarity_tier, base)
+        } else {
+            let payment_mint_config = self.payment_mint_config.ok_or(ErrorCode::WrongRariMint)?;
+            require!(
+                payment_mint_config.enabled,
+                ErrorCode::PaymentMintNotAccepted
+            );
+            require_keys_eq!(
+                self.treasury_token_account.owner,
+                forge_state.key(),
+                ErrorCode::InvalidTreasury
+            );
+            payment_mint_config.threshold
+        };
+
+        // A seeded claim's first feed is prefunded and an Orb burn can stand
+        // in for RARI; governance holders pay the share of the threshold
+        // their discount leaves
+        let threshold = if self.prefunded || forge_state.orb_burn_replaces_threshold() {
+            0
+        } else {
+            threshold
+        };
+        let (_, threshold) = split_threshold(threshold, orb.discount_bps);
+        // A RARI payment carries the relayer fee on top of the threshold
+        let total = if is_rari {
+            threshold
+                .checked_add(forge_state.relayer_fee_for(chain_id))
+                .ok_or(ErrorCode::MathOverflow)?
+        } else {
+            threshold
+        };
+        require!(total <= orb.max_burn, ErrorCode::BurnExceedsMax);
+        require!(
+            self.user_rari_account.amount >= total,
+            ErrorCode::InsufficientRariBalance
+        );
+
+        Ok(FeedQuote {
+            threshold,
+            is_rari,
+            name_hash,
+            destination_mint,
+        })
+    }
+}
+
+impl<'a, 'info> FeedShared<'a, 'info> {
+    /// Validates and feeds one Orb, filling in its claim record. Returns the
+    /// `OrbFedEvent` it emitted.
+    fn feed(
+        &mut self,
+        orb: OrbFeed<'_, 'info>,
+        claim_record: &mut ClaimRecord,
+        now: i64,
+    ) -> Result<OrbFedEvent> {
+        let FeedQuote {
+            threshold,
+            is_rari,
+            name_hash,
+            destination_mint,
+        } = FeedCheck {
+            forge_state: self.forge_state,
+            feeder_stats: self.feeder_stats,
+            rari_mint: self.rari_mint,
+            payment_mint_config: self.payment_mint_config,
+            user_rari_account: self.user_rari_account,
+            treasury_token_account: self.treasury_token_account,
+            prefunded: claim_record.awaits_seeded_feed(),
+        }
+        .validate(&orb, now)?;
+        let OrbFeed {
+            orb_mint,
+            claim,
+            claimer,
+            wormhole_message,
+            chain_id,
+            rarity_tier,
+            discount_bps,
+            memo,
+            batch_index,
+            ..
+        } = orb;
+
+        // An escrowed claim is settled or cancelled before it can be re-fed;
+        // escrow only covers RARI and same-chain feeds, which post nothing
+        require!(
+            claim_record.status != ClaimStatus::Escrowed,
+            ErrorCode::ClaimEscrowed
+        );
+        let escrow_mode = self.forge_state.escrow_mode;
+        if escrow_mode {
+            require!(
+                is_rari && !is_cross_chain(chain_id),
+                ErrorCode::InvalidEscrowFeed
+            );
+        }
+
+        // Count the feed against the paying user's window and the global epoch
+        self.feeder_stats.record(self.forge_state, now)?;
+        self.forge_state.record_epoch_feed(now)?;
+
+        // Trip the circuit breaker on a feed storm. This feed still lands, but
+        // later Orbs of a batch then fail, reverting the batch and the pause
+        if self.forge_state.record_minute_feed(now)? {
+            forge_log!("PAUSE", "auto feeds={}", self.forge_state.feeds_this_minute);
+            emit!(AutoPaused {
+                schema_version: EVENT_SCHEMA_VERSION,
+                seq: self.forge_state.event_seq,
+                feeds_this_minute: self.forge_state.feeds_this_minute,
+                anomaly_threshold: self.forge_state.anomaly_threshold,
+                minute_start: self.forge_state.minute_start,
+            });
+        }
+
+        // Charge the optional SOL feed fee
+        let sol_fee = self.forge_state.sol_fee_for(chain_id);
+        if sol_fee > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.payer.to_account_info(),
+                        to: self.fee_recipient.to_account_info(),
+                    },
+                ),
+                sol_fee,
+            )?;
+        }
+
+        // Cross-chain feeds also fund their relaying, in RARI on top of the threshold
+        let relayer_fee = self.forge_state.relayer_fee_for(chain_id);
+        // Paid alongside a RARI payment, or from separate RARI accounts
+        let relayer_fee_rari = if relayer_fee > 0 && !is_rari {
+            Some(
+                self.relayer_fee_rari
+                    .as_ref()
+                    .ok_or(ErrorCode::RelayerFeeNeedsRari)?,
+            )
+        } else {
+            None
+        };
+        let token_authority = self.token_authority(if relayer_fee_rari.is_none() {
+            threshold
+                .checked_add(relayer_fee)
+                .ok_or(ErrorCode::MathOverflow)?
+        } else {
+            threshold
+        })?;
+
+        // In escrow mode hold the whole threshold until `settle_feed`, recording
+        // what arrived net of any Token-2022 transfer fee
+        let escrowed = escrow_mode && threshold > 0;
+        let mut escrowed_amount = 0;
+        if escrowed {
+            let escrow_info = self
+                .escrow_token_account
+                .ok_or(ErrorCode::EscrowAccountMissing)?
+                .to_account_info();
+            let balance_before = token_account_balance(&escrow_info)?;
+            self.transfer_payment(escrow_info.clone(), token_authority.clone(), threshold)?;
+            escrowed_amount = token_account_balance(&escrow_info)? - balance_before;
+        }
+
+        // Burn required $RARI tokens, routing the non-burned share to the
+        // treasury. Wrapped SOL cannot be burned, so it all goes to the treasury.
+        let payable = if escrow_mode { 0 } else { threshold };
+        let burn_bps = if self.rari_mint.key() == NATIVE_MINT {
+            0
+        } else {
+            self.forge_state.burn_bps
+        };
+        let (burn_amount, treasury_amount) = split_threshold(payable, burn_bps);
+        if burn_amount > 0 {
+            let cpi_accounts = Burn {
+                mint: self.rari_mint.to_account_info(),
+                from: self.user_rari_account.to_account_info(),
+                authority: token_authority.clone(),
+            };
+            let cpi_program = self.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token_interface::burn(cpi_ctx, burn_amount)?;
+        }
+        if treasury_amount > 0 {
+            self.transfer_payment(
+                self.treasury_token_account.to_account_info(),
+                token_authority.clone(),
+                treasury_amount,
+            )?;
+        }
+        if relayer_fee > 0 {
+            let relayer_fee_vault = self
+                .relayer_fee_vault
+                .ok_or(ErrorCode::RelayerFeeVaultMissing)?;
+            if let Some(rari) = relayer_fee_rari {
+                let cpi_accounts = TransferChecked {
+                    from: rari.user_rari_account.to_account_info(),
+                    mint: rari.rari_mint.to_account_info(),
+                    to: relayer_fee_vault.to_account_info(),
+                    authority: self.user.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new(rari.token_program.to_account_info(), cpi_accounts);
+                token_interface::transfer_checked(cpi_ctx, relayer_fee, rari.rari_mint.decimals)?;
+            } else {
+                self.transfer_payment(
+                    relayer_fee_vault.to_account_info(),
+                    token_authority,
+                    relayer_fee,
+                )?;
+            }
+        }
+        // Refresh the balance so later Orbs in a batch see what is left
+        self.user_rari_account.reload()?;
+
+        // If targeting non-Solana chain, post a Wormhole message
+        let mut wormhole_fee = 0;
+        let wormhole_sequence = if is_cross_chain(chain_id) {
+            wormhole_fee = self.wormhole_bridge.fee();
+            let wormhole_message = wormhole_message.ok_or(ErrorCode::InvalidWormholeMessage)?;
+            let message = OrbFedMessage {
+                orb_mint,
+                claimer,
+                target_chain: chain_id,
+                rari_burned: burn_amount,
+                destination_mint,
+            };
+            Some(self.post_wormhole_message(wormhole_message, &message)?)
+        } else {
+            None
+        };
+
+        // Stamp the feed with the next index in the gap-free feed order
+        let claim_index = self.forge_state.assign_claim_index()?;
+
+        // A fresh record defaults to `Pending` without being counted as one
+        let was_pending = claim_record.counts_as_pending();
+        let first_feed = claim_record.feed_count == 0;
+
+        // Update claim record; `claimed_at` keeps the first feed
+        if claim_record.feed_count == 0 {
+            claim_record.claimed_at = now;
+        }
+        claim_record.version = CLAIM_RECORD_VERSION;
+        claim_record.orb_mint = orb_mint;
+        claim_record.claimer = claimer;
+        claim_record.target_chain = chain_id;
+        claim_record.wormhole_sequence = wormhole_sequence;
+        claim_record.feed_count = claim_record
+            .feed_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        claim_record.last_fed_at = now;
+        claim_record.name_hash = name_hash;
+        claim_record.escrowed_amount = escrowed_amount;
+        claim_record.claim_index = claim_index;
+        claim_record.rari_burned = if is_rari { burn_amount } else { 0 };
+        let burn_proof = burn_proof_for(
+            &orb_mint,
+            &claimer,
+            burn_amount,
+            claim_record.claimed_at,
+            chain_id,
+        );
+        claim_record.burn_proof = burn_proof;
+        claim_record.memo = memo;
+        claim_record.rarity_tier = rarity_tier;
+        claim_record.discount_bps = discount_bps;
+        // Same-chain feeds are settled here; cross-chain ones await a relay
+        claim_record.status = if escrowed {
+            ClaimStatus::Escrowed
+        } else if !is_cross_chain(chain_id) {
+            ClaimStatus::Confirmed
+        } else {
+            ClaimStatus::Pending
+        };
+        // Keep `pending_claims` and the relayers' queue in step as re-feeds
+        // move a claim in or out of `Pending`; a full queue refuses new ones
+        let is_pending = claim_record.status == ClaimStatus::Pending;
+        if is_pending && !was_pending {
+            self.forge_state.add_pending_claim()?;
+            self.pending_queue.push(claim)?;
+        } else if was_pending && !is_pending {
+            self.forge_state.release_pending_claim();
+            self.pending_queue.remove(&claim);
+        }
+
+        // Emit event for indexing, along with its fixed-width twin
+        let seq = self.forge_state.event_seq;
+        let event = OrbFedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            orb_mint,
+            payer: self.user.key(),
+            claimer,
+            target_chain: chain_id,
+            rari_burned: burn_amount,
+            wormhole_sequence,
+            name_hash,
+            route: route_for(chain_id),
+            forge_version: FORGE_VERSION,
+            claim_index,
+            burn_proof,
+            wormhole_fee,
+            memo,
+            batch_index,
+            batch_id: batch_id_for(&self.forge_state.namespace, seq),
+        };
+        let compact = OrbFedCompact::new(&event)?;
+        emit!(event);
+        emit!(compact);
+
+        self.forge_state
+            .record_feed(if is_rari { burn_amount } else { 0 })?;
+        if first_feed {
+            self.forge_state.add_active_claim()?;
+        }
+        forge_log!(
+            "FEED",
+            "orb={} payer={} claimer={} chain={} burned={} total_claimed={}",
+            orb_mint,
+            self.user.key(),
+            claimer,
+            chain_id,
+            burn_amount,
+            self.forge_state.total_claimed
+        );
+
+        emit!(ForgeStatsUpdated {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            total_claimed: self.forge_state.total_claimed,
+            total_rari_burned: self.forge_state.total_rari_burned,
+            active_claims: self.forge_state.active_claims,
+        });
+
+        Ok(event)
+    }
+
+    /// Signer moving `amount` out of `user_rari_account`: the delegate when one
+    /// is passed, which must hold an approval covering `amount`, else the user.
+    fn token_authority(&self, amount: u64) -> Result<AccountInfo<'info>> {
+        let Some(delegate) = self.delegate else {
+            return Ok(self.user.to_account_info());
+        };
+        require!(
+            self.user_rari_account.delegate == COption::Some(delegate.key()),
+            ErrorCode::InvalidDelegate
+        );
+        require!(
+            self.user_rari_account.delegated_amount >= amount,
+            ErrorCode::InsufficientDelegatedAmount
+        );
+        Ok(delegate.to_account_info())
+    }
+
+    /// Moves `amount` of the payment from the user to `to`. A Token-2022 mint
+    /// with a transfer hook goes through the hook-aware transfer, which adds
+    /// the extra accounts the hook's extra-account-metas PDA lists; burns never
+    /// run the hook, so only transfers need this.
+    fn transfer_payment(
+        &self,
+        to: AccountInfo<'info>,
+        authority: AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        if transfer_hook_program(&self.rari_mint.to_account_info())?.is_some() {
+            require!(
+                !self.transfer_hook_accounts.is_empty(),
+                ErrorCode::TransferHookAccountsMissing
+            );
+            spl_token_2022::onchain::invoke_transfer_checked(
+                &self.token_program.key(),
+                self.user_rari_account.to_account_info(),
+                self.rari_mint.to_account_info(),
+                to,
+                authority,
+                self.transfer_hook_accounts,
+                amount,
+                self.rari_mint.decimals,
+                &[],
+            )?;
+            return Ok(());
+        }
+        let cpi_accounts = TransferChecked {
+            from: self.user_rari_account.to_account_info(),
+            mint: self.rari_mint.to_account_info(),
+            to,
+            authority,
+        };
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+        token_interface::transfer_checked(cpi_ctx, amount, self.rari_mint.decimals)
+    }
+
+    /// Pays the Wormhole fee from the user and posts `message` through the core
+    /// bridge, signing as the forge emitter and the sequence-derived message PDA.
+    /// Returns the sequence the message was posted under.
+    fn post_wormhole_message(
+        &self,
+        wormhole_message: &AccountInfo<'info>,
+        message: &OrbFedMessage,
+    ) -> Result<u64> {
+        // Same-chain feeds settle in place and must never reach the bridge
+        require!(
+            is_cross_chain(message.target_chain),
+            ErrorCode::InvalidWormholeMessage
+        );
+        let payload = message.try_to_vec()?;
+
+        let fee = self.wormhole_bridge.fee();
+        if fee > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.payer.to_account_info(),
+                        to: self.wormhole_fee_collector.to_account_info(),
+                    },
+                ),
+                fee,
+            )?;
+        }
+        // The bridge only posts once its collector holds the fee on top of
+        // what it last recorded; checking here names the failure up front.
+        // Earlier posts in a batch move `last_lamports`, so it is read afresh
+        let collected = self.wormhole_fee_collector.to_account_info().lamports();
+        let last_lamports = bridge_last_lamports(&self.wormhole_bridge.to_account_info())?;
+        if !wormhole_fee_paid(collected, last_lamports, fee) {
+            forge_log!(
+                "WORMHOLE",
+                "fee mismatch: fee={} collected={} last_lamports={}",
+                fee,
+                collected,
+                last_lamports
+            );
+            return err!(ErrorCode::WormholeFeeMismatch);
+        }
+
+        // The core bridge creates the sequence tracker on the first post, so an
+        // empty account means this emitter has not sent anything yet.
+        let sequence = {
+            let data = self.wormhole_sequence.try_borrow_data()?;
+            if data.is_empty() {
+                0
+            } else {
+                wormhole::SequenceTracker::try_deserialize_unchecked(&mut &data[..])?.value()
+            }
+        };
+        let sequence_bytes = sequence.to_le_bytes();
+        let namespace_seed = self.forge_state.namespace_seed();
+        let (message_key, message_bump) = Pubkey::find_program_address(
+            &[SEED_PREFIX_SENT, namespace_seed, &sequence_bytes],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            wormhole_message.key(),
+            message_key,
+            ErrorCode::InvalidWormholeMessage
+        );
+
+        wormhole::post_message(
+            CpiContext::new_with_signer(
+                self.wormhole_program.to_account_info(),
+                wormhole::PostMessage {
+                    config: self.wormhole_bridge.to_account_info(),
+                    message: wormhole_message.clone(),
+                    emitter: self.wormhole_emitter.to_account_info(),
+                    sequence: self.wormhole_sequence.to_account_info(),
+                    payer: self.payer.to_account_info(),
+                    fee_collector: self.wormhole_fee_collector.to_account_info(),
+                    clock: self.clock.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                },
+                &[
+                    &[
+                        SEED_PREFIX_SENT,
+                        namespace_seed,
+                        &sequence_bytes,
+                        &[message_bump],
+                    ],
+                    &[
+                        wormhole::SEED_PREFIX_EMITTER,
+                        namespace_seed,
+                        self.forge_state.emitter_seed(),
+                        &[self.wormhole_emitter_bump],
+                    ],
+                ],
+            ),
+            0,
+            payload,
+            wormhole::Finality::Finalized,
+        )?;
+
+        Ok(sequence)
+    }
+}
+
+/// Reads the `[b"chain", namespace, chain_id]` config PDA, returning `None`
+/// when no config has been set for the chain.
+fn load_chain_config(
+    chain_config: &AccountInfo,
+    namespace: &[u8; 16],
+    chain_id: u16,
+) -> Result<Option<ChainConfig>> {
+    let (expected, _) = derive_chain_config_pda(namespace, chain_id);
+    require_keys_eq!(chain_config.key(), expected, ErrorCode::InvalidChainConfig);
+
+    if chain_config.owner != &crate::ID || chain_config.data_is_empty() {
+        return Ok(None);
+    }
+    let data = chain_config.try_borrow_data()?;
+    Ok(Some(ChainConfig::try_deserialize(&mut &data[..])?))
+}
+
+/// Creates the `[b"chain", chain_id]` PDA for a `batch_set_chain_configs`
+/// entry whose config does not exist yet.
+fn create_chain_config<'info>(
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    chain_config: &AccountInfo<'info>,
+    namespace: &[u8; 16],
+    chain_id: u16,
+    bump: u8,
+) -> Result<()> {
+    let space = 8 + ChainConfig::LEN;
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::CreateAccount {
+                from: payer.to_account_info(),
+                to: chain_config.clone(),
+            },
+            &[&[
+                b"chain",
+                namespace_seed(namespace),
+                &chain_id.to_le_bytes(),
+                &[bump],
+            ]],
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &crate::ID,
+    )
+}
+
+/// Creates the `[b"claim", orb_mint]` PDA for `feed_orb` and batch entries,
+/// failing if the Orb has already been fed.
+fn create_claim_record<'info>(
+    payer: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    claim_record: &AccountInfo<'info>,
+    namespace: &[u8; 16],
+    orb_mint: &Pubkey,
+) -> Result<()> {
+    let (expected, bump) = derive_claim_pda(namespace, orb_mint);
+    require_keys_eq!(
+        claim_record.key(),
+        expected,
+        ErrorCode::InvalidBatchAccounts
+    );
+
+    // An existing record means the Orb was claimed; report it rather than
+    // failing as an opaque "account already in use"
+    if !claim_record.data_is_empty() {
+        if claim_record.owner == &crate::ID {
+            let data = claim_record.try_borrow_data()?;
+            require_unclaimed(&ClaimRecord::try_deserialize(&mut &data[..])?)?;
+        }
+        return err!(ErrorCode::OrbAlreadyClaimed);
+    }
+
+    // Catch a payer short on SOL here rather than as an opaque system program error
+    let space = 8 + ClaimRecord::LEN;
+    let lamports = Rent::get()?.minimum_balance(space);
+    require!(
+        payer.lamports() >= lamports,
+        ErrorCode::InsufficientSolForRent
+    );
+
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::CreateAccount {
+                from: payer.to_account_info(),
+                to: claim_record.clone(),
+            },
+            &[&[
+                b"claim",
+                namespace_seed(namespace),
+                orb_mint.as_ref(),
+                &[bump],
+            ]],
+        ),
+        lamports,
+        space as u64,
+        &crate::ID,
+    )
+}
+
+/// Writes `claim_record`, discriminator included, into an account created by
+/// `create_claim_record`.
+fn write_claim_record(info: &AccountInfo, claim_record: &ClaimRecord) -> Result<()> {
+    let mut data = info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    claim_record.try_serialize(&mut writer)
+}
+
+/// Makes room for the `version` byte in a record from before it existed,
+/// moving its first `data_len` bytes past it. `data` must already be grown by
+/// at least one byte; the version is left as 0 for the caller to set.
+fn insert_claim_version(data: &mut [u8], data_len: usize) {
+    data.copy_within(8..data_len, 9);
+    data[8] = 0;
+}
+
+/// Reads an existing `[b"claim", orb_mint]` record, which `feed_orb` only
+/// accepts when `seed_claim` created it for `user` and it is yet to be fed.
+fn load_seeded_claim(info: &AccountInfo, user: &Pubkey) -> Result<ClaimRecord> {
+    require!(info.owner == &crate::ID, ErrorCode::OrbAlreadyClaimed);
+    let claim_record = {
+        let data = info.try_borrow_data()?;
+        ClaimRecord::try_deserialize(&mut &data[..])?
+    };
+    require_unclaimed(&claim_record)?;
+    require!(
+        claim_record.awaits_seeded_feed(),
+        ErrorCode::OrbAlreadyClaimed
+    );
+    require_keys_eq!(
+        claim_record.claimer,
+        *user,
+        ErrorCode::SeededClaimerMismatch
+    );
+    Ok(claim_record)
+}
+
+/// Fails with `ClaimerDenied` while the claimer's deny entry exists, which it
+/// does only while `deny_address` blocks the claimer.
+fn require_not_denied(deny_entry: &AccountInfo) -> Result<()> {
+    require!(deny_entry.data_is_empty(), ErrorCode::ClaimerDenied);
+    Ok(())
+}
+
+/// Fails with `OrbAlreadyClaimed` once `claim_record` has been fed, logging
+/// when the Orb was first claimed.
+fn require_unclaimed(claim_record: &ClaimRecord) -> Result<()> {
+    if claim_record.feed_count > 0 {
+        forge_log!(
+            "CLAIM",
+            "orb {} already claimed at {}",
+            claim_record.orb_mint,
+            claim_record.claimed_at
+        );
+        return err!(ErrorCode::OrbAlreadyClaimed);
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(params: InitializeParams)]
+pub struct Initialize<'info> {
+    // `init_if_needed` so a repeated call reaches the `AlreadyInitialized` check
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ForgeState::LEN,
+        seeds = [b"forge_state", namespace_seed(&params.namespace)],
+        bump
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + PendingQueue::space(params.pending_queue_size),
+        seeds = [b"pending_queue", namespace_seed(&params.namespace)],
+        bump
+    )]
+    pub pending_queue: Account<'info, PendingQueue>,
+    /// Read for its decimals
+    #[account(address = params.rari_mint @ ErrorCode::InvalidRariMint)]
+    pub rari_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    chain_id: u16,
+    proof: Vec<[u8; 32]>,
+    max_burn: u64,
+    rarity_tier: u8,
+    beneficiary: Pubkey
+)]
+pub struct FeedOrb<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(mut, seeds = [b"pending_queue", forge_state.namespace_seed()], bump)]
+    pub pending_queue: Account<'info, PendingQueue>,
+
+    /// CHECK: `[b"claim", orb_mint]` PDA, created in `feed_orb` once the user
+    /// is known to cover its rent
+    #[account(mut, seeds = [b"claim", forge_state.namespace_seed(), orb_mint.key().as_ref()], bump)]
+    pub claim_record: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + FeederStats::LEN,
+        seeds = [b"feeder", forge_state.namespace_seed(), user.key().as_ref()],
+        bump
+    )]
+    pub feeder_stats: Account<'info, FeederStats>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ChainCounter::LEN,
+        seeds = [b"chain_counter", forge_state.namespace_seed(), chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub chain_counter: Account<'info, ChainCounter>,
+
+    /// CHECK: `[b"deny", beneficiary]` PDA, which only exists while the
+    /// claimer is denied; checked to be empty in `feed_orb`
+    #[account(seeds = [b"deny", forge_state.namespace_seed(), beneficiary.as_ref()], bump)]
+    pub deny_entry: UncheckedAccount<'info>,
+
+    /// Writable so `orb_burn_amount` can be burned from its supply
+    #[account(mut)]
+    pub orb_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: Owner, PDA and layout verified in `OrbMetadataSource::traits`
+    pub orb_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: `[b"chain", chain_id]` PDA, which may not exist; verified and
+    /// deserialized in `feed_orb`
+    pub chain_config: UncheckedAccount<'info>,
+
+    /// The user's token account holding the Orb being fed. In `freeze_orb`
+    /// mode the user must first approve `orb_freeze_authority` as its delegate;
+    /// with `require_frozen_orb` it must already be frozen.
+    #[account(
+        mut,
+        constraint = orb_token_account.mint == orb_mint.key() @ ErrorCode::OrbNotOwned,
+        constraint = orb_token_account.owner == user.key() @ ErrorCode::OrbNotOwned,
+        constraint = orb_token_account.amount >= 1 @ ErrorCode::OrbNotOwned,
+    )]
+    pub orb_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Orb accounts used in `freeze_orb` mode and omitted otherwise.
+    /// CHECK: Orb master edition, verified by Token Metadata
+    pub orb_edition: Option<UncheckedAccount<'info>>,
+    /// CHECK: Forge PDA acting as the Orb's freeze delegate
+    #[account(seeds = [b"freeze_authority", forge_state.namespace_seed()], bump)]
+    pub orb_freeze_authority: Option<UncheckedAccount<'info>>,
+    /// Also required while `orb_burn_amount` is set
+    pub orb_token_program: Option<Program<'info, Token>>,
+    /// CHECK: Metaplex Token Metadata program
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: Option<UncheckedAccount<'info>>,
+
+    /// Receipt accounts used for cross-chain feeds while `receipt_mint` is
+    /// set, and omitted otherwise.
+    #[account(mut, address = forge_state.receipt_mint @ ErrorCode::InvalidReceiptMint)]
+    pub receipt_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = user_receipt_account.mint == forge_state.receipt_mint,
+        constraint = user_receipt_account.owner == user.key(),
+    )]
+    pub user_receipt_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub receipt_token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Bonus accounts used while the early bonus is still being granted, and
+    /// omitted otherwise; the bonus account must belong to the claimer.
+    #[account(mut, address = forge_state.early_bonus_mint @ ErrorCode::InvalidBonusMint)]
+    pub early_bonus_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = claimer_bonus_account.mint == forge_state.early_bonus_mint,
+    )]
+    pub claimer_bonus_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub bonus_token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Partner accounts used while a partner reward mint is set, and omitted
+    /// otherwise; the reward account must belong to the partner.
+    #[account(
+        mut,
+        address = forge_state.partner_reward_mint @ ErrorCode::InvalidPartnerRewardMint
+    )]
+    pub partner_reward_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = partner_reward_account.mint == forge_state.partner_reward_mint,
+        constraint = partner_reward_account.owner == forge_state.partner
+            @ ErrorCode::InvalidPartnerAccount,
+    )]
+    pub partner_reward_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub partner_token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// The user's points balance, required while `points_per_feed` is set
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PointsAccount::LEN,
+        seeds = [b"points", forge_state.namespace_seed(), user.key().as_ref()],
+        bump
+    )]
+    pub points_account: Option<Account<'info, PointsAccount>>,
+
+    /// CHECK: Instructions sysvar, read for the Ed25519 feed authorization;
+    /// required while `require_signed_feed` is set
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Token Metadata account of a governance NFT claiming the holder
+    /// discount, verified in `holder_discount_bps`; omit to pay in full
+    pub gov_metadata: Option<UncheckedAccount<'info>>,
+    /// The user's token account holding that governance NFT
+    pub gov_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Program called with each feed while `event_sink` is set; its
+    /// key is checked against `forge_state.event_sink` before the call
+    pub event_sink_program: Option<UncheckedAccount<'info>>,
+
+    /// The payment mint: canonical RARI or a mint accepted through
+    /// `add_payment_mint`. It may live under either the legacy Token program
+    /// or Token-2022; `token_program` must be the program that owns the mint.
+    #[account(mut, mint::token_program = token_program)]
+    pub rari_mint: InterfaceAccount<'info, Mint>,
+
+    /// Required when paying with anything other than canonical RARI.
+    #[account(seeds = [b"payment", forge_state.namespace_seed(), rari_mint.key().as_ref()], bump)]
+    pub payment_mint_config: Option<Account<'info, PaymentMintConfig>>,
+
+    #[account(
+        mut,
+        constraint = user_rari_account.owner == user.key(),
+        constraint = user_rari_account.mint == rari_mint.key(),
+        token::token_program = token_program,
+    )]
+    pub user_rari_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Approved delegate of `user_rari_account`, e.g. a custodian, that moves
+    /// the payment and feeds without the user's signature; omit to pay with
+    /// the user's signature
+    pub delegate: Option<Signer<'info>>,
+
+    #[account(
+        mut,
+        constraint = treasury_token_account.mint == rari_mint.key(),
+        token::token_program = token_program,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Forge-owned escrow created by `init_escrow`; required in escrow mode
+    #[account(
+        mut,
+        seeds = [b"escrow", forge_state.namespace_seed(), rari_mint.key().as_ref()],
+        bump,
+        token::mint = rari_mint,
+        token::token_program = token_program,
+    )]
+    pub escrow_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Forge-owned vault collecting `relayer_fee`; required for cross-chain
+    /// feeds while a relayer fee is set
+    #[account(
+        mut,
+        address = forge_state.relayer_fee_vault,
+        token::token_program = token_program,
+    )]
+    pub relayer_fee_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Owner of the Orb and of `user_rari_account`. Signs the feed
+    /// unless a `delegate` feeds in its place, checked in `feed_orb`
+    #[account(mut)]
+    pub user: UncheckedAccount<'info>,
+
+    /// Pays rent and SOL fees; any signer, not necessarily the user or delegate
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Only receives the SOL feed fee
+    #[account(mut, address = forge_state.fee_recipient @ ErrorCode::InvalidFeeRecipient)]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = forge_state.wormhole_bridge @ ErrorCode::InvalidWormholeBridge,
+        seeds = [wormhole::BridgeData::SEED_PREFIX],
+        bump,
+        seeds::program = wormhole_program.key(),
+    )]
+    pub wormhole_bridge: Account<'info, wormhole::BridgeData>,
+
+    #[account(
+        mut,
+        seeds = [wormhole::FeeCollector::SEED_PREFIX],
+        bump,
+        seeds::program = wormhole_program.key(),
+    )]
+    pub wormhole_fee_collector: Account<'info, wormhole::FeeCollector>,
+
+    /// CHECK: Forge emitter PDA, only used as a signer for the core bridge
+    #[account(
+        seeds = [
+            wormhole::SEED_PREFIX_EMITTER,
+            forge_state.namespace_seed(),
+            forge_state.emitter_seed()
+        ],
+        bump
+    )]
+    pub wormhole_emitter: UncheckedAccount<'info>,
+
+    /// CHECK: Emitter sequence tracker, created and written by the core bridge
+    #[account(
+        mut,
+        seeds = [wormhole::SequenceTracker::SEED_PREFIX, wormhole_emitter.key().as_ref()],
+        bump,
+        seeds::program = wormhole_program.key(),
+    )]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+
+    /// CHECK: Message PDA seeded by the next sequence, verified in `feed_orb`
+    /// and initialized by the core bridge
+    #[account(mut)]
+    pub wormhole_message: UncheckedAccount<'info>,
+
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> FeedOrb<'info> {
+    /// `discount_bps` when the user proves holding an NFT verified into
+    /// `discount_collection`: `gov_metadata` must be the Token Metadata PDA of
+    /// the mint in `gov_token_account`, which the user owns with a nonzero
+    /// balance. Passing neither account forgoes the discount.
+    fn holder_discount_bps(&self) -> Result<u16> {
+        if self.gov_metadata.is_none() && self.gov_token_account.is_none() {
+            return Ok(0);
+        }
+        let (Some(gov_metadata), Some(gov_token_account)) =
+            (&self.gov_metadata, &self.gov_token_account)
+        else {
+            return err!(ErrorCode::InvalidDiscountProof);
+        };
+        require!(
+            gov_token_account.owner == self.user.key() && gov_token_account.amount > 0,
+            ErrorCode::InvalidDiscountProof
+        );
+        let traits = OrbMetadataSource::Account(gov_metadata.as_ref())
+            .traits(&gov_token_account.mint)
+            .map_err(|_| error!(ErrorCode::InvalidDiscountProof))?;
+        let discount_collection = self.forge_state.discount_collection;
+        require!(
+            discount_collection != Pubkey::default()
+                && traits.verified_collection == Some(discount_collection),
+            ErrorCode::InvalidDiscountProof
+        );
+        Ok(self.forge_state.discount_bps)
+    }
+
+    /// Passes `event` to the `log_event` entrypoint of `forge_state.event_sink`,
+    /// if one is set. Unless `sink_fatal`, a sink that is not passed or that
+    /// refuses the call is logged and skipped; a sink program that fails
+    /// mid-execution still aborts the transaction, as the runtime requires.
+    fn forward_to_event_sink(&self, event: &OrbFedEvent) -> Result<()> {
+        let Some(event_sink) = self.forge_state.event_sink else {
+            return Ok(());
+        };
+        let forwarded = match &self.event_sink_program {
+            Some(program) if program.key() == event_sink => {
+                let instruction = log_event_instruction(event_sink, self.forge_state.key(), event)?;
+                anchor_lang::solana_program::program::invoke(
+                    &instruction,
+                    &[
+                        self.forge_state.to_account_info(),
+                        program.to_account_info(),
+                    ],
+                )
+                .map_err(Into::into)
+            }
+            _ => err!(ErrorCode::InvalidEventSink),
+        };
+        if let Err(error) = forwarded {
+            if self.forge_state.sink_fatal {
+                return Err(error);
+            }
+            forge_log!("SINK", "skipped {}: {}", event_sink, error);
+        }
+        Ok(())
+    }
+
+    /// Mints one receipt token to the user, signing as `forge_state`, the
+    /// receipt mint's authority.
+    fn mint_receipt(&self, forge_state_bump: u8) -> Result<()> {
+        let (Some(receipt_mint), Some(user_receipt_account), Some(receipt_token_program)) = (
+            &self.receipt_mint,
+            &self.user_receipt_account,
+            &self.receipt_token_program,
+        ) else {
+            return err!(ErrorCode::ReceiptAccountsMissing);
+        };
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"forge_state",
+            self.forge_state.namespace_seed(),
+            &[forge_state_bump],
+        ]];
+        let cpi_accounts = MintTo {
+            mint: receipt_mint.to_account_info(),
+            to: user_receipt_account.to_account_info(),
+            authority: self.forge_state.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            receipt_token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_interface::mint_to(cpi_ctx, 1)
+    }
+
+    /// Mints `early_bonus_amount` to the claimer's bonus account, signing as
+    /// `forge_state`, the bonus mint's authority.
+    fn mint_early_bonus(&self, forge_state_bump: u8, claimer: &Pubkey) -> Result<()> {
+        let (Some(bonus_mint), Some(claimer_bonus_account), Some(bonus_token_program)) = (
+            &self.early_bonus_mint,
+            &self.claimer_bonus_account,
+            &self.bonus_token_program,
+        ) else {
+            return err!(ErrorCode::BonusAccountsMissing);
+        };
+        require_keys_eq!(
+            claimer_bonus_account.owner,
+            *claimer,
+            ErrorCode::InvalidBonusAccount
+        );
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"forge_state",
+            self.forge_state.namespace_seed(),
+            &[forge_state_bump],
+        ]];
+        let cpi_accounts = MintTo {
+            mint: bonus_mint.to_account_info(),
+            to: claimer_bonus_account.to_account_info(),
+            authority: self.forge_state.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            bonus_token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_interface::mint_to(cpi_ctx, self.forge_state.early_bonus_amount)
+    }
+
+    /// Mints `amount` of the partner reward mint to the partner, signing as
+    /// `forge_state`.
+    fn mint_partner_share(&self, forge_state_bump: u8, amount: u64) -> Result<()> {
+        let (Some(reward_mint), Some(reward_account), Some(token_program)) = (
+            &self.partner_reward_mint,
+            &self.partner_reward_account,
+            &self.partner_token_program,
+        ) else {
+            return err!(ErrorCode::PartnerAccountsMissing);
+        };
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"forge_state",
+            self.forge_state.namespace_seed(),
+            &[forge_state_bump],
+        ]];
+        let cpi_accounts = MintTo {
+            mint: reward_mint.to_account_info(),
+            to: reward_account.to_account_info(),
+            authority: self.forge_state.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_interface::mint_to(cpi_ctx, amount)
+    }
+
+    /// Burns `amount` of the fed Orb's tokens from the user's Orb account.
+    fn burn_orb_tokens(&self, amount: u64) -> Result<()> {
+        let orb_token_program = self
+            .orb_token_program
+            .as_ref()
+            .ok_or(ErrorCode::OrbTokenProgramMissing)?;
+        let cpi_accounts = Burn {
+            mint: self.orb_mint.to_account_info(),
+            from: self.orb_token_account.to_account_info(),
+            authority: self.user.to_account_info(),
+        };
+        let cpi_program = orb_token_program.to_account_info();
+        token_interface::burn(CpiContext::new(cpi_program, cpi_accounts), amount)
+    }
+
+    fn orb_freeze_accounts(&self) -> Result<OrbFreezeAccounts<'_, 'info>> {
+        let (Some(edition), Some(freeze_authority), Some(token_program), Some(metadata_program)) = (
+            &self.orb_edition,
+            &self.orb_freeze_authority,
+            &self.orb_token_program,
+            &self.token_metadata_program,
+        ) else {
+            return err!(ErrorCode::FreezeAccountsMissing);
+        };
+        Ok(OrbFreezeAccounts {
+            namespace_seed: self.forge_state.namespace_seed(),
+            metadata_program: metadata_program.as_ref(),
+            freeze_authority: freeze_authority.as_ref(),
+            token_account: self.orb_token_account.as_ref(),
+            edition: edition.as_ref(),
+            mint: self.orb_mint.as_ref(),
+            token_program: token_program.as_ref(),
+        })
+    }
+
+    fn split(
+        &mut self,
+        wormhole_emitter_bump: u8,
+        chain_id: u16,
+        beneficiary: Pubkey,
+        max_burn: u64,
+        rarity_tier: u8,
+        discount_bps: u16,
+    ) -> (FeedShared<'_, 'info>, OrbFeed<'_, 'info>) {
+        let shared = FeedShared {
+            forge_state: &mut self.forge_state,
+            feeder_stats: &mut self.feeder_stats,
+            pending_queue: &mut self.pending_queue,
+            rari_mint: &self.rari_mint,
+            payment_mint_config: self.payment_mint_config.as_ref(),
+            user_rari_account: &mut self.user_rari_account,
+            delegate: self.delegate.as_ref(),
+            treasury_token_account: &self.treasury_token_account,
+            escrow_token_account: self.escrow_token_account.as_ref(),
+            relayer_fee_vault: self.relayer_fee_vault.as_ref(),
+            relayer_fee_rari: None,
+            transfer_hook_accounts: &[],
+            user: &self.user,
+            payer: &self.payer,
+            fee_recipient: &self.fee_recipient,
+            wormhole_bridge: &self.wormhole_bridge,
+            wormhole_fee_collector: &self.wormhole_fee_collector,
+            wormhole_emitter: &self.wormhole_emitter,
+            wormhole_emitter_bump,
+            wormhole_sequence: &self.wormhole_sequence,
+            wormhole_program: &self.wormhole_program,
+            token_program: &self.token_program,
+            system_program: &self.system_program,
+            clock: &self.clock,
+            rent: &self.rent,
+        };
+        let orb = OrbFeed {
+            orb_mint: self.orb_mint.key(),
+            claim: self.claim_record.key(),
+            claimer: beneficiary,
+            orb_metadata: OrbMetadataSource::Account(self.orb_metadata.as_ref()),
+            chain_config: self.chain_config.as_ref(),
+            wormhole_message: Some(self.wormhole_message.as_ref()),
+            chain_id,
+            max_burn,
+            rarity_tier,
+            discount_bps,
+            memo: [0; MEMO_LEN],
+            batch_index: 0,
+        };
+        (shared, orb)
+    }
+}
+
+#[derive(Accounts)]
+pub struct FeedOrbWithSol<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(mut, seeds = [b"pending_queue", forge_state.namespace_seed()], bump)]
+    pub pending_queue: Account<'info, PendingQueue>,
+
+    /// CHECK: `[b"claim", orb_mint]` PDA, created in `feed_orb_with_sol` once
+    /// the user is known to cover its rent
+    #[account(mut, seeds = [b"claim", forge_state.namespace_seed(), orb_mint.key().as_ref()], bump)]
+    pub claim_record: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + FeederStats::LEN,
+        seeds = [b"feeder", forge_state.namespace_seed(), user.key().as_ref()],
+        bump
+    )]
+    pub feeder_stats: Account<'info, FeederStats>,
+
+    pub orb_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: Owner, PDA and layout verified in `OrbMetadataSource::traits`
+    pub orb_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: `[b"chain", chain_id]` PDA, which may not exist; verified and
+    /// deserialized in `feed_orb_with_sol`
+    pub chain_config: UncheckedAccount<'info>,
+
+    /// CHECK: `[b"deny", user]` PDA, which only exists while the claimer is
+    /// denied; checked to be empty in `feed_orb_with_sol`
+    #[account(seeds = [b"deny", forge_state.namespace_seed(), user.key().as_ref()], bump)]
+    pub deny_entry: UncheckedAccount<'info>,
+
+    #[account(
+        address = NATIVE_MINT @ ErrorCode::NotNativeMint,
+        mint::token_program = token_program,
+    )]
+    pub native_mint: InterfaceAccount<'info, Mint>,
+
+    /// The native mint's config, which sets the SOL threshold
+    #[account(seeds = [b"payment", forge_state.namespace_seed(), native_mint.key().as_ref()], bump)]
+    pub payment_mint_config: Account<'info, PaymentMintConfig>,
+
+    /// Holds the wrapped SOL for this instruction only
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"wsol", forge_state.namespace_seed(), user.key().as_ref()],
+        bump,
+        token::mint = native_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub wsol_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = treasury_token_account.mint == native_mint.key(),
+        token::token_program = token_program,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// RARI accounts paying the relayer fee of a cross-chain feed while one
+    /// is set, and omitted otherwise
+    #[account(address = forge_state.rari_mint @ ErrorCode::InvalidRariMint)]
+    pub rari_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = user_rari_account.owner == user.key(),
+        constraint = user_rari_account.mint == forge_state.rari_mint,
+    )]
+    pub user_rari_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = forge_state.relayer_fee_vault)]
+    pub relayer_fee_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub rari_token_program: Option<Interface<'info, TokenInterface>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: Only receives the SOL feed fee
+    #[account(mut, address = forge_state.fee_recipient @ ErrorCode::InvalidFeeRecipient)]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = forge_state.wormhole_bridge @ ErrorCode::InvalidWormholeBridge,
+        seeds = [wormhole::BridgeData::SEED_PREFIX],
+        bump,
+        seeds::program = wormhole_program.key(),
+    )]
+    pub wormhole_bridge: Account<'info, wormhole::BridgeData>,
+
+    #[account(
+        mut,
+        seeds = [wormhole::FeeCollector::SEED_PREFIX],
+        bump,
+        seeds::program = wormhole_program.key(),
+    )]
+    pub wormhole_fee_collector: Account<'info, wormhole::FeeCollector>,
+
+    /// CHECK: Forge emitter PDA, only used as a signer for the core bridge
+    #[account(
+        seeds = [
+            wormhole::SEED_PREFIX_EMITTER,
+            forge_state.namespace_seed(),
+            forge_state.emitter_seed()
+        ],
+        bump
+    )]
+    pub wormhole_emitter: UncheckedAccount<'info>,
+
+    /// CHECK: Emitter sequence tracker, created and written by the core bridge
+    #[account(
+        mut,
+        seeds = [wormhole::SequenceTracker::SEED_PREFIX, wormhole_emitter.key().as_ref()],
+        bump,
+        seeds::program = wormhole_program.key(),
+    )]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+
+    /// CHECK: Message PDA seeded by the next sequence, verified in
+    /// `feed_orb_with_sol` and initialized by the core bridge
+    #[account(mut)]
+    pub wormhole_message: UncheckedAccount<'info>,
+
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> FeedOrbWithSol<'info> {
+    /// Moves `amount` lamports from the user into the WSOL account and syncs
+    /// its token balance.
+    fn wrap_sol(&mut self, amount: u64) -> Result<()> {
+        system_program::transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.user.to_account_info(),
+                    to: self.wsol_account.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        token_interface::sync_native(CpiContext::new(
+            self.token_program.to_account_info(),
+            SyncNative {
+                account: self.wsol_account.to_account_info(),
+            },
+        ))?;
+        self.wsol_account.reload()?;
+        Ok(())
+    }
+
+    /// Closes the WSOL account, returning its rent and any unspent SOL to the user.
+    fn close_wsol_account(&self) -> Result<()> {
+        token_interface::close_account(CpiContext::new(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.wsol_account.to_account_info(),
+                destination: self.user.to_account_info(),
+                authority: self.user.to_account_info(),
+            },
+        ))
+    }
+
+    fn split(
+        &mut self,
+        wormhole_emitter_bump: u8,
+        chain_id: u16,
+        max_burn: u64,
+    ) -> (FeedShared<'_, 'info>, OrbFeed<'_, 'info>) {
+        let shared = FeedShared {
+            forge_state: &mut self.forge_state,
+            feeder_stats: &mut self.feeder_stats,
+            pending_queue: &mut self.pending_queue,
+            rari_mint: &self.native_mint,
+            payment_mint_config: Some(&self.payment_mint_config),
+            user_rari_account: &mut self.wsol_account,
+            delegate: None,
+            treasury_token_account: &self.treasury_token_account,
+            escrow_token_account: None,
+            relayer_fee_vault: self.relayer_fee_vault.as_ref(),
+            relayer_fee_rari: match (
+                &self.rari_mint,
+                &self.user_rari_account,
+                &self.rari_token_program,
+            ) {
+                (Some(rari_mint), Some(user_rari_account), Some(token_program)) => {
+                    Some(RelayerFeeRari {
+                        rari_mint,
+                        user_rari_account,
+                        token_program,
+                    })
+                }
+                _ => None,
+            },
+            transfer_hook_accounts: &[],
+            user: &self.user,
+            payer: &self.user,
+            fee_recipient: &self.fee_recipient,
+            wormhole_bridge: &self.wormhole_bridge,
+            wormhole_fee_collector: &self.wormhole_fee_collector,
+            wormhole_emitter: &self.wormhole_emitter,
+            wormhole_emitter_bump,
+            wormhole_sequence: &self.wormhole_sequence,
+            wormhole_program: &self.wormhole_program,
+            token_program: &self.token_program,
+            system_program: &self.system_program,
+            clock: &self.clock,
+            rent: &self.rent,
+        };
+        let orb = OrbFeed {
+            orb_mint: self.orb_mint.key(),
+            claim: self.claim_record.key(),
+            claimer: self.user.key(),
+            orb_metadata: OrbMetadataSource::Account(self.orb_metadata.as_ref()),
+            chain_config: self.chain_config.as_ref(),
+            wormhole_message: Some(self.wormhole_message.as_ref()),
+            chain_id,
+            max_burn,
+            rarity_tier: 0,
+            discount_bps: 0,
+            memo: [0; MEMO_LEN],
+            batch_index: 0,
+        };
+        (shared, orb)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: Pubkey)]
+pub struct FeedCompressedOrb<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(mut, seeds = [b"pending_queue", forge_state.namespace_seed()], bump)]
+    pub pending_queue: Account<'info, PendingQueue>,
+
+    // `init_if_needed` so an already-claimed Orb reaches the `OrbAlreadyClaimed` check
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + ClaimRecord::LEN,
+        seeds = [b"claim", forge_state.namespace_seed(), asset_id.as_ref()],
+        bump
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + FeederStats::LEN,
+        seeds = [b"feeder", forge_state.namespace_seed(), user.key().as_ref()],
+        bump
+    )]
+    pub feeder_stats: Account<'info, FeederStats>,
+
+    /// CHECK: Concurrent Merkle tree holding the Orb's leaf; the leaf is
+    /// verified against it through the account compression program
+    #[account(owner = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: Leaf delegate, only hashed into the verified leaf
+    pub leaf_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: `[b"chain", chain_id]` PDA, which may not exist; verified and
+    /// deserialized in `feed_orb`
+    pub chain_config: UncheckedAccount<'info>,
+
+    /// CHECK: `[b"deny", user]` PDA, which only exists while the claimer is
+    /// denied; checked to be empty in `feed_compressed_orb`
+    #[account(seeds = [b"deny", forge_state.namespace_seed(), user.key().as_ref()], bump)]
+    pub deny_entry: UncheckedAccount<'info>,
+
+    /// The payment mint: canonical RARI or a mint accepted through
+    /// `add_payment_mint`. It may live under either the legacy Token program
+    /// or Token-2022; `token_program` must be the program that owns the mint.
+    #[account(mut, mint::token_program = token_program)]
+    pub rari_mint: InterfaceAccount<'info, Mint>,
+
+    /// Required when paying with anything other than canonical RARI.
+    #[account(seeds = [b"payment", forge_state.namespace_seed(), rari_mint.key().as_ref()], bump)]
+    pub payment_mint_config: Option<Account<'info, PaymentMintConfig>>,
+
+    #[account(
+        mut,
+        constraint = user_rari_account.owner == user.key(),
+        constraint = user_rari_account.mint == rari_mint.key(),
+        token::token_program = token_program,
+    )]
+    pub user_rari_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = treasury_token_account.mint == rari_mint.key(),
+        token::token_program = token_program,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Forge-owned escrow created by `init_escrow`; required in escrow mode
+    #[account(
+        mut,
+        seeds = [b"escrow", forge_state.namespace_seed(), rari_mint.key().as_ref()],
+        bump,
+        token::mint = rari_mint,
+        token::token_program = token_program,
+    )]
+    pub escrow_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Forge-owned vault collecting `relayer_fee`; required for cross-chain
+    /// feeds while a relayer fee is set
+    #[account(
+        mut,
+        address = forge_state.relayer_fee_vault,
+        token::token_program = token_program,
+    )]
+    pub relayer_fee_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: Only receives the SOL feed fee
+    #[account(mut, address = forge_state.fee_recipient @ ErrorCode::InvalidFeeRecipient)]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = forge_state.wormhole_bridge @ ErrorCode::InvalidWormholeBridge,
+        seeds = [wormhole::BridgeData::SEED_PREFIX],
+        bump,
+        seeds::program = wormhole_program.key(),
+    )]
+    pub wormhole_bridge: Account<'info, wormhole::BridgeData>,
+
+    #[account(
+        mut,
+        seeds = [wormhole::FeeCollector::SEED_PREFIX],
+        bump,
+        seeds::program = wormhole_program.key(),
+    )]
+    pub wormhole_fee_collector: Account<'info, wormhole::FeeCollector>,
+
+    /// CHECK: Forge emitter PDA, only used as a signer for the core bridge
+    #[account(
+        seeds = [
+            wormhole::SEED_PREFIX_EMITTER,
+            forge_state.namespace_seed(),
+            forge_state.emitter_seed()
+        ],
+        bump
+    )]
+    pub wormhole_emitter: UncheckedAccount<'info>,
+
+    /// CHECK: Emitter sequence tracker, created and written by the core bridge
+    #[account(
+        mut,
+        seeds = [wormhole::SequenceTracker::SEED_PREFIX, wormhole_emitter.key().as_ref()],
+        bump,
+        seeds::program = wormhole_program.key(),
+    )]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+
+    /// CHECK: Message PDA seeded by the next sequence, verified in `feed_orb`
+    /// and initialized by the core bridge
+    #[account(mut)]
+    pub wormhole_message: UncheckedAccount<'info>,
+
+    /// CHECK: SPL account compression program
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> FeedCompressedOrb<'info> {
+    fn split<'a>(
+        &'a mut self,
+        wormhole_emitter_bump: u8,
+        chain_id: u16,
+        asset_id: Pubkey,
+        metadata: &'a CompressedOrbMetadata,
+    ) -> (
+        FeedShared<'a, 'info>,
+        OrbFeed<'a, 'info>,
+        &'a mut Account<'info, ClaimRecord>,
+    ) {
+        let shared = FeedShared {
+            forge_state: &mut self.forge_state,
+            feeder_stats: &mut self.feeder_stats,
+            pending_queue: &mut self.pending_queue,
+            rari_mint: &self.rari_mint,
+            payment_mint_config: self.payment_mint_config.as_ref(),
+            user_rari_account: &mut self.user_rari_account,
+            delegate: None,
+            treasury_token_account: &self.treasury_token_account,
+            escrow_token_account: self.escrow_token_account.as_ref(),
+            relayer_fee_vault: self.relayer_fee_vault.as_ref(),
+            relayer_fee_rari: None,
+            transfer_hook_accounts: &[],
+            user: &self.user,
+            payer: &self.user,
+            fee_recipient: &self.fee_recipient,
+            wormhole_bridge: &self.wormhole_bridge,
+            wormhole_fee_collector: &self.wormhole_fee_collector,
+            wormhole_emitter: &self.wormhole_emitter,
+            wormhole_emitter_bump,
+            wormhole_sequence: &self.wormhole_sequence,
+            wormhole_program: &self.wormhole_program,
+            token_program: &self.token_program,
+            system_program: &self.system_program,
+            clock: &self.clock,
+            rent: &self.rent,
+        };
+        let orb = OrbFeed {
+            orb_mint: asset_id,
+            claim: self.claim_record.key(),
+            claimer: self.user.key(),
+            orb_metadata: OrbMetadataSource::Compressed(metadata),
+            chain_config: self.chain_config.as_ref(),
+            wormhole_message: Some(self.wormhole_message.as_ref()),
+            chain_id,
+            max_burn: u64::MAX,
+            rarity_tier: 0,
+            discount_bps: 0,
+            memo: [0; MEMO_LEN],
+            batch_index: 0,
+        };
+        (shared, orb, &mut self.claim_record)
+    }
+}
+
+#[derive(Accounts)]
+pub struct RefeedOrb<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(mut, seeds = [b"pending_queue", forge_state.namespace_seed()], bump)]
+    pub pending_queue: Account<'info, PendingQueue>,
+
+    #[account(
+        mut,
+        seeds = [b"claim", forge_state.namespace_seed(), orb_mint.key().as_ref()],
+        bump
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + FeederStats::LEN,
+        seeds = [b"feeder", forge_state.namespace_seed(), user.key().as_ref()],
+        bump
+    )]
+    pub feeder_stats: Account<'info, FeederStats>,
+
+    pub orb_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: Owner, PDA and layout verified in `OrbMetadataSource::traits`
+    pub orb_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: `[b"chain", chain_id]` PDA, which may not exist; verified and
+    /// deserialized in `refeed_orb`
+    pub chain_config: UncheckedAccount<'info>,
+
+    /// CHECK: `[b"deny", user]` PDA, which only exists while the claimer is
+    /// denied; checked to be empty in `refeed_orb`
+    #[account(seeds = [b"deny", forge_state.namespace_seed(), user.key().as_ref()], bump)]
+    pub deny_entry: UncheckedAccount<'info>,
+
+    /// The payment mint: canonical RARI or a mint accepted through
+    /// `add_payment_mint`. It may live under either the legacy Token program
+    /// or Token-2022; `token_program` must be the program that owns the mint.
+    #[account(mut, mint::token_program = token_program)]
+    pub rari_mint: InterfaceAccount<'info, Mint>,
+
+    /// Required when paying with anything other than canonical RARI.
+    #[account(seeds = [b"payment", forge_state.namespace_seed(), rari_mint.key().as_ref()], bump)]
+    pub payment_mint_config: Option<Account<'info, PaymentMintConfig>>,
+
+    #[account(
+        mut,
+        constraint = user_rari_account.owner == user.key(),
+        constraint = user_rari_account.mint == rari_mint.key(),
+        token::token_program = token_program,
+    )]
+    pub user_rari_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = treasury_token_account.mint == rari_mint.key(),
+        token::token_program = token_program,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Forge-owned escrow created by `init_escrow`; required in escrow mode
+    #[account(
+        mut,
+        seeds = [b"escrow", forge_state.namespace_seed(), rari_mint.key().as_ref()],
+        bump,
+        token::mint = rari_mint,
+        token::token_program = token_program,
+    )]
+    pub escrow_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Forge-owned vault collecting `relayer_fee`; required for cross-chain
+    /// feeds while a relayer fee is set
+    #[account(
+        mut,
+        address = forge_state.relayer_fee_vault,
+        token::token_program = token_program,
+    )]
+    pub relayer_fee_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: Only receives the SOL feed fee
+    #[account(mut, address = forge_state.fee_recipient @ ErrorCode::InvalidFeeRecipient)]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = forge_state.wormhole_bridge @ ErrorCode::InvalidWormholeBridge,
+        seeds = [wormhole::BridgeData::SEED_PREFIX],
+        bump,
+        seeds::program = wormhole_program.key(),
+    )]
+    pub wormhole_bridge: Account<'info, wormhole::BridgeData>,
+
+    #[account(
+        mut,
+        seeds = [wormhole::FeeCollector::SEED_PREFIX],
+        bump,
+        seeds::program = wormhole_program.key(),
+    )]
+    pub wormhole_fee_collector: Account<'info, wormhole::FeeCollector>,
+
+    /// CHECK: Forge emitter PDA, only used as a signer for the core bridge
+    #[account(
+        seeds = [
+            wormhole::SEED_PREFIX_EMITTER,
+            forge_state.namespace_seed(),
+            forge_state.emitter_seed()
+        ],
+        bump
+    )]
+    pub wormhole_emitter: UncheckedAccount<'info>,
+
+    /// CHECK: Emitter sequence tracker, created and written by the core bridge
+    #[account(
+        mut,
+        seeds = [wormhole::SequenceTracker::SEED_PREFIX, wormhole_emitter.key().as_ref()],
+        bump,
+        seeds::program = wormhole_program.key(),
+    )]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+
+    /// CHECK: Message PDA seeded by the next sequence, verified in `refeed_orb`
+    /// and initialized by the core bridge
+    #[account(mut)]
+    pub wormhole_message: UncheckedAccount<'info>,
+
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> RefeedOrb<'info> {
+    fn split(
+        &mut self,
+        wormhole_emitter_bump: u8,
+        chain_id: u16,
+    ) -> (
+        FeedShared<'_, 'info>,
+        OrbFeed<'_, 'info>,
+        &mut Account<'info, ClaimRecord>,
+    ) {
+        let shared = FeedShared {
+            forge_state: &mut self.forge_state,
+            feeder_stats: &mut self.feeder_stats,
+            pending_queue: &mut self.pending_queue,
+            rari_mint: &self.rari_mint,
+            payment_mint_config: self.payment_mint_config.as_ref(),
+            user_rari_account: &mut self.user_rari_account,
+            delegate: None,
+            treasury_token_account: &self.treasury_token_account,
+            escrow_token_account: self.escrow_token_account.as_ref(),
+            relayer_fee_vault: self.relayer_fee_vault.as_ref(),
+            relayer_fee_rari: None,
+            transfer_hook_accounts: &[],
+            user: &self.user,
+            payer: &self.user,
+            fee_recipient: &self.fee_recipient,
+            wormhole_bridge: &self.wormhole_bridge,
+            wormhole_fee_collector: &self.wormhole_fee_collector,
+            wormhole_emitter: &self.wormhole_emitter,
+            wormhole_emitter_bump,
+            wormhole_sequence: &self.wormhole_sequence,
+            wormhole_program: &self.wormhole_program,
+            token_program: &self.token_program,
+            system_program: &self.system_program,
+            clock: &self.clock,
+            rent: &self.rent,
+        };
+        let orb = OrbFeed {
+            orb_mint: self.orb_mint.key(),
+            claim: self.claim_record.key(),
+            claimer: self.user.key(),
+            orb_metadata: OrbMetadataSource::Account(self.orb_metadata.as_ref()),
+            chain_config: self.chain_config.as_ref(),
+            wormhole_message: Some(self.wormhole_message.as_ref()),
+            chain_id,
+            max_burn: u64::MAX,
+            rarity_tier: 0,
+            discount_bps: 0,
+            memo: [0; MEMO_LEN],
+            batch_index: 0,
+        };
+        (shared, orb, &mut self.claim_record)
+    }
+}
+
+#[derive(Accounts)]
+pub struct FeedOrbsBatch<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(mut, seeds = [b"pending_queue", forge_state.namespace_seed()], bump)]
+    pub pending_queue: Account<'info, PendingQueue>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + FeederStats::LEN,
+        seeds = [b"feeder", forge_state.namespace_seed(), user.key().as_ref()],
+        bump
+    )]
+    pub feeder_stats: Account<'info, FeederStats>,
+
+    /// The payment mint: canonical RARI or a mint accepted through
+    /// `add_payment_mint`. It may live under either the legacy Token program
+    /// or Token-2022; `token_program` must be the program that owns the mint.
+    #[account(mut, mint::token_program = token_program)]
+    pub rari_mint: InterfaceAccount<'info, Mint>,
+
+    /// Required when paying with anything other than canonical RARI.
+    #[account(seeds = [b"payment", forge_state.namespace_seed(), rari_mint.key().as_ref()], bump)]
+    pub payment_mint_config: Option<Account<'info, PaymentMintConfig>>,
+
+    #[account(
+        mut,
+        constraint = user_rari_account.owner == user.key(),
+        constraint = user_rari_account.mint == rari_mint.key(),
+        token::token_program = token_program,
+    )]
+    pub user_rari_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = treasury_token_account.mint == rari_mint.key(),
+        token::token_program = token_program,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Forge-owned escrow created by `init_escrow`; required in escrow mode
+    #[account(
+        mut,
+        seeds = [b"escrow", forge_state.namespace_seed(), rari_mint.key().as_ref()],
+        bump,
+        token::mint = rari_mint,
+        token::token_program = token_program,
+    )]
+    pub escrow_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Forge-owned vault collecting `relayer_fee`; required for cross-chain
+    /// feeds while a relayer fee is set
+    #[account(
+        mut,
+        address = forge_state.relayer_fee_vault,
+        token::token_program = token_program,
+    )]
+    pub relayer_fee_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: Only receives the SOL feed fee
+    #[account(mut, address = forge_state.fee_recipient @ ErrorCode::InvalidFeeRecipient)]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = forge_state.wormhole_bridge @ ErrorCode::InvalidWormholeBridge,
+        seeds = [wormhole::BridgeData::SEED_PREFIX],
+        bump,
+        seeds::program = wormhole_program.key(),
+    )]
+    pub wormhole_bridge: Account<'info, wormhole::BridgeData>,
+
+    #[account(
+        mut,
+        seeds = [wormhole::FeeCollector::SEED_PREFIX],
+        bump,
+        seeds::program = wormhole_program.key(),
+    )]
+    pub wormhole_fee_collector: Account<'info, wormhole::FeeCollector>,
+
+    /// CHECK: Forge emitter PDA, only used as a signer for the core bridge
+    #[account(
+        seeds = [
+            wormhole::SEED_PREFIX_EMITTER,
+            forge_state.namespace_seed(),
+            forge_state.emitter_seed()
+        ],
+        bump
+    )]
+    pub wormhole_emitter: UncheckedAccount<'info>,
+
+    /// CHECK: Emitter sequence tracker, created and written by the core bridge
+    #[account(
+        mut,
+        seeds = [wormhole::SequenceTracker::SEED_PREFIX, wormhole_emitter.key().as_ref()],
+        bump,
+        seeds::program = wormhole_program.key(),
+    )]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> FeedOrbsBatch<'info> {
+    fn shared(&mut self, wormhole_emitter_bump: u8) -> FeedShared<'_, 'info> {
+        FeedShared {
+            forge_state: &mut self.forge_state,
+            feeder_stats: &mut self.feeder_stats,
+            pending_queue: &mut self.pending_queue,
+            rari_mint: &self.rari_mint,
+            payment_mint_config: self.payment_mint_config.as_ref(),
+            user_rari_account: &mut self.user_rari_account,
+            delegate: None,
+            treasury_token_account: &self.treasury_token_account,
+            escrow_token_account: self.escrow_token_account.as_ref(),
+            relayer_fee_vault: self.relayer_fee_vault.as_ref(),
+            relayer_fee_rari: None,
+            transfer_hook_accounts: &[],
+            user: &self.user,
+            payer: &self.user,
+            fee_recipient: &self.fee_recipient,
+            wormhole_bridge: &self.wormhole_bridge,
+            wormhole_fee_collector: &self.wormhole_fee_collector,
+            wormhole_emitter: &self.wormhole_emitter,
+            wormhole_emitter_bump,
+            wormhole_sequence: &self.wormhole_sequence,
+            wormhole_program: &self.wormhole_program,
+            token_program: &self.token_program,
+            system_program: &self.system_program,
+            clock: &self.clock,
+            rent: &self.rent,
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct TogglePause<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        constraint = forge_state.can_pause(signer.key) @ ErrorCode::UnauthorizedOperator
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    /// Authority or operator
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetOperator<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateSupportedChains<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddUpdateAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveUpdateAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMaxTotalClaims<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFreezeOrb<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct DenyAddress<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + DenyEntry::LEN,
+        seeds = [b"deny", forge_state.namespace_seed(), address.as_ref()],
+        bump
+    )]
+    pub deny_entry: Account<'info, DenyEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct AllowAddress<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"deny", forge_state.namespace_seed(), address.as_ref()],
+        bump
+    )]
+    pub deny_entry: Account<'info, DenyEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRequireFrozenOrb<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetOrbBurn<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetReceiptMint<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(mint::authority = forge_state)]
+    pub receipt_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(vaa_hash: [u8; 32])]
+pub struct BurnReceipt<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(
+        mut,
+        seeds = [b"redeemed".as_ref(), forge_state.namespace_seed(), &vaa_hash],
+        bump,
+        has_one = claimer
+    )]
+    pub inbound_claim: Account<'info, InboundClaim>,
+
+    /// Any receipt mint the forge issues from, including one since replaced
+    #[account(
+        mut,
+        mint::authority = forge_state,
+        mint::token_program = token_program,
+    )]
+    pub receipt_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = receipt_mint,
+        token::authority = claimer,
+        token::token_program = token_program,
+    )]
+    pub claimer_receipt_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub claimer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct UnfreezeOrb<'info> {
+    #[account(seeds = [b"forge_state", forge_state.namespace_seed()], bump, has_one = authority)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    pub orb_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, constraint = orb_token_account.mint == orb_mint.key())]
+    pub orb_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: Orb master edition, verified by Token Metadata
+    pub orb_edition: UncheckedAccount<'info>,
+    /// CHECK: Forge PDA acting as the Orb's freeze delegate
+    #[account(seeds = [b"freeze_authority", forge_state.namespace_seed()], bump)]
+    pub orb_freeze_authority: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+    pub orb_token_program: Program<'info, Token>,
+    /// CHECK: Metaplex Token Metadata program
+    #[account(address = mpl_token_metadata::ID)]
+    pub token_metadata_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPauseFlags<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        constraint = forge_state.can_pause(signer.key) @ ErrorCode::UnauthorizedOperator
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    /// Authority or operator
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPointsPerFeed<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemPoints<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(
+        mut,
+        seeds = [b"points", forge_state.namespace_seed(), user.key().as_ref()],
+        bump
+    )]
+    pub points_account: Account<'info, PointsAccount>,
+
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCounters<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRequireImmutableMetadata<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMemoIsText<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAnomalyThreshold<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFreeFeed<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateThreshold<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority,
+        has_one = rari_mint @ ErrorCode::InvalidRariMint
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    /// Checked against `forge_state.rari_decimals`
+    pub rari_mint: InterfaceAccount<'info, Mint>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeThreshold<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority,
+        has_one = rari_mint @ ErrorCode::InvalidRariMint
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    /// Checked against `forge_state.rari_decimals`
+    pub rari_mint: InterfaceAccount<'info, Mint>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyThreshold<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTimelock<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateUserCooldown<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateWindow<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateEpochConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRateLimit<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateDiscount<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateFeeSplit<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRequiredCreator<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateSolFee<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetClockSkewTolerance<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetChargeSolFeeSameChain<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRefeedCooldown<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMaxFeedsPerOrb<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMerkleRoot<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeedSigner<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTierThresholds<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RotateEmitter<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateCloseDelay<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGovernance<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeChange<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + ProposalAccount::LEN,
+        seeds = [b"proposal", forge_state.namespace_seed(), forge_state.next_proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, ProposalAccount>,
+
+    /// Must be a governance signer
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ApproveChange<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", forge_state.namespace_seed(), proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, ProposalAccount>,
+
+    /// Must be a governance signer
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ExecuteChange<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", forge_state.namespace_seed(), proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, ProposalAccount>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRedeemedTtl<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BatchSetChainConfigs<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump, has_one = authority)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id: u16)]
+pub struct SetChainConfig<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump, has_one = authority)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ChainConfig::LEN,
+        seeds = [b"chain", forge_state.namespace_seed(), chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id: u16)]
+pub struct SetDestinationMint<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump, has_one = authority)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ChainConfig::LEN,
+        seeds = [b"chain", forge_state.namespace_seed(), chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id: u16)]
+pub struct PauseChain<'info> {
+    #[account(seeds = [b"forge_state", forge_state.namespace_seed()], bump, has_one = authority)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ChainConfig::LEN,
+        seeds = [b"chain", forge_state.namespace_seed(), chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id: u16)]
+pub struct RemoveChainConfig<'info> {
+    #[account(seeds = [b"forge_state", forge_state.namespace_seed()], bump, has_one = authority)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"chain", forge_state.namespace_seed(), chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(orb_mint: Pubkey)]
+pub struct SeedClaim<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump, has_one = authority)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ClaimRecord::LEN,
+        seeds = [b"claim", forge_state.namespace_seed(), orb_mint.as_ref()],
+        bump
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: ReissueClaimParams)]
+pub struct ReissueClaim<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump, has_one = authority)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(mut, seeds = [b"pending_queue", forge_state.namespace_seed()], bump)]
+    pub pending_queue: Account<'info, PendingQueue>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ClaimRecord::LEN,
+        seeds = [b"claim", forge_state.namespace_seed(), params.orb_mint.as_ref()],
+        bump
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseClaim<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(mut, seeds = [b"pending_queue", forge_state.namespace_seed()], bump)]
+    pub pending_queue: Account<'info, PendingQueue>,
+
+    #[account(
+        mut,
+        close = claimer,
+        seeds = [b"claim", forge_state.namespace_seed(), claim_record.orb_mint.as_ref()],
+        bump,
+        has_one = claimer
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateExpiryConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id: u16)]
+pub struct SetTrustedEmitter<'info> {
+    #[account(seeds = [b"forge_state", forge_state.namespace_seed()], bump, has_one = authority)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + TrustedEmitter::LEN,
+        seeds = [b"emitter", forge_state.namespace_seed(), chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub trusted_emitter: Account<'info, TrustedEmitter>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(vaa_hash: [u8; 32])]
+pub struct RedeemClaim<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    /// Posted and signature-verified by the core bridge
+    #[account(
+        seeds = [wormhole::SEED_PREFIX_POSTED_VAA, &vaa_hash],
+        bump,
+        seeds::program = wormhole_program.key(),
+    )]
+    pub posted_vaa: Account<'info, wormhole::PostedVaa<OrbFedMessage>>,
+
+    #[account(
+        seeds = [b"emitter", forge_state.namespace_seed(), posted_vaa.emitter_chain().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub trusted_emitter: Account<'info, TrustedEmitter>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + InboundClaim::LEN,
+        seeds = [b"redeemed".as_ref(), forge_state.namespace_seed(), &vaa_hash],
+        bump
+    )]
+    pub inbound_claim: Account<'info, InboundClaim>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(vaa_hash: [u8; 32])]
+pub struct PruneRedeemed<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(
+        mut,
+        close = caller,
+        seeds = [b"redeemed".as_ref(), forge_state.namespace_seed(), &vaa_hash],
+        bump
+    )]
+    pub inbound_claim: Account<'info, InboundClaim>,
+
+    /// Anyone; receives the marker's rent
+    #[account(mut)]
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(vaa_hash: [u8; 32])]
+pub struct ConfirmClaim<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(mut, seeds = [b"pending_queue", forge_state.namespace_seed()], bump)]
+    pub pending_queue: Account<'info, PendingQueue>,
+
+    #[account(
+        mut,
+        seeds = [b"claim", forge_state.namespace_seed(), claim_record.orb_mint.as_ref()],
+        bump
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    /// Acknowledgment posted and signature-verified by the core bridge
+    #[account(
+        seeds = [wormhole::SEED_PREFIX_POSTED_VAA, &vaa_hash],
+        bump,
+        seeds::program = wormhole_program.key(),
+    )]
+    pub posted_vaa: Account<'info, wormhole::PostedVaa<ClaimAckMessage>>,
+
+    #[account(
+        seeds = [b"emitter", forge_state.namespace_seed(), claim_record.target_chain.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub trusted_emitter: Account<'info, TrustedEmitter>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ClaimAck::LEN,
+        seeds = [b"acked".as_ref(), forge_state.namespace_seed(), &vaa_hash],
+        bump
+    )]
+    pub claim_ack: Account<'info, ClaimAck>,
+
+    /// Anyone; pays the marker's rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireClaim<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority,
+        has_one = rent_recipient
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(mut, seeds = [b"pending_queue", forge_state.namespace_seed()], bump)]
+    pub pending_queue: Account<'info, PendingQueue>,
+
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [b"claim", forge_state.namespace_seed(), claim_record.orb_mint.as_ref()],
+        bump
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    /// CHECK: Only receives lamports; must match `forge_state.rent_recipient`
+    #[account(mut)]
+    pub rent_recipient: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetEscrowMode<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetEventSink<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetEarlyBonus<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(mint::authority = forge_state)]
+    pub early_bonus_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPartnerShare<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(mint::authority = forge_state)]
+    pub partner_reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitEscrow<'info> {
+    #[account(seeds = [b"forge_state", forge_state.namespace_seed()], bump, has_one = authority, has_one = rari_mint)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(mint::token_program = token_program)]
+    pub rari_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"escrow", forge_state.namespace_seed(), rari_mint.key().as_ref()],
+        bump,
+        token::mint = rari_mint,
+        token::authority = forge_state,
+        token::token_program = token_program,
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitRelayerFeeVault<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority,
+        has_one = rari_mint
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(mint::token_program = token_program)]
+    pub rari_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"relayer_fees", forge_state.namespace_seed(), rari_mint.key().as_ref()],
+        bump,
+        token::mint = rari_mint,
+        token::authority = forge_state,
+        token::token_program = token_program,
+    )]
+    pub relayer_fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRelayerFee<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(relayer: Pubkey)]
+pub struct RegisterRelayer<'info> {
+    #[account(seeds = [b"forge_state", forge_state.namespace_seed()], bump, has_one = authority)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RelayerEntry::LEN,
+        seeds = [b"relayer", forge_state.namespace_seed(), relayer.as_ref()],
+        bump
+    )]
+    pub relayer_entry: Account<'info, RelayerEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(relayer: Pubkey)]
+pub struct RemoveRelayer<'info> {
+    #[account(seeds = [b"forge_state", forge_state.namespace_seed()], bump, has_one = authority)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"relayer", forge_state.namespace_seed(), relayer.as_ref()],
+        bump
+    )]
+    pub relayer_entry: Account<'info, RelayerEntry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRelayerFee<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump, has_one = rari_mint)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    /// Only exists for registered relayers
+    #[account(
+        mut,
+        seeds = [b"relayer", forge_state.namespace_seed(), relayer.key().as_ref()],
+        bump,
+        has_one = relayer
+    )]
+    pub relayer_entry: Account<'info, RelayerEntry>,
+
+    #[account(mint::token_program = token_program)]
+    pub rari_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        address = forge_state.relayer_fee_vault,
+        token::token_program = token_program,
+    )]
+    pub relayer_fee_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = relayer_rari_account.mint == rari_mint.key(),
+        token::token_program = token_program,
+    )]
+    pub relayer_rari_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub relayer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SettleFeed<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump, has_one = rari_mint)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(
+        mut,
+        seeds = [b"claim", forge_state.namespace_seed(), claim_record.orb_mint.as_ref()],
+        bump,
+        has_one = claimer
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    #[account(mut, mint::token_program = token_program)]
+    pub rari_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", forge_state.namespace_seed(), rari_mint.key().as_ref()],
+        bump,
+        token::token_program = token_program,
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = forge_state.treasury @ ErrorCode::InvalidTreasury,
+        token::token_program = token_program,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub claimer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CancelFeed<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump, has_one = rari_mint)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(
+        mut,
+        close = claimer,
+        seeds = [b"claim", forge_state.namespace_seed(), claim_record.orb_mint.as_ref()],
+        bump,
+        has_one = claimer
+    )]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    #[account(mint::token_program = token_program)]
+    pub rari_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", forge_state.namespace_seed(), rari_mint.key().as_ref()],
+        bump,
+        token::token_program = token_program,
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = claimer_rari_account.mint == rari_mint.key(),
+        token::token_program = token_program,
+    )]
+    pub claimer_rari_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RefundClaim<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority,
+        has_one = rari_mint @ ErrorCode::InvalidRariMint
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(mut, seeds = [b"claim", forge_state.namespace_seed(), claim_record.orb_mint.as_ref()], bump)]
+    pub claim_record: Account<'info, ClaimRecord>,
+
+    /// CHECK: `[b"chain", target_chain]` PDA of the claim, which may not
+    /// exist; verified and deserialized in `refund_claim`
+    pub chain_config: UncheckedAccount<'info>,
+
+    /// Minted from with `forge_state` as mint authority
+    #[account(mut, mint::token_program = token_program)]
+    pub rari_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = claimer_rari_account.mint == rari_mint.key(),
+        constraint = claimer_rari_account.owner == claim_record.claimer,
+        token::token_program = token_program,
+    )]
+    pub claimer_rari_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct AddPaymentMint<'info> {
+    #[account(seeds = [b"forge_state", forge_state.namespace_seed()], bump, has_one = authority)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + PaymentMintConfig::LEN,
+        seeds = [b"payment", forge_state.namespace_seed(), mint.key().as_ref()],
+        bump
+    )]
+    pub payment_mint_config: Account<'info, PaymentMintConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DisablePaymentMint<'info> {
+    #[account(seeds = [b"forge_state", forge_state.namespace_seed()], bump, has_one = authority)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(
+        mut,
+        seeds = [b"payment", forge_state.namespace_seed(), payment_mint_config.mint.as_ref()],
+        bump
+    )]
+    pub payment_mint_config: Account<'info, PaymentMintConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump, has_one = authority, has_one = rari_mint)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(mint::token_program = token_program)]
+    pub rari_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        address = forge_state.treasury,
+        token::token_program = token_program,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = destination.mint == rari_mint.key(),
+        token::token_program = token_program,
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SweepTokens<'info> {
+    #[account(mut, seeds = [b"forge_state", forge_state.namespace_seed()], bump, has_one = authority)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = forge_state,
+        token::token_program = token_program,
+    )]
+    pub source: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = mint, token::token_program = token_program)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CheckFeedEligible<'info> {
+    #[account(seeds = [b"forge_state", forge_state.namespace_seed()], bump)]
+    pub forge_state: Account<'info, ForgeState>,
+
+    /// CHECK: `[b"claim", orb_mint]` PDA, which must not exist yet unless seeded
+    /// for the user
+    #[account(seeds = [b"claim", forge_state.namespace_seed(), orb_mint.key().as_ref()], bump)]
+    pub claim_record: UncheckedAccount<'info>,
+
+    /// CHECK: `[b"feeder", user]` PDA, which may not exist yet
+    #[account(seeds = [b"feeder", forge_state.namespace_seed(), user.key().as_ref()], bump)]
+    pub feeder_stats: UncheckedAccount<'info>,
+
+    pub orb_mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: Owner, PDA and layout verified in `OrbMetadataSource::traits`
+    pub orb_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: `[b"chain", chain_id]` PDA, which may not exist; verified and
+    /// deserialized in `check_feed_eligible`
+    pub chain_config: UncheckedAccount<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub rari_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(seeds = [b"payment", forge_state.namespace_seed(), rari_mint.key().as_ref()], bump)]
+    pub payment_mint_config: Option<Account<'info, PaymentMintConfig>>,
+
+    #[account(
+        constraint = user_rari_account.owner == user.key(),
+        constraint = user_rari_account.mint == rari_mint.key(),
+        token::token_program = token_program,
+    )]
+    pub user_rari_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = treasury_token_account.mint == rari_mint.key(),
+        token::token_program = token_program,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: The wallet being checked; simulations need no signature
+    pub user: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CheckFeedEligible<'info> {
+    fn check(&self, chain_id: u16, proof: &[[u8; 32]], now: i64) -> Result<()> {
+        let prefunded = !self.claim_record.data_is_empty();
+        if prefunded {
+            load_seeded_claim(&self.claim_record, &self.user.key())?;
+        }
+        require_allowlisted(&self.forge_state, &self.orb_mint.key(), proof)?;
+
+        let feeder_stats = if self.feeder_stats.owner == &crate::ID {
+            let data = self.feeder_stats.try_borrow_data()?;
+            FeederStats::try_deserialize(&mut &data[..])?
+        } else {
+            FeederStats::default()
+        };
+        let orb = OrbFeed {
+            orb_mint: self.orb_mint.key(),
+            claim: self.claim_record.key(),
+            claimer: self.user.key(),
+            orb_metadata: OrbMetadataSource::Account(self.orb_metadata.as_ref()),
+            chain_config: self.chain_config.as_ref(),
+            wormhole_message: None,
+            chain_id,
+            max_burn: u64::MAX,
+            rarity_tier: 0,
+            discount_bps: 0,
+            memo: [0; MEMO_LEN],
+            batch_index: 0,
+        };
+        FeedCheck {
+            forge_state: &self.forge_state,
+            feeder_stats: &feeder_stats,
+            rari_mint: &self.rari_mint,
+            payment_mint_config: self.payment_mint_config.as_ref(),
+            user_rari_account: &self.user_rari_account,
+            treasury_token_account: &self.treasury_token_account,
+            prefunded,
+        }
+        .validate(&orb, now)?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(namespace: [u8; 16])]
+pub struct MigrateState<'info> {
+    /// CHECK: may be too small to deserialize as `ForgeState` until reallocated;
+    /// its discriminator and authority are checked in `migrate_state`
+    #[account(
+        mut,
+        seeds = [b"forge_state", namespace_seed(&namespace)],
+        bump,
+        owner = crate::ID
+    )]
+    pub forge_state: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Required when migrating from before layout 13, to record its decimals
+    pub rari_mint: Option<InterfaceAccount<'info, Mint>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(orb_mint: Pubkey)]
+pub struct MigrateClaim<'info> {
+    #[account(seeds = [b"forge_state", forge_state.namespace_seed()], bump)]
+    pub forge_state: Account<'info, ForgeState>,
+    /// CHECK: may be too small to deserialize as `ClaimRecord` until
+    /// reallocated; its discriminator and claimer are checked in `migrate_claim`
+    #[account(
+        mut,
+        seeds = [b"claim", forge_state.namespace_seed(), orb_mint.as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub claim_record: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetStats<'info> {
+    #[account(seeds = [b"forge_state", forge_state.namespace_seed()], bump)]
+    pub forge_state: Account<'info, ForgeState>,
+}
+
+#[derive(Accounts)]
+pub struct CloseForge<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"pending_queue", forge_state.namespace_seed()],
+        bump
+    )]
+    pub pending_queue: Account<'info, PendingQueue>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TransferAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetEmergencyAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyHalt<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        has_one = emergency_authority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub emergency_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"forge_state", forge_state.namespace_seed()],
+        bump,
+        constraint = forge_state.pending_authority == Some(pending_authority.key())
+            @ ErrorCode::UnauthorizedPendingAuthority
+    )]
+    pub forge_state: Account<'info, ForgeState>,
+    pub pending_authority: Signer<'info>,
+}
+
+#[account]
+#[derive(Default)]
+pub struct ForgeState {
+    pub authority: Pubkey,
+    pub wormhole_bridge: Pubkey,
+    pub rari_mint: Pubkey,
+    pub rari_threshold: u64,
+    pub total_claimed: u64,
+    pub pause_flags: u8,
+    pub pending_authority: Option<Pubkey>,
+    pub approved_collection: Pubkey,
+    pub max_feeds_per_window: u64,
+    pub window_seconds: i64,
+    pub treasury: Pubkey,
+    pub burn_bps: u16,
+    pub close_delay: i64,
+    pub total_rari_burned: u64,
+    pub is_initialized: bool,
+    pub required_creator: Pubkey,
+    pub epoch_seconds: i64,
+    pub epoch_cap: u64,
+    pub current_epoch_start: i64,
+    pub current_epoch_count: u64,
+    pub refeed_cooldown: i64,
+    pub merkle_root: [u8; 32],
+    pub allow_free_feed: bool,
+    pub expiry_seconds: i64,
+    pub rent_recipient: Pubkey,
+    pub sol_fee_lamports: u64,
+    pub fee_recipient: Pubkey,
+    pub freeze_orb: bool,
+    pub max_total_claims: u64,
+    pub operator: Pubkey,
+    pub layout_version: u8,
+    pub supported_chains: Vec<u16>,
+    pub escrow_mode: bool,
+    pub halted: bool,
+    /// Earliest time `clear_emergency_halt` may lift the current halt
+    pub halt_cleared_at: i64,
+    pub emergency_authority: Pubkey,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    /// Mint of the cross-chain feed receipts; `Pubkey::default()` when disabled
+    pub receipt_mint: Pubkey,
+    pub user_cooldown_seconds: i64,
+    /// Index `feed` stamps on the next feed
+    pub next_claim_index: u64,
+    /// Metadata update authorities Orbs must have; empty accepts any
+    pub accepted_update_authorities: Vec<Pubkey>,
+    /// Cross-chain claims still `Pending`; `close_forge` requires none
+    pub pending_claims: u64,
+    /// How long redemption markers outlive `VAA_FINALITY_WINDOW`; 0 disables pruning
+    pub redeemed_ttl: i64,
+    /// `redeemed_at` of the newest pruned marker; older VAAs are not redeemable
+    pub pruned_through: i64,
+    /// Signers approving threshold and fee changes once governance is on
+    pub governance_signers: Vec<Pubkey>,
+    /// Approvals a proposal needs; 0 leaves those changes to the authority
+    pub governance_threshold: u8,
+    /// Bumped on every signer-set change so older proposals go stale
+    pub governance_nonce: u64,
+    pub next_proposal_id: u64,
+    /// Decimals of `rari_mint`; amounts stay in base units on-chain
+    pub rari_decimals: u8,
+    /// Backend key whose Ed25519 signature authorizes feeds
+    pub feed_signer: Pubkey,
+    /// Whether `feed_orb` requires a `feed_signer` authorization
+    pub require_signed_feed: bool,
+    /// RARI thresholds of rarity tiers 1 to `MAX_RARITY_TIER`; 0 falls back
+    /// to the base threshold
+    pub tier_thresholds: [u64; MAX_RARITY_TIER as usize],
+    /// Orbs with a live claim record, unlike the lifetime `total_claimed`
+    pub active_claims: u64,
+    /// Seed version of the Wormhole emitter PDA, bumped by `rotate_emitter`
+    pub emitter_seed_version: u8,
+    /// Most times a single Orb may be fed, re-feeds included; 0 = unlimited
+    pub max_feeds_per_orb: u16,
+    /// Collection whose holders feed at a discount; default disables it
+    pub discount_collection: Pubkey,
+    /// Share of the threshold waived for `discount_collection` holders
+    pub discount_bps: u16,
+    /// Delay between `propose_threshold` and `apply_threshold`; 0 allows
+    /// immediate `update_threshold` changes
+    pub timelock_seconds: i64,
+    /// Threshold queued by `propose_threshold`
+    pub pending_threshold: u64,
+    /// When `pending_threshold` can be applied; 0 when none is queued
+    pub threshold_effective_at: i64,
+    /// Program `feed_orb` forwards each `OrbFedEvent` to; `None` disables it
+    pub event_sink: Option<Pubkey>,
+    /// Whether a sink that cannot be called fails the feed
+    pub sink_fatal: bool,
+    /// Number of first-fed Orbs whose claimer receives the early bonus
+    pub early_bonus_count: u64,
+    /// Mint the early bonus is issued from; `Pubkey::default()` when disabled
+    pub early_bonus_mint: Pubkey,
+    /// Bonus amount per Orb, in the bonus mint's base units
+    pub early_bonus_amount: u64,
+    /// When a `pause_for` pause lapses; 0 while paused indefinitely
+    pub pause_expires_at: i64,
+    /// Namespace seeding this forge's PDAs; all zero for the original forge
+    pub namespace: [u8; 16],
+    /// Whether feeds staying on Solana pay `sol_fee_lamports` too
+    pub charge_sol_fee_same_chain: bool,
+    /// Seconds of validator clock skew `is_after` forgives, at most
+    /// `MAX_CLOCK_SKEW_TOLERANCE`
+    pub clock_skew_tolerance: i64,
+    /// Orb tokens `feed_orb` burns from the user; 0 burns none
+    pub orb_burn_amount: u64,
+    /// Whether the Orb burn replaces the RARI threshold instead of adding to it
+    pub orb_burn_replaces_rari: bool,
+    /// Advanced once by each instruction that emits events and stamped into
+    /// them as `seq`, so an indexer resuming from its last `seq` skips replays
+    pub event_seq: u64,
+    /// Whether `feed_orb` only accepts Orbs frozen by a staking program
+    pub require_frozen_orb: bool,
+    /// Wallet receiving the partner share of each feed
+    pub partner: Pubkey,
+    /// Partner share of the RARI threshold, in basis points
+    pub partner_bps: u16,
+    /// Mint the partner share is issued from; `Pubkey::default()` when disabled
+    pub partner_reward_mint: Pubkey,
+    /// Feeds in one anomaly window that trip the circuit breaker; 0 disables it
+    pub anomaly_threshold: u64,
+    /// Feeds counted in the anomaly window starting at `minute_start`
+    pub feeds_this_minute: u64,
+    /// Start of the current `ANOMALY_WINDOW_SECONDS` window
+    pub minute_start: i64,
+    /// Whether `feed_orb` memos must be UTF-8 text rather than opaque bytes
+    pub memo_is_text: bool,
+    /// Set once `set_counters` has carried over a legacy forge's counters
+    pub counters_seeded: bool,
+    /// Loyalty points `feed_orb` accrues to the payer per feed; 0 disables them
+    pub points_per_feed: u64,
+    /// Addresses with a live `deny_address` entry
+    pub denied_count: u32,
+    /// Whether feeds only accept Orbs whose metadata can no longer be updated
+    pub require_immutable_metadata: bool,
+    /// RARI each cross-chain feed pays into `relayer_fee_vault`; 0 for none
+    pub relayer_fee: u64,
+    /// Forge-owned RARI vault registered relayers claim fees from;
+    /// `Pubkey::default()` until `init_relayer_fee_vault`
+    pub relayer_fee_vault: Pubkey,
+}
+
+impl ForgeState {
+    pub const LEN: usize = 32
+        + 32
+        + 32
+        + 8
+        + 8
+        + 1
+        + (1 + 32)
+        + 32
+        + 8
+        + 8
+        + 32
+        + 2
+        + 8
+        + 8
+        + 1
+        + 32
+        + 8
+        + 8
+        + 8
+        + 8
+        + 8
+        + 32
+        + 1
+        + 8
+        + 32
+        + 8
+        + 32
+        + 1
+        + 8
+        + 32
+        + 1
+        + (4 + 2 * MAX_SUPPORTED_CHAINS)
+        + 1
+        + 1
+        + 8
+        + 32
+        + 8
+        + 8
+        + 32
+        + 8
+        + 8
+        + (4 + 32 * MAX_UPDATE_AUTHORITIES)
+        + 8
+        + 8
+        + 8
+        + (4 + 32 * MAX_GOVERNANCE_SIGNERS)
+        + 1
+        + 8
+        + 8
+        + 1
+        + 32
+        + 1
+        + 8 * MAX_RARITY_TIER as usize
+        + 8
+        + 1
+        + 2
+        + 32
+        + 2
+        + 8
+        + 8
+        + 8
+        + (1 + 32)
+        + 1
+        + 8
+        + 32
+        + 8
+        + 8
+        + 16
+        + 1
+        + 8
+        + 8
+        + 1
+        + 8
+        + 1
+        + 32
+        + 2
+        + 32
+        + 8
+        + 8
+        + 8
+        + 1
+        + 1
+        + 8
+        + 4
+        + 1
+        + 8
+        + 32;
+
+    /// Fills in the fields added since `layout_version` and stamps the state
+    /// with `FORGE_LAYOUT_VERSION`. `rari_decimals`, read from the RARI mint,
+    /// is required when upgrading from before layout 13.
+    fn upgrade_layout(&mut self, rari_decimals: Option<u8>) -> Result<()> {
+        // Forges deployed before `is_initialized` existed read it as false;
+        // `migrate_state` has already matched their authority
+        if self.layout_version == 0 {
+            self.is_initialized = true;
+        }
+        // An empty chain list would reject every feed
+        if self.layout_version < 2 {
+            self.supported_chains = DEFAULT_SUPPORTED_CHAINS.to_vec();
+        }
+        // Capture the decimals `initialize` now records
+        if self.layout_version < 13 {
+            self.rari_decimals = rari_decimals.ok_or(ErrorCode::InvalidRariMint)?;
+        }
+        // Records fed before `active_claims` existed can still be closed;
+        // starting from the lifetime count keeps those closes from underflowing
+        if self.layout_version < 16 {
+            self.active_claims = self.total_claimed;
+        }
+        // Keep charging same-chain feeds as before the flag existed
+        if self.layout_version < 25 {
+            self.charge_sol_fee_same_chain = true;
+        }
+        self.layout_version = FORGE_LAYOUT_VERSION;
+        Ok(())
+    }
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.pause_flags & flag != 0
+    }
+
+    /// Advances `event_seq` for an instruction about to emit events, returning
+    /// the value to stamp them with.
+    fn next_event_seq(&mut self) -> Result<u64> {
+        self.event_seq = self
+            .event_seq
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(self.event_seq)
+    }
+
+    /// Whether the Orb burn is the whole payment, leaving no RARI threshold.
+    fn orb_burn_replaces_threshold(&self) -> bool {
+        self.orb_burn_amount > 0 && self.orb_burn_replaces_rari
+    }
+
+    /// Whether `now` has reached `ts`, forgiving a validator clock up to
+    /// `clock_skew_tolerance` seconds behind. Every time-window check goes
+    /// through here so the tolerance applies uniformly: cooldowns, timelocks
+    /// and windows end that much early, and so do deadlines. A larger
+    /// tolerance spares users edge failures at the cost of those guarantees.
+    pub fn is_after(&self, ts: i64, now: i64) -> bool {
+        now.saturating_add(self.clock_skew_tolerance) >= ts
+    }
+
+    /// Like `is_paused`, but a `pause_for` pause no longer holds once its
+    /// expiry has passed at `now`.
+    fn is_paused_at(&self, flag: u8, now: i64) -> bool {
+        self.is_paused(flag)
+            && (self.pause_expires_at == 0 || !self.is_after(self.pause_expires_at, now))
+    }
+
+    /// Feeds counted in the epoch containing `now`, treating a lapsed epoch as empty.
+    fn current_epoch_count_at(&self, now: i64) -> Result<u64> {
+        Ok(if self.is_after(self.current_epoch_end()?, now) {
+            0
+        } else {
+            self.current_epoch_count
+        })
+    }
+
+    /// When the current epoch lapses.
+    fn current_epoch_end(&self) -> Result<i64> {
+        Ok(self
+            .current_epoch_start
+            .checked_add(self.epoch_seconds)
+            .ok_or(ErrorCode::MathOverflow)?)
+    }
+
+    /// Counts one feed in the epoch containing `now`, rolling the epoch forward first.
+    fn record_epoch_feed(&mut self, now: i64) -> Result<()> {
+        if self.is_after(self.current_epoch_end()?, now) {
+            self.current_epoch_start = now;
+            self.current_epoch_count = 0;
+        }
+        self.current_epoch_count = self
+            .current_epoch_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Overwrites the feed counters with a legacy forge's, failing with
+    /// `CountersAlreadySeeded` after the first time.
+    fn seed_counters(
+        &mut self,
+        total_claimed: u64,
+        total_rari_burned: u64,
+        next_claim_index: u64,
+    ) -> Result<()> {
+        require!(!self.counters_seeded, ErrorCode::CountersAlreadySeeded);
+        self.total_claimed = total_claimed;
+        self.total_rari_burned = total_rari_burned;
+        self.next_claim_index = next_claim_index;
+        self.counters_seeded = true;
+        Ok(())
+    }
+
+    /// Fails with `InvalidMemo` while `memo_is_text` is set unless `memo`,
+    /// less its zero padding, is UTF-8.
+    fn require_valid_memo(&self, memo: &[u8; MEMO_LEN]) -> Result<()> {
+        if self.memo_is_text {
+            let len = memo
+                .iter()
+                .rposition(|&byte| byte != 0)
+                .map_or(0, |i| i + 1);
+            require!(
+                std::str::from_utf8(&memo[..len]).is_ok(),
+                ErrorCode::InvalidMemo
+            );
+        }
+        Ok(())
+    }
+
+    /// Counts one feed in the anomaly window containing `now`, rolling the
+    /// window forward first. Sets the `PAUSE_FEED` bit with no expiry and
+    /// returns true once the count exceeds a nonzero `anomaly_threshold`.
+    fn record_minute_feed(&mut self, now: i64) -> Result<bool> {
+        let window_end = self
+            .minute_start
+            .checked_add(ANOMALY_WINDOW_SECONDS)
+            .ok_or(ErrorCode::MathOverflow)?;
+        if self.is_after(window_end, now) {
+            self.minute_start = now;
+            self.feeds_this_minute = 0;
+        }
+        self.feeds_this_minute = self
+            .feeds_this_minute
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let tripped = self.anomaly_threshold > 0 && self.feeds_this_minute > self.anomaly_threshold;
+        if tripped {
+            self.pause_flags |= PAUSE_FEED;
+            self.pause_expires_at = 0;
+        }
+        Ok(tripped)
+    }
+
+    /// Fails unless `now` falls inside the `start_ts`..=`end_ts` drop window.
+    fn require_open(&self, now: i64) -> Result<()> {
+        require!(
+            self.start_ts == 0 || self.is_after(self.start_ts, now),
+            ErrorCode::ForgeNotOpen
+        );
+        require!(
+            self.end_ts == 0 || !self.is_after(self.end_ts.saturating_add(1), now),
+            ErrorCode::ForgeClosed
+        );
+        Ok(())
+    }
+
+    /// Raises an emergency halt that can be cleared no earlier than
+    /// `EMERGENCY_HALT_TIMELOCK` after `now`.
+    fn halt(&mut self, now: i64) -> Result<()> {
+        self.halt_cleared_at = now
+            .checked_add(EMERGENCY_HALT_TIMELOCK)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.halted = true;
+        Ok(())
+    }
+
+    /// Lifts the emergency halt if its timelock has passed at `now`.
+    fn clear_halt(&mut self, now: i64) -> Result<()> {
+        require!(self.halted, ErrorCode::NotHalted);
+        require!(
+            self.is_after(self.halt_cleared_at, now),
+            ErrorCode::HaltTimelockActive
+        );
+        self.halted = false;
+        Ok(())
+    }
+
+    /// Queues `threshold` to become applicable `timelock_seconds` after `now`.
+    fn propose_threshold(&mut self, threshold: u64, now: i64) -> Result<()> {
+        self.pending_threshold = threshold;
+        self.threshold_effective_at = now
+            .checked_add(self.timelock_seconds)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Makes the queued threshold current if its timelock has passed at `now`.
+    fn apply_threshold(&mut self, now: i64) -> Result<()> {
+        require!(
+            self.threshold_effective_at != 0,
+            ErrorCode::NoPendingThreshold
+        );
+        require!(
+            self.is_after(self.threshold_effective_at, now),
+            ErrorCode::ThresholdTimelockActive
+        );
+        self.rari_threshold = self.pending_threshold;
+        self.pending_threshold = 0;
+        self.threshold_effective_at = 0;
+        Ok(())
+    }
+
+    /// Takes the next sequential claim index.
+    fn assign_claim_index(&mut self) -> Result<u64> {
+        let claim_index = self.next_claim_index;
+        self.next_claim_index = claim_index
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(claim_index)
+    }
+
+    /// Whether Orbs under `update_authority` may be fed.
+    fn accepts_update_authority(&self, update_authority: &Pubkey) -> bool {
+        self.accepted_update_authorities.is_empty()
+            || self.accepted_update_authorities.contains(update_authority)
+    }
+
+    fn add_update_authority(&mut self, update_authority: Pubkey) -> Result<()> {
+        require!(
+            !self.accepted_update_authorities.contains(&update_authority),
+            ErrorCode::UpdateAuthorityAlreadyAccepted
+        );
+        require!(
+            self.accepted_update_authorities.len() < MAX_UPDATE_AUTHORITIES,
+            ErrorCode::TooManyUpdateAuthorities
+        );
+        self.accepted_update_authorities.push(update_authority);
+        Ok(())
+    }
+
+    fn remove_update_authority(&mut self, update_authority: &Pubkey) -> Result<()> {
+        let index = self
+            .accepted_update_authorities
+            .iter()
+            .position(|accepted| accepted == update_authority)
+            .ok_or(ErrorCode::UpdateAuthorityNotAccepted)?;
+        self.accepted_update_authorities.remove(index);
+        Ok(())
+    }
+
+    fn add_pending_claim(&mut self) -> Result<()> {
+        self.pending_claims = self
+            .pending_claims
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    fn add_active_claim(&mut self) -> Result<()> {
+        self.active_claims = self
+            .active_claims
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Uncounts a closed or expired claim record.
+    fn release_active_claim(&mut self) -> Result<()> {
+        self.active_claims = self
+            .active_claims
+            .checked_sub(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Uncounts a claim leaving `Pending`. Saturates because claims made
+    /// before `pending_claims` existed were never counted.
+    fn release_pending_claim(&mut self) {
+        self.pending_claims = self.pending_claims.saturating_sub(1);
+    }
+
+    /// Applies an approved (or, for the first signer set, authority-made) change.
+    fn apply_change(&mut self, change: &ProposedChange) -> Result<()> {
+        change.validate()?;
+        match change {
+            ProposedChange::RariThreshold { rari_threshold } => {
+                require!(
+                    *rari_threshold > 0 || self.allow_free_feed,
+                    ErrorCode::ZeroThreshold
+                );
+                self.rari_threshold = *rari_threshold;
+            }
+            ProposedChange::BurnBps { burn_bps } => self.burn_bps = *burn_bps,
+            ProposedChange::SolFee {
+                sol_fee_lamports,
+                fee_recipient,
+            } => {
+                self.sol_fee_lamports = *sol_fee_lamports;
+                self.fee_recipient = *fee_recipient;
+            }
+            ProposedChange::Governance { signers, threshold } => {
+                self.governance_signers = signers.clone();
+                self.governance_threshold = *threshold;
+                self.governance_nonce = self
+                    .governance_nonce
+                    .checked_add(1)
+                    .ok_or(ErrorCode::MathOverflow)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Seed following `b"emitter"` in the Wormhole emitter PDA. Version 0 adds
+    /// none, keeping the forge's original `[b"emitter"]` emitter.
+    pub fn emitter_seed(&self) -> &[u8] {
+        if self.emitter_seed_version == 0 {
+            &[]
+        } else {
+            std::slice::from_ref(&self.emitter_seed_version)
+        }
+    }
+
+    /// Seed following the prefix of each of this forge's PDAs.
+    pub fn namespace_seed(&self) -> &[u8] {
+        namespace_seed(&self.namespace)
+    }
+
+    /// Whether `account` holds feed payments `sweep_tokens` must not touch:
+    /// the RARI treasury or this forge's escrow for `mint`.
+    pub fn protects_token_account(&self, account: &Pubkey, mint: &Pubkey) -> bool {
+        *account == self.treasury || *account == derive_escrow_pda(&self.namespace, mint).0
+    }
+
+    /// The current Wormhole emitter PDA.
+    fn emitter_address(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                wormhole::SEED_PREFIX_EMITTER,
+                self.namespace_seed(),
+                self.emitter_seed(),
+            ],
+            &crate::ID,
+        )
+        .0
+    }
+
+    /// RARI threshold of `rarity_tier`, given the `base` threshold of tier 0.
+    fn threshold_for_tier(&self, rarity_tier: u8, base: u64) -> u64 {
+        match rarity_tier.checked_sub(1) {
+            Some(index) => match self.tier_thresholds[index as usize] {
+                0 => base,
+                threshold => threshold,
+            },
+            None => base,
+        }
+    }
+
+    /// Whether a VAA timestamped `vaa_timestamp` is newer than every pruned marker.
+    fn accepts_vaa_timestamp(&self, vaa_timestamp: i64) -> bool {
+        vaa_timestamp > self.pruned_through
+    }
+
+    /// Advances `pruned_through` past a marker redeemed at `redeemed_at`,
+    /// provided it has outlived `VAA_FINALITY_WINDOW` plus `redeemed_ttl`.
+    fn record_pruned(&mut self, redeemed_at: i64, now: i64) -> Result<()> {
+        require!(self.redeemed_ttl > 0, ErrorCode::PruningDisabled);
+        let prunable_at = redeemed_at
+            .checked_add(VAA_FINALITY_WINDOW)
+            .and_then(|at| at.checked_add(self.redeemed_ttl))
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            self.is_after(prunable_at.saturating_add(1), now),
+            ErrorCode::RedeemedMarkerLive
+        );
+        self.pruned_through = self.pruned_through.max(redeemed_at);
+        Ok(())
+    }
+
+    /// Whether `key` may change the pause flags.
+    fn can_pause(&self, key: &Pubkey) -> bool {
+        *key == self.authority || (self.operator != Pubkey::default() && *key == self.operator)
+    }
+
+    /// Whether another Orb may be counted under `max_total_claims` (0 = unlimited).
+    fn below_claim_cap(&self) -> bool {
+        self.max_total_claims == 0 || self.total_claimed < self.max_total_claims
+    }
+
+    /// Whether the next Orb fed is still among the first `early_bonus_count`,
+    /// while a bonus mint is set.
+    fn grants_early_bonus(&self) -> bool {
+        self.early_bonus_mint != Pubkey::default() && self.total_claimed < self.early_bonus_count
+    }
+
+    /// Partner reward minted per feed: `partner_bps` of `rari_threshold`, in
+    /// the reward mint's base units, or 0 while no reward mint is set.
+    fn partner_share(&self) -> u64 {
+        if self.partner_reward_mint == Pubkey::default() {
+            return 0;
+        }
+        split_threshold(self.rari_threshold, self.partner_bps).0
+    }
+
+    /// SOL fee a feed to `chain_id` pays; same-chain feeds skip it unless
+    /// `charge_sol_fee_same_chain` is set.
+    fn sol_fee_for(&self, chain_id: u16) -> u64 {
+        if is_cross_chain(chain_id) || self.charge_sol_fee_same_chain {
+            self.sol_fee_lamports
+        } else {
+            0
+        }
+    }
+
+    /// RARI relayer fee a feed to `chain_id` pays; same-chain feeds are
+    /// relayed by no one.
+    fn relayer_fee_for(&self, chain_id: u16) -> u64 {
+        if is_cross_chain(chain_id) {
+            self.relayer_fee
+        } else {
+            0
+        }
+    }
+
+    /// Whether an Orb already fed `feed_count` times may be fed again under
+    /// `max_feeds_per_orb` (0 = unlimited).
+    fn allows_orb_feed(&self, feed_count: u16) -> bool {
+        self.max_feeds_per_orb == 0 || feed_count < self.max_feeds_per_orb
+    }
+
+    /// Counts one fed Orb and `rari_burned` towards the running totals.
+    fn record_feed(&mut self, rari_burned: u64) -> Result<()> {
+        require!(self.below_claim_cap(), ErrorCode::ClaimCapReached);
+        self.total_claimed = self
+            .total_claimed
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_rari_burned = self
+            .total_rari_burned
+            .checked_add(rari_burned)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct ClaimRecord {
+    /// `CLAIM_RECORD_VERSION` of the layout the record was last written with
+    pub version: u8,
+    pub orb_mint: Pubkey,
+    pub claimer: Pubkey,
+    pub claimed_at: i64,
+    pub target_chain: u16,
+    pub wormhole_sequence: Option<u64>,
+    pub feed_count: u16,
+    pub last_fed_at: i64,
+    pub name_hash: [u8; 32],
+    pub status: ClaimStatus,
+    /// RARI held in the forge escrow until `settle_feed` or `cancel_feed`
+    pub escrowed_amount: u64,
+    /// Position of the latest feed in the forge-wide feed order
+    pub claim_index: u64,
+    /// Set by `seed_claim`; the claimer's first feed charges no threshold
+    pub prefunded: bool,
+    /// RARI burned by the latest feed, less any `refund_claim` refund
+    pub rari_burned: u64,
+    /// Commitment to the latest feed, as emitted in `OrbFedEvent`
+    pub burn_proof: [u8; 32],
+    /// Memo the latest feed was tagged with; zero-filled when absent
+    pub memo: [u8; MEMO_LEN],
+    /// Set when `reissue_claim` recreated the record after it was closed
+    pub reissued: bool,
+    /// Rarity tier the latest feed was charged at
+    pub rarity_tier: u8,
+    /// Holder discount the latest feed was charged at
+    pub discount_bps: u16,
+}
+
+impl ClaimRecord {
+    pub const LEN: usize =
+        1 + 32 + 32 + 8 + 2 + (1 + 8) + 2 + 8 + 32 + 1 + 8 + 8 + 1 + 8 + 32 + MEMO_LEN + 1 + 1 + 2;
+
+    /// Whether a record account of `data_len` bytes predates `version`.
+    fn is_unversioned(data_len: usize) -> bool {
+        data_len <= 8 + UNVERSIONED_CLAIM_RECORD_LEN
+    }
+
+    /// Size `migrate_claim` grows a record account of `data_len` bytes to.
+    fn migrated_len(data_len: usize) -> usize {
+        data_len.max(8 + Self::LEN)
+    }
+
+    /// RARI the latest feed burned beyond what the same feed burns now, with
+    /// `base_threshold` the target chain's current threshold and the tier
+    /// and discount the feed was charged at.
+    fn refund_due(&self, forge_state: &ForgeState, base_threshold: u64) -> u64 {
+        let threshold = forge_state.threshold_for_tier(self.rarity_tier, base_threshold);
+        let (_, threshold) = split_threshold(threshold, self.discount_bps);
+        let (burn_now, _) = split_threshold(threshold, forge_state.burn_bps);
+        self.rari_burned.saturating_sub(burn_now)
+    }
+
+    /// Whether `seed_claim` created this record and it has not been fed yet.
+    fn awaits_seeded_feed(&self) -> bool {
+        self.prefunded && self.feed_count == 0
+    }
+
+    /// Fails unless `ack` acknowledges this claim's latest feed while it is
+    /// still `Pending`.
+    fn check_ack(&self, ack: &ClaimAckMessage) -> Result<()> {
+        require!(
+            self.status == ClaimStatus::Pending && self.feed_count > 0,
+            ErrorCode::ClaimNotPending
+        );
+        require_keys_eq!(ack.orb_mint, self.orb_mint, ErrorCode::InvalidVaaPayload);
+        require!(
+            ack.burn_proof == self.burn_proof,
+            ErrorCode::BurnProofMismatch
+        );
+        Ok(())
+    }
+
+    /// Whether the claim is counted in `ForgeState::pending_claims`; a record
+    /// defaults to `Pending` before its first feed without being counted.
+    fn counts_as_pending(&self) -> bool {
+        self.feed_count > 0 && self.status == ClaimStatus::Pending
+    }
+}
+
+/// Lifecycle of a claim. Cross-chain claims stay `Pending` until relayed;
+/// escrowed feeds stay `Escrowed` until settled or cancelled.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClaimStatus {
+    #[default]
+    Pending,
+    Confirmed,
+    Expired,
+    Escrowed,
+}
+
+#[account]
+pub struct ChainConfig {
+    pub chain_id: u16,
+    pub threshold: u64,
+    pub enabled: bool,
+    /// Why the chain is disabled, as zero-padded UTF-8
+    pub disabled_reason: [u8; MAX_DISABLED_REASON_LEN],
+    /// Canonical token on the chain, as a 32-byte (left-padded for EVM)
+    /// address carried in `OrbFedMessage`
+    pub destination_mint: [u8; 32],
+}
+
+impl ChainConfig {
+    pub const LEN: usize = 2 + 8 + 1 + MAX_DISABLED_REASON_LEN + 32;
+
+    /// An enabled cross-chain target must name its destination mint.
+    /// Sets the threshold and enabled flag of `chain_id`, as `set_chain_config`
+    /// does; enabling clears the disabled reason.
+    fn apply(&mut self, chain_id: u16, threshold: u64, enabled: bool) -> Result<()> {
+        self.chain_id = chain_id;
+        self.threshold = threshold;
+        self.enabled = enabled;
+        if enabled {
+            self.disabled_reason = [0; MAX_DISABLED_REASON_LEN];
+        }
+        self.require_destination_mint()
+    }
+
+    fn require_destination_mint(&self) -> Result<()> {
+        require!(
+            !self.enabled || !is_cross_chain(self.chain_id) || self.destination_mint != [0; 32],
+            ErrorCode::MissingDestinationMint
+        );
+        Ok(())
+    }
+
+    fn updated_event(&self, seq: u64) -> ChainConfigUpdated {
+        ChainConfigUpdated {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq,
+            chain_id: self.chain_id,
+            threshold: self.threshold,
+            enabled: self.enabled,
+            destination_mint: self.destination_mint,
+        }
+    }
+
+    /// `disabled_reason` without its zero padding.
+    fn disabled_reason(&self) -> &str {
+        let len = self
+            .disabled_reason
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(MAX_DISABLED_REASON_LEN);
+        std::str::from_utf8(&self.disabled_reason[..len]).unwrap_or_default()
+    }
+}
+
+/// A change `propose_change` puts to the governance signers.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum ProposedChange {
+    RariThreshold {
+        rari_threshold: u64,
+    },
+    BurnBps {
+        burn_bps: u16,
+    },
+    SolFee {
+        sol_fee_lamports: u64,
+        fee_recipient: Pubkey,
+    },
+    /// Replaces the signer set; a 0 threshold with no signers hands changes
+    /// back to the authority
+    Governance {
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    },
+}
+
+impl Default for ProposedChange {
+    fn default() -> Self {
+        ProposedChange::RariThreshold { rari_threshold: 0 }
+    }
+}
+
+impl ProposedChange {
+    /// Largest serialized variant, the signer set.
+    pub const MAX_LEN: usize = 1 + (4 + 32 * MAX_GOVERNANCE_SIGNERS) + 1;
+
+    /// Checks what can be checked before the change applies.
+    fn validate(&self) -> Result<()> {
+        match self {
+            ProposedChange::BurnBps { burn_bps } => {
+                require!(*burn_bps <= MAX_BPS, ErrorCode::InvalidBps);
+            }
+            ProposedChange::Governance { signers, threshold } => {
+                require!(
+                    signers.len() <= MAX_GOVERNANCE_SIGNERS,
+                    ErrorCode::InvalidGovernance
+                );
+                require!(
+                    *threshold as usize <= signers.len()
+                        && (*threshold > 0 || signers.is_empty()),
+                    ErrorCode::InvalidGovernance
+                );
+                let unique = signers
+                    .iter()
+                    .enumerate()
+                    .all(|(i, signer)| !signers[..i].contains(signer));
+                require!(unique, ErrorCode::InvalidGovernance);
+            }
+            ProposedChange::RariThreshold { .. } | ProposedChange::SolFee { .. } => {}
+        }
+        Ok(())
+    }
+}
+
+/// A governance proposal at `[b"proposal", proposal_id]`.
+#[account]
+#[derive(Default)]
+pub struct ProposalAccount {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub change: ProposedChange,
+    /// Bit `i` is set once `governance_signers[i]` has approved
+    pub approvals: u8,
+    /// `ForgeState::governance_nonce` the approvals were counted against
+    pub governance_nonce: u64,
+    pub executed: bool,
+    /// When the proposal reached `governance_threshold` approvals; 0 until then
+    pub approved_at: i64,
+}
+
+impl ProposalAccount {
+    pub const LEN: usize = 8 + 32 + ProposedChange::MAX_LEN + 1 + 8 + 1 + 8;
+
+    /// Records `signer`'s approval at `now`, applying the change to
+    /// `forge_state` once it has `governance_threshold` approvals, unless it
+    /// must first wait out the threshold timelock.
+    fn approve(&mut self, forge_state: &mut ForgeState, signer: &Pubkey, now: i64) -> Result<()> {
+        require!(!self.executed, ErrorCode::ProposalExecuted);
+        require!(
+            self.governance_nonce == forge_state.governance_nonce,
+            ErrorCode::StaleProposal
+        );
+        let index = forge_state
+            .governance_signers
+            .iter()
+            .position(|governance_signer| governance_signer == signer)
+            .ok_or(ErrorCode::NotGovernanceSigner)?;
+        let bit = 1u8 << index;
+        require!(self.approvals & bit == 0, ErrorCode::AlreadyApproved);
+        self.approvals |= bit;
+
+        if self.approved_at == 0
+            && self.approvals.count_ones() >= forge_state.governance_threshold as u32
+        {
+            self.approved_at = now;
+            let timelocked = matches!(self.change, ProposedChange::RariThreshold { .. })
+                && forge_state.timelock_seconds > 0;
+            if !timelocked {
+                self.execute(forge_state, now)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the approved change to `forge_state`. A threshold change, like
+    /// one queued by `propose_threshold`, waits `timelock_seconds` from its
+    /// approval.
+    fn execute(&mut self, forge_state: &mut ForgeState, now: i64) -> Result<()> {
+        require!(!self.executed, ErrorCode::ProposalExecuted);
+        require!(
+            self.governance_nonce == forge_state.governance_nonce,
+            ErrorCode::StaleProposal
+        );
+        require!(self.approved_at != 0, ErrorCode::ProposalNotApproved);
+        if let ProposedChange::RariThreshold { .. } = self.change {
+            let effective_at = self
+                .approved_at
+                .checked_add(forge_state.timelock_seconds)
+                .ok_or(ErrorCode::MathOverflow)?;
+            require!(
+                forge_state.is_after(effective_at, now),
+                ErrorCode::ThresholdTimelockActive
+            );
+        }
+        forge_state.apply_change(&self.change)?;
+        self.executed = true;
+        Ok(())
+    }
+}
+
+/// Number of Orbs `feed_orb` has sent to one target chain.
+#[account]
+#[derive(Default)]
+pub struct ChainCounter {
+    pub chain_id: u16,
+    pub count: u64,
+}
+
+impl ChainCounter {
+    pub const LEN: usize = 2 + 8;
+
+    /// Counts one more Orb fed to `chain_id`.
+    fn record(&mut self, chain_id: u16) -> Result<()> {
+        self.chain_id = chain_id;
+        self.count = self.count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// Claim records currently `Pending`, oldest first, at `[b"pending_queue"]`.
+/// Relayers poll this one account for work instead of scanning every claim.
+#[account]
+#[derive(Default)]
+pub struct PendingQueue {
+    /// Most claims the queue holds, fixed by `initialize`
+    pub capacity: u16,
+    pub claims: Vec<Pubkey>,
+}
+
+impl PendingQueue {
+    /// Account size, past the discriminator, of a queue holding `capacity` claims.
+    pub const fn space(capacity: u16) -> usize {
+        2 + 4 + 32 * capacity as usize
+    }
+
+    /// Lists a claim that just became `Pending`, failing once the queue is full
+    /// so cross-chain feeds back off until relayers catch up.
+    fn push(&mut self, claim: Pubkey) -> Result<()> {
+        require!(
+            self.claims.len() < self.capacity as usize,
+            ErrorCode::PendingQueueFull
+        );
+        self.claims.push(claim);
+        Ok(())
+    }
+
+    /// Drops a claim leaving `Pending`; one that was never queued is ignored.
+    fn remove(&mut self, claim: &Pubkey) {
+        if let Some(index) = self.claims.iter().position(|queued| queued == claim) {
+            self.claims.remove(index);
+        }
+    }
+}
+
+#[account]
+pub struct TrustedEmitter {
+    pub chain_id: u16,
+    pub emitter_address: [u8; 32],
+}
+
+impl TrustedEmitter {
+    pub const LEN: usize = 2 + 32;
+}
+
+/// An inbound claim redeemed from a VAA, doubling as its replay guard.
+#[account]
+pub struct InboundClaim {
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub orb_mint: Pubkey,
+    pub claimer: Pubkey,
+    pub rari_burned: u64,
+    pub redeemed_at: i64,
+    /// Set once `burn_receipt` has consumed the claimer's receipt for this VAA
+    pub receipt_burned: bool,
+}
+
+impl InboundClaim {
+    pub const LEN: usize = 2 + 8 + 32 + 32 + 8 + 8 + 1;
+}
+
+/// Registers `relayer` as allowed to claim relayer fees.
+#[account]
+pub struct RelayerEntry {
+    pub relayer: Pubkey,
+    /// RARI the relayer has claimed from the relayer fee vault
+    pub claimed: u64,
+}
+
+impl RelayerEntry {
+    pub const LEN: usize = 32 + 8;
+}
+
+/// Marks `address` as denied from being credited with feeds.
+#[account]
+pub struct DenyEntry {
+    pub address: Pubkey,
+    pub denied_at: i64,
+}
+
+impl DenyEntry {
+    pub const LEN: usize = 32 + 8;
+}
+
+/// A consumed acknowledgment VAA, doubling as its replay guard.
+#[account]
+pub struct ClaimAck {
+    pub orb_mint: Pubkey,
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub confirmed_at: i64,
+}
+
+impl ClaimAck {
+    pub const LEN: usize = 32 + 2 + 8 + 8;
+}
+
+#[account]
+pub struct PaymentMintConfig {
+    pub mint: Pubkey,
+    pub threshold: u64,
+    pub enabled: bool,
+}
+
+impl PaymentMintConfig {
+    pub const LEN: usize = 32 + 8 + 1;
+}
+
+/// Off-token loyalty points of one user, accrued by `feed_orb` and zeroed by
+/// `redeem_points`.
+#[account]
+#[derive(Default)]
+pub struct PointsAccount {
+    pub points: u64,
+}
+
+impl PointsAccount {
+    pub const LEN: usize = 8;
+
+    fn accrue(&mut self, points: u64) -> Result<()> {
+        self.points = self
+            .points
+            .checked_add(points)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct FeederStats {
+    pub window_start: i64,
+    pub count: u64,
+    pub last_feed_at: i64,
+}
+
+impl FeederStats {
+    pub const LEN: usize = 8 + 8 + 8;
+
+    /// Whether `now` is within `user_cooldown_seconds` of the last feed (0 = no cooldown).
+    fn cooling_down(&self, forge_state: &ForgeState, now: i64) -> bool {
+        let user_cooldown_seconds = forge_state.user_cooldown_seconds;
+        user_cooldown_seconds > 0
+            && !forge_state.is_after(self.last_feed_at + user_cooldown_seconds, now)
+    }
+
+    /// Feeds counted in the window containing `now`, treating a lapsed window as empty.
+    fn current_count(&self, forge_state: &ForgeState, now: i64) -> u64 {
+        if forge_state.is_after(self.window_start + forge_state.window_seconds + 1, now) {
+            0
+        } else {
+            self.count
+        }
+    }
+
+    /// Counts one feed in the window containing `now`, starting a new window first.
+    fn record(&mut self, forge_state: &ForgeState, now: i64) -> Result<()> {
+        if forge_state.is_after(self.window_start + forge_state.window_seconds + 1, now) {
+            self.window_start = now;
+            self.count = 0;
+        }
+        self.count = self.count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        self.last_feed_at = now;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitializeParams {
+    pub wormhole_bridge: Pubkey,
+    pub rari_mint: Pubkey,
+    pub rari_threshold: u64,
+    pub approved_collection: Pubkey,
+    pub treasury: Pubkey,
+    pub burn_bps: u16,
+    pub required_creator: Pubkey,
+    pub allow_free_feed: bool,
+    /// Feeds open at this time; 0 opens immediately
+    pub start_ts: i64,
+    /// Feeds close after this time; 0 never closes
+    pub end_ts: i64,
+    /// Capacity of the `PendingQueue`, up to `MAX_PENDING_QUEUE_SIZE`
+    pub pending_queue_size: u16,
+    /// Seeds this forge's PDAs apart from other forges of the program; all
+    /// zero for the original forge's addresses
+    pub namespace: [u8; 16],
+}
+
+/// Historical values of a closed claim record, restored by `reissue_claim`.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ReissueClaimParams {
+    pub orb_mint: Pubkey,
+    pub claimer: Pubkey,
+    pub claimed_at: i64,
+    pub target_chain: u16,
+    pub wormhole_sequence: Option<u64>,
+    pub feed_count: u16,
+    pub last_fed_at: i64,
+    pub name_hash: [u8; 32],
+    pub claim_index: u64,
+    pub rari_burned: u64,
+    pub burn_proof: [u8; 32],
+    pub memo: [u8; MEMO_LEN],
+    pub rarity_tier: u8,
+    pub discount_bps: u16,
+}
+
+/// Payload of the acknowledgment VAA a destination chain posts once it has
+/// honoured a claim, consumed by `confirm_claim`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ClaimAckMessage {
+    pub orb_mint: Pubkey,
+    /// `burn_proof` of the acknowledged feed, as carried in `OrbFedEvent`
+    pub burn_proof: [u8; 32],
+}
+
+/// Payload posted to Wormhole for cross-chain feeds, and expected in
+/// inbound VAAs redeemed through `redeem_claim`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OrbFedMessage {
+    pub orb_mint: Pubkey,
+    pub claimer: Pubkey,
+    pub target_chain: u16,
+    pub rari_burned: u64,
+    /// Token the receiving side mints or unlocks, as a 32-byte address on
+    /// `target_chain`; zero when the chain has no config
+    pub destination_mint: [u8; 32],
+}
+
+/// One `batch_set_chain_configs` entry, laid out as `(chain_id, threshold,
+/// enabled)`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ChainConfigUpdate {
+    pub chain_id: u16,
+    pub threshold: u64,
+    pub enabled: bool,
+}
+
+/// Leaf inputs of a compressed Orb for `feed_compressed_orb`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedOrb {
+    /// Tree root the proof was generated against
+    pub root: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+    pub metadata: CompressedOrbMetadata,
+}
+
+impl CompressedOrb {
+    /// Bubblegum V1 leaf hash of this Orb held by `owner`.
+    fn leaf_hash(&self, asset_id: &Pubkey, owner: &Pubkey, delegate: &Pubkey) -> Result<[u8; 32]> {
+        Ok(keccak::hashv(&[
+            &[1],
+            asset_id.as_ref(),
+            owner.as_ref(),
+            delegate.as_ref(),
+            &self.nonce.to_le_bytes(),
+            &self.metadata.data_hash()?,
+            &self.metadata.creator_hash(),
+        ])
+        .to_bytes())
+    }
+}
+
+/// Bubblegum `MetadataArgs`, mirrored field for field so its Borsh encoding
+/// hashes to the leaf's data hash.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedOrbMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<CompressedTokenStandard>,
+    pub collection: Option<CompressedCollection>,
+    pub uses: Option<CompressedUses>,
+    pub token_program_version: CompressedTokenProgramVersion,
+    pub creators: Vec<CompressedCreator>,
+}
+
+impl CompressedOrbMetadata {
+    fn data_hash(&self) -> Result<[u8; 32]> {
+        let metadata_hash = keccak::hash(&self.try_to_vec()?);
+        Ok(keccak::hashv(&[
+            metadata_hash.as_ref(),
+            &self.seller_fee_basis_points.to_le_bytes(),
+        ])
+        .to_bytes())
+    }
+
+    fn creator_hash(&self) -> [u8; 32] {
+        let creators: Vec<[u8; 34]> = self
+            .creators
+            .iter()
+            .map(|creator| {
+                let mut bytes = [0u8; 34];
+                bytes[..32].copy_from_slice(creator.address.as_ref());
+                bytes[32] = creator.verified as u8;
+                bytes[33] = creator.share;
+                bytes
+            })
+            .collect();
+        let slices: Vec<&[u8]> = creators.iter().map(|bytes| bytes.as_ref()).collect();
+        keccak::hashv(&slices).to_bytes()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum CompressedTokenStandard {
+    NonFungible,
+    FungibleAsset,
+    Fungible,
+    NonFungibleEdition,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedCollection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum CompressedUseMethod {
+    Burn,
+    Multiple,
+    Single,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedUses {
+    pub use_method: CompressedUseMethod,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum CompressedTokenProgramVersion {
+    Original,
+    Token2022,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedCreator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+#[event]
+pub struct OrbFedEvent {
+    pub schema_version: u8,
+    /// `ForgeState::event_seq` of the instruction that emitted the event;
+    /// every event carries it so indexers can skip replayed ones
+    pub seq: u64,
+    pub orb_mint: Pubkey,
+    /// Wallet that paid the burn and fees
+    pub payer: Pubkey,
+    /// Wallet credited with the claim; differs from `payer` for a gift
+    pub claimer: Pubkey,
+    pub target_chain: u16,
+    pub rari_burned: u64,
+    pub wormhole_sequence: Option<u64>,
+    pub name_hash: [u8; 32],
+    /// `ROUTE_SAME_CHAIN` or `ROUTE_CROSS_CHAIN`
+    pub route: u8,
+    pub forge_version: u16,
+    pub claim_index: u64,
+    /// `burn_proof_for` commitment, also stored on the claim record
+    pub burn_proof: [u8; 32],
+    /// Lamports paid to the Wormhole fee collector; 0 for same-chain feeds
+    pub wormhole_fee: u64,
+    /// `feed_orb` memo, also stored on the claim record; zero when absent
+    pub memo: [u8; MEMO_LEN],
+    /// Position of the feed among those its instruction emitted, from 0
+    pub batch_index: u16,
+    /// `batch_id_for` the instruction, shared by every feed it emitted
+    pub batch_id: [u8; 16],
+}
+
+/// Fixed-width twin of `OrbFedEvent`, decodable from the `Program data:` log
+/// without the IDL. After Anchor's 8-byte event discriminator, the payload is
+/// laid out as follows, integers little-endian:
+///
+/// | offset | size | field                                      |
+/// |--------|------|--------------------------------------------|
+/// | 0      | 1    | `schema_version`                           |
+/// | 1      | 32   | `orb_mint`                                 |
+/// | 33     | 32   | `claimer`                                  |
+/// | 65     | 2    | `target_chain`                             |
+/// | 67     | 8    | `rari_burned`                              |
+/// | 75     | 1    | `has_wormhole_sequence` (0 or 1)           |
+/// | 76     | 8    | `wormhole_sequence` (0 when absent)        |
+/// | 84     | 32   | `name_hash`                                |
+/// | 116    | 1    | `route`                                    |
+/// | 117    | 2    | `forge_version`                            |
+/// | 119    | 8    | `seq`                                      |
+/// | 127    | 4    | `crc32` of payload bytes 0..127            |
+///
+/// The layout only ever grows at the end, before `crc32`, alongside a
+/// `schema_version` bump.
+#[event]
+pub struct OrbFedCompact {
+    pub schema_version: u8,
+    pub orb_mint: Pubkey,
+    pub claimer: Pubkey,
+    pub target_chain: u16,
+    pub rari_burned: u64,
+    pub has_wormhole_sequence: bool,
+    pub wormhole_sequence: u64,
+    pub name_hash: [u8; 32],
+    pub route: u8,
+    pub forge_version: u16,
+    pub seq: u64,
+    pub crc32: u32,
+}
+
+impl OrbFedCompact {
+    /// Size of the payload after the discriminator.
+    pub const LEN: usize = 1 + 32 + 32 + 2 + 8 + 1 + 8 + 32 + 1 + 2 + 8 + 4;
+
+    fn new(event: &OrbFedEvent) -> Result<Self> {
+        let mut compact = Self {
+            schema_version: event.schema_version,
+            orb_mint: event.orb_mint,
+            claimer: event.claimer,
+            target_chain: event.target_chain,
+            rari_burned: event.rari_burned,
+            has_wormhole_sequence: event.wormhole_sequence.is_some(),
+            wormhole_sequence: event.wormhole_sequence.unwrap_or(0),
+            name_hash: event.name_hash,
+            route: event.route,
+            forge_version: event.forge_version,
+            seq: event.seq,
+            crc32: 0,
+        };
+        let payload = compact.try_to_vec()?;
+        compact.crc32 = crc32(&payload[..Self::LEN - 4]);
+        Ok(compact)
+    }
+}
+
+#[event]
+pub struct ForgeStatsUpdated {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub total_claimed: u64,
+    pub total_rari_burned: u64,
+    pub active_claims: u64,
+}
+
+#[event]
+pub struct ChainCounterUpdated {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub chain_id: u16,
+    pub count: u64,
+}
+
+#[event]
+pub struct InboundClaimRedeemed {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub orb_mint: Pubkey,
+    pub claimer: Pubkey,
+}
+
+/// A `feed_orb` that failed, logged in the reverted transaction. Only errors
+/// from the handler body are reported: a failed account constraint, e.g. a
+/// wrong PDA or a missing signature, aborts before the handler runs and
+/// emits nothing.
+#[event]
+pub struct FeedRejected {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub user: Pubkey,
+    pub orb_mint: Pubkey,
+    pub target_chain: u16,
+    /// Anchor error number: 6000 plus the `ErrorCode` discriminant for forge
+    /// errors, or the encoded `ProgramError` otherwise
+    pub error_code: u32,
+}
+
+#[event]
+pub struct PointsRedeemed {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub user: Pubkey,
+    pub points: u64,
+}
+
+#[event]
+pub struct CountersSeeded {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub total_claimed: u64,
+    pub total_rari_burned: u64,
+    pub next_claim_index: u64,
+}
+
+#[event]
+pub struct AutoPaused {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub feeds_this_minute: u64,
+    pub anomaly_threshold: u64,
+    pub minute_start: i64,
+}
+
+#[event]
+pub struct ProposalApproved {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub proposal_id: u64,
+    /// Approvals counted so far
+    pub approvals: u8,
+    /// Whether this approval applied the change
+    pub executed: bool,
+}
+
+#[event]
+pub struct ClaimConfirmed {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub orb_mint: Pubkey,
+    pub claimer: Pubkey,
+    pub target_chain: u16,
+    pub burn_proof: [u8; 32],
+    /// Chain and sequence of the acknowledgment VAA
+    pub emitter_chain: u16,
+    pub sequence: u64,
+}
+
+#[event]
+pub struct RedeemedPruned {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub emitter_chain: u16,
+    pub sequence: u64,
+}
+
+#[event]
+pub struct PartnerShareMinted {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub orb_mint: Pubkey,
+    pub partner: Pubkey,
+    pub reward_mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct OrbTokensBurned {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub orb_mint: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ReceiptBurned {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub orb_mint: Pubkey,
+    pub claimer: Pubkey,
+}
+
+#[event]
+pub struct FeedSettled {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub orb_mint: Pubkey,
+    pub claimer: Pubkey,
+    pub rari_burned: u64,
+}
+
+#[event]
+pub struct ClaimRefunded {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub orb_mint: Pubkey,
+    pub claimer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct FeedCancelled {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub orb_mint: Pubkey,
+    pub claimer: Pubkey,
+    pub refunded: u64,
+}
+
+#[event]
+pub struct EmitterRotated {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub emitter_seed_version: u8,
+    pub previous_emitter: Pubkey,
+    pub emitter: Pubkey,
+}
+
+#[event]
+pub struct ChainConfigUpdated {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub chain_id: u16,
+    pub threshold: u64,
+    pub enabled: bool,
+    pub destination_mint: [u8; 32],
+}
+
+#[event]
+pub struct ThresholdProposed {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub current_threshold: u64,
+    pub pending_threshold: u64,
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct ThresholdApplied {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub previous_threshold: u64,
+    pub rari_threshold: u64,
+}
+
+#[event]
+pub struct ClaimSeeded {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub orb_mint: Pubkey,
+    pub claimer: Pubkey,
+}
+
+#[event]
+pub struct RelayerFeeClaimed {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub relayer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ClaimReissued {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub orb_mint: Pubkey,
+    pub claimer: Pubkey,
+    pub claimed_at: i64,
+    pub target_chain: u16,
+    pub burn_proof: [u8; 32],
+}
+
+#[event]
+pub struct ClaimExpired {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub orb_mint: Pubkey,
+    pub claimer: Pubkey,
+    pub target_chain: u16,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub amount: u64,
+    pub destination: Pubkey,
+}
+
+#[event]
+pub struct TokensSwept {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub mint: Pubkey,
+    pub source: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct EligibilityResult {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub orb_mint: Pubkey,
+    pub user: Pubkey,
+    pub target_chain: u16,
+    pub eligible: bool,
+    /// Error code `feed_orb` would fail with (a `ProgramError` as its `u64`
+    /// encoding), 0 when eligible.
+    pub reason: u64,
+}
+
+#[event]
+pub struct ForgeStatsSnapshot {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub total_claimed: u64,
+    pub total_rari_burned: u64,
+    pub active_claims: u64,
+    pub rari_threshold: u64,
+    /// Decimals of the RARI mint, for displaying `rari_threshold`
+    pub rari_decimals: u8,
+    pub paused: bool,
+    /// Threshold queued by `propose_threshold`; meaningful while
+    /// `threshold_effective_at` is nonzero
+    pub pending_threshold: u64,
+    /// When `pending_threshold` can be applied; 0 when none is queued
+    pub threshold_effective_at: i64,
+}
+
+#[event]
+pub struct ForgeClosed {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub total_claimed: u64,
+    pub total_rari_burned: u64,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct BonusGranted {
+    pub schema_version: u8,
+    pub seq: u64,
+    pub orb_mint: Pubkey,
+    pub claimer: Pubkey,
+    pub bonus_mint: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted by `initialize` so indexers can discover the event schema on deploy.
+#[event]
+pub struct SchemaVersionEvent {
+    pub schema_version: u8,
+    pub seq: u64,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Program is currently paused")]
+    ProgramPaused,
+    #[msg("Invalid Orb metadata")]
+    InvalidOrbMetadata,
+    #[msg("Insufficient RARI balance")]
+    InsufficientRariBalance,
+    #[msg("Wormhole bridge does not match the configured bridge")]
+    InvalidWormholeBridge,
+    #[msg("Wormhole message account does not match the emitter sequence")]
+    InvalidWormholeMessage,
+    #[msg("Signer is not the pending authority")]
+    UnauthorizedPendingAuthority,
+    #[msg("Orb is not a verified member of the approved collection")]
+    OrbNotInCollection,
+    #[msg("Feed rate limit reached for this window")]
+    FeedRateLimited,
+    #[msg("Basis points must not exceed 10000")]
+    InvalidBps,
+    #[msg("Claim record cannot be closed before the close delay has elapsed")]
+    CloseDelayActive,
+    #[msg("Batch exceeds the maximum number of Orbs")]
+    BatchTooLarge,
+    #[msg("Batch remaining accounts do not match the target chains")]
+    InvalidBatchAccounts,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Feeding to this chain is disabled")]
+    ChainDisabled,
+    #[msg("Chain config account does not match the target chain")]
+    InvalidChainConfig,
+    #[msg("Forge state is already initialized")]
+    AlreadyInitialized,
+    #[msg("Forge state has not been initialized")]
+    NotInitialized,
+    #[msg("Orb metadata lacks the required verified creator")]
+    CreatorNotVerified,
+    #[msg("Treasury balance is below the requested amount")]
+    InsufficientTreasuryBalance,
+    #[msg("Payment mint is not accepted")]
+    PaymentMintNotAccepted,
+    #[msg("Treasury token account is not held by the forge")]
+    InvalidTreasury,
+    #[msg("Global feed cap reached for this epoch")]
+    EpochCapReached,
+    #[msg("Orb was fed too recently to be fed again")]
+    RefeedCooldownActive,
+    #[msg("Orb mint is not on the allowlist")]
+    OrbNotAllowlisted,
+    #[msg("Threshold must be greater than zero unless free feeding is enabled")]
+    ZeroThreshold,
+    #[msg("Claim is not pending")]
+    ClaimNotPending,
+    #[msg("Claim has not reached its expiry")]
+    ClaimNotExpired,
+    #[msg("VAA was not emitted by the trusted emitter for its chain")]
+    UntrustedEmitter,
+    #[msg("VAA payload is not an Orb claim for Solana")]
+    InvalidVaaPayload,
+    #[msg("Fee recipient does not match the configured recipient")]
+    InvalidFeeRecipient,
+    #[msg("Orb has already been claimed")]
+    OrbAlreadyClaimed,
+    #[msg("Freeze mode needs the Orb token account, edition and freeze delegate")]
+    FreezeAccountsMissing,
+    #[msg("Batch feeding is unavailable while Orbs are frozen on feed")]
+    FreezeNeedsSingleFeed,
+    #[msg("Total claim cap reached")]
+    ClaimCapReached,
+    #[msg("Signer is neither the authority nor the operator")]
+    UnauthorizedOperator,
+    #[msg("Target chain is not supported")]
+    UnsupportedChain,
+    #[msg("Too many supported chains")]
+    TooManySupportedChains,
+    #[msg("Escrow mode needs the forge escrow token account")]
+    EscrowAccountMissing,
+    #[msg("Escrowed feeds must pay RARI to a same-chain target")]
+    InvalidEscrowFeed,
+    #[msg("Claim is not escrowed")]
+    ClaimNotEscrowed,
+    #[msg("Claim is escrowed; settle or cancel it first")]
+    ClaimEscrowed,
+    #[msg("Program is halted")]
+    ProgramHalted,
+    #[msg("Program is not halted")]
+    NotHalted,
+    #[msg("Emergency halt timelock has not elapsed")]
+    HaltTimelockActive,
+    #[msg("Asset id does not match the compressed Orb's tree and nonce")]
+    InvalidCompressedOrb,
+    #[msg("Compressed Orbs cannot be frozen on feed")]
+    FreezeUnsupportedForCompressed,
+    #[msg("Forge is not open yet")]
+    ForgeNotOpen,
+    #[msg("Forge is closed")]
+    ForgeClosed,
+    #[msg("Forge window must open before it closes")]
+    InvalidForgeWindow,
+    #[msg("Not enough SOL to pay rent for the claim record")]
+    InsufficientSolForRent,
+    #[msg("Receipt mint does not match the configured receipt mint")]
+    InvalidReceiptMint,
+    #[msg("Receipts need the receipt mint, the user's receipt account and its token program")]
+    ReceiptAccountsMissing,
+    #[msg("Receipt for this claim has already been burned")]
+    ReceiptAlreadyBurned,
+    #[msg("Threshold exceeds the caller's max burn")]
+    BurnExceedsMax,
+    #[msg("Disabled reason must be UTF-8 of at most 64 bytes")]
+    InvalidDisabledReason,
+    #[msg("User fed too recently")]
+    UserCooldownActive,
+    #[msg("Payment mint is not the native mint")]
+    NotNativeMint,
+    #[msg("Orb update authority is not accepted")]
+    UpdateAuthorityNotAccepted,
+    #[msg("Update authority is already accepted")]
+    UpdateAuthorityAlreadyAccepted,
+    #[msg("Too many accepted update authorities")]
+    TooManyUpdateAuthorities,
+    #[msg("Feeding must be paused first")]
+    ForgeNotPaused,
+    #[msg("Cross-chain claims are still pending")]
+    PendingClaimsRemain,
+    #[msg("Redeemed TTL cannot be negative")]
+    InvalidRedeemedTtl,
+    #[msg("Pruning redemption markers is disabled")]
+    PruningDisabled,
+    #[msg("Redemption marker has not outlived its TTL")]
+    RedeemedMarkerLive,
+    #[msg("VAA is older than a pruned redemption marker")]
+    VaaTooOld,
+    #[msg("This change requires governance approval")]
+    GovernanceRequired,
+    #[msg("Governance is not enabled")]
+    GovernanceDisabled,
+    #[msg("Invalid governance signer set or threshold")]
+    InvalidGovernance,
+    #[msg("Signer is not a governance signer")]
+    NotGovernanceSigner,
+    #[msg("Signer already approved this proposal")]
+    AlreadyApproved,
+    #[msg("Proposal was already executed")]
+    ProposalExecuted,
+    #[msg("Governance signers changed since the proposal was opened")]
+    StaleProposal,
+    #[msg("Account is not the forge's RARI mint")]
+    InvalidRariMint,
+    #[msg("RARI mint decimals differ from the recorded decimals")]
+    RariDecimalsMismatch,
+    #[msg("Signed feeds need a feed signer")]
+    InvalidFeedSigner,
+    #[msg("Missing or invalid feed signature")]
+    InvalidFeedSignature,
+    #[msg("Feed signature has expired")]
+    FeedSignatureExpired,
+    #[msg("Rarity tier is out of range")]
+    InvalidRarityTier,
+    #[msg("Claim was seeded for another claimer")]
+    SeededClaimerMismatch,
+    #[msg("Orb has been fed the maximum number of times")]
+    OrbFeedLimitReached,
+    #[msg("Governance NFT does not qualify for the discount")]
+    InvalidDiscountProof,
+    #[msg("Claim burned no more than the current threshold")]
+    NothingToRefund,
+    #[msg("Pending queue is full; retry once relayers drain it")]
+    PendingQueueFull,
+    #[msg("Pending queue size must be between 1 and MAX_PENDING_QUEUE_SIZE")]
+    InvalidPendingQueueSize,
+    #[msg("User does not hold the Orb being fed")]
+    OrbNotOwned,
+    #[msg("Threshold changes must go through propose_threshold while a timelock is set")]
+    ThresholdTimelocked,
+    #[msg("No threshold change has been proposed")]
+    NoPendingThreshold,
+    #[msg("Proposed threshold is still timelocked")]
+    ThresholdTimelockActive,
+    #[msg("Timelock cannot be negative")]
+    InvalidTimelock,
+    #[msg("Enabled cross-chain targets need a destination mint")]
+    MissingDestinationMint,
+    #[msg("Signer is not the delegate of the user's RARI account")]
+    InvalidDelegate,
+    #[msg("Delegated allowance does not cover the threshold")]
+    InsufficientDelegatedAmount,
+    #[msg("Event sink program missing or not the configured sink")]
+    InvalidEventSink,
+    #[msg("Early bonus mint does not match the configured bonus mint")]
+    InvalidBonusMint,
+    #[msg("Early bonus needs the bonus mint, the claimer's bonus account and its token program")]
+    BonusAccountsMissing,
+    #[msg("Bonus account does not belong to the claimer")]
+    InvalidBonusAccount,
+    #[msg("Pause duration cannot be negative")]
+    InvalidPauseDuration,
+    #[msg("Wormhole fee collector does not hold the bridge fee")]
+    WormholeFeeMismatch,
+    #[msg("Account holds feed payments and cannot be swept")]
+    ProtectedAccount,
+    #[msg("Clock skew tolerance must be between 0 and MAX_CLOCK_SKEW_TOLERANCE")]
+    InvalidClockSkewTolerance,
+    #[msg("An Orb burn replacing RARI must burn at least one token")]
+    InvalidOrbBurn,
+    #[msg("Orb token account holds fewer tokens than the Orb burn")]
+    InsufficientOrbBalance,
+    #[msg("Burning Orb tokens requires the Orb token program")]
+    OrbTokenProgramMissing,
+    #[msg("Orb burns are only supported by feed_orb")]
+    OrbBurnNeedsSingleFeed,
+    #[msg("Compressed Orbs have no tokens to burn")]
+    OrbBurnUnsupportedForCompressed,
+    #[msg("Orb must be staked (its token account frozen) to be fed")]
+    OrbNotStaked,
+    #[msg("Partner reward mint does not match the forge's")]
+    InvalidPartnerRewardMint,
+    #[msg("Partner reward accounts are required while a partner share is set")]
+    PartnerAccountsMissing,
+    #[msg("Partner reward account does not belong to the partner")]
+    InvalidPartnerAccount,
+    #[msg("Payment mint has a transfer hook but its accounts were not passed")]
+    TransferHookAccountsMissing,
+    #[msg("Memo is not UTF-8 text")]
+    InvalidMemo,
+    #[msg("Counters were already seeded from a legacy forge")]
+    CountersAlreadySeeded,
+    #[msg("Points account is required while points_per_feed is set")]
+    PointsAccountMissing,
+    #[msg("No points to redeem")]
+    NothingToRedeem,
+    #[msg("Payment mint is neither the forge's RARI mint nor an accepted payment mint")]
+    WrongRariMint,
+    #[msg("Acknowledgment does not carry the claim's burn proof")]
+    BurnProofMismatch,
+    #[msg("Claimer is on the denylist")]
+    ClaimerDenied,
+    #[msg("Orb metadata is still mutable")]
+    MetadataMutable,
+    #[msg("Only cross-chain claims can be reissued")]
+    ReissueNeedsCrossChain,
+    #[msg("Relayer fee vault is required while a relayer fee is set")]
+    RelayerFeeVaultMissing,
+    #[msg("Relayer fees are paid in RARI; pass RARI accounts to cover them")]
+    RelayerFeeNeedsRari,
+    #[msg("The user must sign unless a delegate feeds in its place")]
+    UserSignatureMissing,
+    #[msg("Durations and cooldowns cannot be negative")]
+    InvalidDuration,
+    #[msg("Proposal has not reached its approval threshold")]
+    ProposalNotApproved,
+    #[msg("A delegate feeding without the user's signature must credit the user")]
+    DelegateBeneficiaryMismatch,
+    #[msg("A delegate cannot feed without the user's signature while Orbs are frozen")]
+    DelegateCannotFreeze,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_feed_rejects_total_claimed_overflow() {
+        let mut forge_state = ForgeState {
+            total_claimed: u64::MAX,
+            ..Default::default()
+        };
+        let err = forge_state.record_feed(0).unwrap_err();
+        assert_eq!(err, ErrorCode::MathOverflow.into());
+        assert_eq!(forge_state.total_claimed, u64::MAX);
+    }
+
+    #[test]
+    fn record_feed_rejects_total_rari_burned_overflow() {
+        let mut forge_state = ForgeState {
+            total_rari_burned: u64::MAX - 1,
+            ..Default::default()
+        };
+        let err = forge_state.record_feed(2).unwrap_err();
+        assert_eq!(err, ErrorCode::MathOverflow.into());
+    }
+
+    #[test]
+    fn record_feed_accumulates_totals() {
+        let mut forge_state = ForgeState {
+            total_claimed: u64::MAX - 1,
+            ..Default::default()
+        };
+        forge_state.record_feed(5).unwrap();
+        assert_eq!(forge_state.total_claimed, u64::MAX);
+        assert_eq!(forge_state.total_rari_burned, 5);
+    }
+
+    #[test]
+    fn record_feed_stops_exactly_at_claim_cap() {
+        let mut forge_state = ForgeState {
+            total_claimed: 9,
+            max_total_claims: 10,
+            ..Default::default()
+        };
+        forge_state.record_feed(0).unwrap();
+        assert_eq!(forge_state.total_claimed, 10);
+
+        let err = forge_state.record_feed(0).unwrap_err();
+        assert_eq!(err, ErrorCode::ClaimCapReached.into());
+        assert_eq!(forge_state.total_claimed, 10);
+    }
+
+    #[test]
+    fn emergency_halt_clears_exactly_at_timelock() {
+        let mut forge_state = ForgeState::default();
+        forge_state.halt(1_000).unwrap();
+        assert!(forge_state.halted);
+
+        let err = forge_state
+            .clear_halt(1_000 + EMERGENCY_HALT_TIMELOCK - 1)
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::HaltTimelockActive.into());
+        assert!(forge_state.halted);
+
+        forge_state
+            .clear_halt(1_000 + EMERGENCY_HALT_TIMELOCK)
+            .unwrap();
+        assert!(!forge_state.halted);
+    }
+
+    #[test]
+    fn halting_near_the_end_of_time_overflows() {
+        let mut forge_state = ForgeState::default();
+        let err = forge_state.halt(i64::MAX).unwrap_err();
+        assert_eq!(err, ErrorCode::MathOverflow.into());
+        assert!(!forge_state.halted);
+    }
+
+    #[test]
+    fn clearing_requires_an_active_halt() {
+        let mut forge_state = ForgeState::default();
+        let err = forge_state.clear_halt(i64::MAX).unwrap_err();
+        assert_eq!(err, ErrorCode::NotHalted.into());
+    }
+
+    #[test]
+    fn forge_window_bounds_feeds() {
+        let forge_state = ForgeState {
+            start_ts: 100,
+            end_ts: 200,
+            ..Default::default()
+        };
+        assert_eq!(
+            forge_state.require_open(99).unwrap_err(),
+            ErrorCode::ForgeNotOpen.into()
+        );
+        forge_state.require_open(100).unwrap();
+        forge_state.require_open(200).unwrap();
+        assert_eq!(
+            forge_state.require_open(201).unwrap_err(),
+            ErrorCode::ForgeClosed.into()
+        );
+    }
+
+    #[test]
+    fn zero_window_bounds_are_disabled() {
+        let forge_state = ForgeState {
+            end_ts: 200,
+            ..Default::default()
+        };
+        forge_state.require_open(i64::MIN).unwrap();
+
+        let forge_state = ForgeState {
+            start_ts: 100,
+            ..Default::default()
+        };
+        forge_state.require_open(i64::MAX).unwrap();
+    }
+
+    #[test]
+    fn chain_counter_rejects_overflow() {
+        let mut chain_counter = ChainCounter {
+            chain_id: 10,
+            count: u64::MAX,
+        };
+        let err = chain_counter.record(10).unwrap_err();
+        assert_eq!(err, ErrorCode::MathOverflow.into());
+        assert_eq!(chain_counter.count, u64::MAX);
+    }
+
+    #[test]
+    fn crc32_matches_the_ieee_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn orb_fed_compact_has_its_documented_layout() {
+        let event = OrbFedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq: 9,
+            orb_mint: Pubkey::new_from_array([1; 32]),
+            payer: Pubkey::new_from_array([4; 32]),
+            claimer: Pubkey::new_from_array([2; 32]),
+            target_chain: 8453,
+            rari_burned: 100,
+            wormhole_sequence: Some(7),
+            name_hash: [3; 32],
+            route: ROUTE_CROSS_CHAIN,
+            forge_version: FORGE_VERSION,
+            claim_index: 0,
+            burn_proof: [5; 32],
+            wormhole_fee: 0,
+            memo: [0; MEMO_LEN],
+            batch_index: 0,
+            batch_id: [0; 16],
+        };
+        let payload = OrbFedCompact::new(&event).unwrap().try_to_vec().unwrap();
+        assert_eq!(payload.len(), OrbFedCompact::LEN);
+        assert_eq!(&payload[65..67], &8453u16.to_le_bytes());
+        assert_eq!(payload[75], 1);
+        assert_eq!(&payload[76..84], &7u64.to_le_bytes());
+        assert_eq!(&payload[117..119], &FORGE_VERSION.to_le_bytes());
+        assert_eq!(&payload[119..127], &9u64.to_le_bytes());
+        assert_eq!(
+            &payload[127..],
+            &crc32(&payload[..127]).to_le_bytes()
+        );
+    }
+
+    #[test]
+    fn disabled_reason_is_zero_padded() {
+        let padded = encode_disabled_reason("bridge congested".as_bytes()).unwrap();
+        assert_eq!(&padded[..16], b"bridge congested");
+        assert!(padded[16..].iter().all(|&byte| byte == 0));
+
+        let chain_config = ChainConfig {
+            chain_id: 10,
+            threshold: 0,
+            enabled: false,
+            disabled_reason: padded,
+            destination_mint: [0; 32],
+        };
+        assert_eq!(chain_config.disabled_reason(), "bridge congested");
+    }
+
+    #[test]
+    fn enabled_cross_chain_targets_need_a_destination_mint() {
+        let mut chain_config = ChainConfig {
+            chain_id: 10,
+            threshold: 0,
+            enabled: true,
+            disabled_reason: [0; MAX_DISABLED_REASON_LEN],
+            destination_mint: [0; 32],
+        };
+        let err = chain_config.require_destination_mint().unwrap_err();
+        assert_eq!(err, ErrorCode::MissingDestinationMint.into());
+
+        chain_config.destination_mint = [7; 32];
+        chain_config.require_destination_mint().unwrap();
+
+        // Solana feeds settle in place and a disabled chain posts nothing
+        chain_config.destination_mint = [0; 32];
+        chain_config.enabled = false;
+        chain_config.require_destination_mint().unwrap();
+        chain_config.chain_id = SOLANA_CHAIN_ID;
+        chain_config.enabled = true;
+        chain_config.require_destination_mint().unwrap();
+    }
+
+    #[test]
+    fn applying_an_enabled_chain_config_clears_the_disabled_reason() {
+        let mut chain_config = ChainConfig {
+            chain_id: 0,
+            threshold: 0,
+            enabled: false,
+            disabled_reason: encode_disabled_reason(b"maintenance").unwrap(),
+            destination_mint: [7; 32],
+        };
+        chain_config.apply(10, 500, false).unwrap();
+        assert_eq!((chain_config.chain_id, chain_config.threshold), (10, 500));
+        assert_eq!(chain_config.disabled_reason(), "maintenance");
+
+        chain_config.apply(10, 750, true).unwrap();
+        assert!(chain_config.enabled);
+        assert_eq!(chain_config.threshold, 750);
+        assert_eq!(chain_config.disabled_reason(), "");
+
+        chain_config.destination_mint = [0; 32];
+        let err = chain_config.apply(10, 750, true).unwrap_err();
+        assert_eq!(err, ErrorCode::MissingDestinationMint.into());
+    }
+
+    #[test]
+    fn disabled_reason_rejects_invalid_input() {
+        let err = encode_disabled_reason(&[0xff, 0xfe]).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidDisabledReason.into());
+
+        let err = encode_disabled_reason(&[b'a'; MAX_DISABLED_REASON_LEN + 1]).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidDisabledReason.into());
+        encode_disabled_reason(&[b'a'; MAX_DISABLED_REASON_LEN]).unwrap();
+    }
+
+    #[test]
+    fn user_cooldown_lapses_exactly_after_its_length() {
+        let forge_state = ForgeState {
+            user_cooldown_seconds: 60,
+            ..Default::default()
+        };
+        let mut feeder_stats = FeederStats::default();
+        feeder_stats.record(&forge_state, 1_000).unwrap();
+        assert!(feeder_stats.cooling_down(&forge_state, 1_059));
+        assert!(!feeder_stats.cooling_down(&forge_state, 1_060));
+        assert!(!feeder_stats.cooling_down(&ForgeState::default(), 1_000));
+    }
+
+    #[test]
+    fn claim_indices_are_sequential() {
+        let mut forge_state = ForgeState::default();
+        assert_eq!(forge_state.assign_claim_index().unwrap(), 0);
+        assert_eq!(forge_state.assign_claim_index().unwrap(), 1);
+        assert_eq!(forge_state.next_claim_index, 2);
+    }
+
+    #[test]
+    fn claim_index_rejects_overflow() {
+        let mut forge_state = ForgeState {
+            next_claim_index: u64::MAX,
+            ..Default::default()
+        };
+        let err = forge_state.assign_claim_index().unwrap_err();
+        assert_eq!(err, ErrorCode::MathOverflow.into());
+        assert_eq!(forge_state.next_claim_index, u64::MAX);
+    }
+
+    #[test]
+    fn update_authorities_are_capped_and_removable() {
+        let mut forge_state = ForgeState::default();
+        let accepted = Pubkey::new_unique();
+        assert!(forge_state.accepts_update_authority(&accepted));
+
+        forge_state.add_update_authority(accepted).unwrap();
+        assert!(forge_state.accepts_update_authority(&accepted));
+        assert!(!forge_state.accepts_update_authority(&Pubkey::new_unique()));
+        let err = forge_state.add_update_authority(accepted).unwrap_err();
+        assert_eq!(err, ErrorCode::UpdateAuthorityAlreadyAccepted.into());
+
+        for _ in 1..MAX_UPDATE_AUTHORITIES {
+            forge_state
+                .add_update_authority(Pubkey::new_unique())
+                .unwrap();
+        }
+        let err = forge_state
+            .add_update_authority(Pubkey::new_unique())
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::TooManyUpdateAuthorities.into());
+
+        forge_state.remove_update_authority(&accepted).unwrap();
+        assert!(!forge_state.accepts_update_authority(&accepted));
+        let err = forge_state.remove_update_authority(&accepted).unwrap_err();
+        assert_eq!(err, ErrorCode::UpdateAuthorityNotAccepted.into());
+    }
+
+    #[test]
+    fn releasing_pending_claims_saturates() {
+        let mut forge_state = ForgeState::default();
+        forge_state.add_pending_claim().unwrap();
+        forge_state.release_pending_claim();
+        forge_state.release_pending_claim();
+        assert_eq!(forge_state.pending_claims, 0);
+    }
+
+    #[test]
+    fn redeemed_markers_prune_only_after_their_ttl() {
+        let mut forge_state = ForgeState::default();
+        let err = forge_state.record_pruned(1_000, i64::MAX).unwrap_err();
+        assert_eq!(err, ErrorCode::PruningDisabled.into());
+
+        forge_state.redeemed_ttl = 100;
+        let prunable_at = 1_000 + VAA_FINALITY_WINDOW + 100;
+        let err = forge_state.record_pruned(1_000, prunable_at).unwrap_err();
+        assert_eq!(err, ErrorCode::RedeemedMarkerLive.into());
+        assert!(forge_state.accepts_vaa_timestamp(1_000));
+
+        forge_state.record_pruned(1_000, prunable_at + 1).unwrap();
+        assert!(!forge_state.accepts_vaa_timestamp(1_000));
+        assert!(forge_state.accepts_vaa_timestamp(1_001));
+
+        // Pruning an older marker never moves the horizon back
+        forge_state.record_pruned(500, prunable_at + 1).unwrap();
+        assert_eq!(forge_state.pruned_through, 1_000);
+    }
+
+    #[test]
+    fn proposals_apply_once_the_threshold_approves() {
+        let signers = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut forge_state = ForgeState::default();
+        forge_state
+            .apply_change(&ProposedChange::Governance {
+                signers: signers.to_vec(),
+                threshold: 2,
+            })
+            .unwrap();
+
+        let mut proposal = ProposalAccount {
+            change: ProposedChange::BurnBps { burn_bps: 2_500 },
+            governance_nonce: forge_state.governance_nonce,
+            ..Default::default()
+        };
+        proposal
+            .approve(&mut forge_state, &signers[0], 1_000)
+            .unwrap();
+        assert_eq!(forge_state.burn_bps, 0);
+        let err = proposal
+            .approve(&mut forge_state, &signers[0], 1_000)
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::AlreadyApproved.into());
+        let err = proposal
+            .approve(&mut forge_state, &Pubkey::new_unique(), 1_000)
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::NotGovernanceSigner.into());
+
+        proposal
+            .approve(&mut forge_state, &signers[2], 1_000)
+            .unwrap();
+        assert!(proposal.executed);
+        assert_eq!(forge_state.burn_bps, 2_500);
+        let err = proposal
+            .approve(&mut forge_state, &signers[1], 1_000)
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::ProposalExecuted.into());
+    }
+
+    #[test]
+    fn approved_threshold_changes_wait_out_the_timelock() {
+        let signers = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut forge_state = ForgeState {
+            rari_threshold: 100,
+            timelock_seconds: 3_600,
+            ..Default::default()
+        };
+        forge_state
+            .apply_change(&ProposedChange::Governance {
+                signers: signers.to_vec(),
+                threshold: 2,
+            })
+            .unwrap();
+
+        let mut proposal = ProposalAccount {
+            change: ProposedChange::RariThreshold { rari_threshold: 50 },
+            governance_nonce: forge_state.governance_nonce,
+            ..Default::default()
+        };
+        proposal
+            .approve(&mut forge_state, &signers[0], 1_000)
+            .unwrap();
+        let err = proposal.execute(&mut forge_state, 1_000).unwrap_err();
+        assert_eq!(err, ErrorCode::ProposalNotApproved.into());
+
+        proposal
+            .approve(&mut forge_state, &signers[1], 1_000)
+            .unwrap();
+        assert_eq!(proposal.approved_at, 1_000);
+        assert!(!proposal.executed);
+        assert_eq!(forge_state.rari_threshold, 100);
+        let err = proposal.execute(&mut forge_state, 4_599).unwrap_err();
+        assert_eq!(err, ErrorCode::ThresholdTimelockActive.into());
+
+        proposal.execute(&mut forge_state, 4_600).unwrap();
+        assert!(proposal.executed);
+        assert_eq!(forge_state.rari_threshold, 50);
+        let err = proposal.execute(&mut forge_state, 4_600).unwrap_err();
+        assert_eq!(err, ErrorCode::ProposalExecuted.into());
+    }
+
+    #[test]
+    fn signer_set_changes_stale_open_proposals() {
+        let signer = Pubkey::new_unique();
+        let mut forge_state = ForgeState::default();
+        let governance = ProposedChange::Governance {
+            signers: vec![signer, Pubkey::new_unique()],
+            threshold: 2,
+        };
+        forge_state.apply_change(&governance).unwrap();
+        let mut proposal = ProposalAccount {
+            governance_nonce: forge_state.governance_nonce,
+            ..Default::default()
+        };
+        forge_state.apply_change(&governance).unwrap();
+
+        let err = proposal.approve(&mut forge_state, &signer, 0).unwrap_err();
+        assert_eq!(err, ErrorCode::StaleProposal.into());
+    }
+
+    #[test]
+    fn governance_rejects_unreachable_or_duplicate_signer_sets() {
+        let signer = Pubkey::new_unique();
+        for (signers, threshold) in [
+            (vec![signer], 2),
+            (vec![signer, signer], 1),
+            (vec![signer], 0),
+            (vec![Pubkey::new_unique(); MAX_GOVERNANCE_SIGNERS + 1], 1),
+        ] {
+            let err = ProposedChange::Governance { signers, threshold }
+                .validate()
+                .unwrap_err();
+            assert_eq!(err, ErrorCode::InvalidGovernance.into());
+        }
+        ProposedChange::Governance {
+            signers: Vec::new(),
+            threshold: 0,
+        }
+        .validate()
+        .unwrap();
+    }
+
+    /// Ed25519 program data checking one signature, laid out as the web3.js
+    /// and solana-sdk helpers do, with every offset in this instruction.
+    fn ed25519_instruction_data(
+        pubkey: &Pubkey,
+        message: &[u8],
+        instruction_index: u16,
+    ) -> Vec<u8> {
+        const PUBKEY_OFFSET: u16 = 16;
+        const SIGNATURE_OFFSET: u16 = PUBKEY_OFFSET + 32;
+        const MESSAGE_OFFSET: u16 = SIGNATURE_OFFSET + 64;
+        let mut data = vec![1, 0];
+        for field in [
+            SIGNATURE_OFFSET,
+            instruction_index,
+            PUBKEY_OFFSET,
+            instruction_index,
+            MESSAGE_OFFSET,
+            message.len() as u16,
+            instruction_index,
+        ] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+        data.extend_from_slice(pubkey.as_ref());
+        data.extend_from_slice(&[0; 64]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    #[test]
+    fn ed25519_instruction_yields_its_signer_and_message() {
+        let signer = Pubkey::new_unique();
+        let message = [7u8; FEED_AUTHORIZATION_LEN];
+        let data = ed25519_instruction_data(&signer, &message, u16::MAX);
+        let (parsed_signer, parsed_message) = parse_ed25519_instruction(&data).unwrap();
+        assert_eq!(parsed_signer, signer);
+        assert_eq!(parsed_message, &message);
+    }
+
+    #[test]
+    fn ed25519_instruction_must_not_reference_other_instructions() {
+        let message = [7; FEED_AUTHORIZATION_LEN];
+        let data = ed25519_instruction_data(&Pubkey::new_unique(), &message, 0);
+        let err = parse_ed25519_instruction(&data).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidFeedSignature.into());
+
+        let mut data = ed25519_instruction_data(&Pubkey::new_unique(), &message, u16::MAX);
+        data.truncate(data.len() - 1);
+        let err = parse_ed25519_instruction(&data).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidFeedSignature.into());
+    }
+
+    #[test]
+    fn rarity_tiers_pick_their_threshold() {
+        let forge_state = ForgeState {
+            tier_thresholds: [10, 0, 30, 40],
+            ..Default::default()
+        };
+        assert_eq!(forge_state.threshold_for_tier(0, 5), 5);
+        assert_eq!(forge_state.threshold_for_tier(1, 5), 10);
+        // An unset tier falls back to the base threshold
+        assert_eq!(forge_state.threshold_for_tier(2, 5), 5);
+        assert_eq!(forge_state.threshold_for_tier(MAX_RARITY_TIER, 5), 40);
+    }
+
+    #[test]
+    fn active_claims_count_down_to_zero_and_no_further() {
+        let mut forge_state = ForgeState::default();
+        forge_state.add_active_claim().unwrap();
+        forge_state.add_active_claim().unwrap();
+        forge_state.release_active_claim().unwrap();
+        forge_state.release_active_claim().unwrap();
+        assert_eq!(forge_state.active_claims, 0);
+
+        let err = forge_state.release_active_claim().unwrap_err();
+        assert_eq!(err, ErrorCode::MathOverflow.into());
+        assert_eq!(forge_state.active_claims, 0);
+    }
+
+    #[test]
+    fn emitter_rotation_keeps_the_original_emitter_at_version_zero() {
+        let mut forge_state = ForgeState::default();
+        let (original, _) =
+            Pubkey::find_program_address(&[wormhole::SEED_PREFIX_EMITTER], &crate::ID);
+        assert_eq!(forge_state.emitter_address(), original);
+
+        forge_state.emitter_seed_version = 1;
+        let (rotated, _) =
+            Pubkey::find_program_address(&[wormhole::SEED_PREFIX_EMITTER, &[1]], &crate::ID);
+        assert_eq!(forge_state.emitter_address(), rotated);
+        assert_ne!(rotated, original);
+    }
+
+    #[test]
+    fn orb_feed_cap_counts_every_feed() {
+        let mut forge_state = ForgeState {
+            max_feeds_per_orb: 2,
+            ..Default::default()
+        };
+        assert!(forge_state.allows_orb_feed(1));
+        assert!(!forge_state.allows_orb_feed(2));
+
+        // 0 lifts the cap
+        forge_state.max_feeds_per_orb = 0;
+        assert!(forge_state.allows_orb_feed(u16::MAX));
+    }
+
+    #[test]
+    fn migrating_a_baseline_forge_marks_it_initialized() {
+        // The baseline layout: authority, wormhole_bridge, rari_mint,
+        // rari_threshold, total_claimed and paused, zero-padded by the realloc
+        let authority = Pubkey::new_unique();
+        let mut data = <ForgeState as anchor_lang::Discriminator>::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(&100u64.to_le_bytes());
+        data.extend_from_slice(&7u64.to_le_bytes());
+        data.push(0);
+        data.resize(8 + ForgeState::LEN, 0);
+
+        let mut forge_state = ForgeState::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(forge_state.authority, authority);
+        assert_eq!(forge_state.layout_version, 0);
+        assert!(require_initialized(&forge_state).is_err());
+
+        // The mint's decimals are needed from before layout 13
+        let err = forge_state.clone().upgrade_layout(None).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidRariMint.into());
+
+        forge_state.upgrade_layout(Some(9)).unwrap();
+        assert!(require_initialized(&forge_state).is_ok());
+        assert_eq!(forge_state.layout_version, FORGE_LAYOUT_VERSION);
+        assert_eq!(forge_state.rari_decimals, 9);
+        assert_eq!(forge_state.active_claims, 7);
+        assert_eq!(
+            forge_state.supported_chains,
+            DEFAULT_SUPPORTED_CHAINS.to_vec()
+        );
+        assert!(forge_state.charge_sol_fee_same_chain);
+    }
+
+    #[test]
+    fn epoch_end_rejects_overflow() {
+        let mut forge_state = ForgeState {
+            current_epoch_start: i64::MAX,
+            epoch_seconds: 1,
+            ..Default::default()
+        };
+        let err = forge_state.current_epoch_count_at(0).unwrap_err();
+        assert_eq!(err, ErrorCode::MathOverflow.into());
+        let err = forge_state.record_epoch_feed(0).unwrap_err();
+        assert_eq!(err, ErrorCode::MathOverflow.into());
+    }
+
+    #[test]
+    fn refund_covers_the_burn_above_the_current_threshold() {
+        let mut forge_state = ForgeState {
+            burn_bps: 7_500,
+            ..Default::default()
+        };
+        let mut claim_record = ClaimRecord {
+            rari_burned: 75,
+            ..Default::default()
+        };
+        // 75% of a threshold lowered from 100 to 80 burns 60
+        assert_eq!(claim_record.refund_due(&forge_state, 80), 15);
+        assert_eq!(claim_record.refund_due(&forge_state, 100), 0);
+        // A raised threshold owes nothing
+        assert_eq!(claim_record.refund_due(&forge_state, 120), 0);
+
+        // A feed charged at a tier is measured against that tier's threshold
+        forge_state.tier_thresholds[0] = 200;
+        claim_record.rarity_tier = 1;
+        claim_record.rari_burned = 150;
+        assert_eq!(claim_record.refund_due(&forge_state, 100), 0);
+        forge_state.tier_thresholds[0] = 160;
+        assert_eq!(claim_record.refund_due(&forge_state, 100), 30);
+
+        // and a discounted feed against the discounted threshold
+        claim_record.rarity_tier = 0;
+        claim_record.discount_bps = 5_000;
+        claim_record.rari_burned = 45;
+        assert_eq!(claim_record.refund_due(&forge_state, 100), 8);
+    }
+
+    #[test]
+    fn only_solana_feeds_stay_on_chain() {
+        assert!(!is_cross_chain(SOLANA_CHAIN_ID));
+        assert_eq!(route_for(SOLANA_CHAIN_ID), ROUTE_SAME_CHAIN);
+        for chain_id in [0, 10, 8453, u16::MAX] {
+            assert!(is_cross_chain(chain_id));
+            assert_eq!(route_for(chain_id), ROUTE_CROSS_CHAIN);
+        }
+    }
+
+    #[test]
+    fn pending_queue_applies_backpressure() {
+        let mut queue = PendingQueue {
+            capacity: 2,
+            claims: Vec::new(),
+        };
+        let (first, second, third) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        queue.push(first).unwrap();
+        queue.push(second).unwrap();
+        assert!(queue.push(third).is_err());
+
+        queue.remove(&first);
+        queue.remove(&first);
+        queue.push(third).unwrap();
+        assert_eq!(queue.claims, vec![second, third]);
+    }
+
+    #[test]
+    fn proposed_threshold_applies_exactly_at_timelock() {
+        let mut forge_state = ForgeState {
+            rari_threshold: 100,
+            timelock_seconds: 3_600,
+            ..Default::default()
+        };
+        assert_eq!(
+            forge_state.apply_threshold(0).unwrap_err(),
+            ErrorCode::NoPendingThreshold.into()
+        );
+
+        forge_state.propose_threshold(500, 1_000).unwrap();
+        assert_eq!(forge_state.threshold_effective_at, 4_600);
+        let err = forge_state.apply_threshold(4_599).unwrap_err();
+        assert_eq!(err, ErrorCode::ThresholdTimelockActive.into());
+        assert_eq!(forge_state.rari_threshold, 100);
+
+        forge_state.apply_threshold(4_600).unwrap();
+        assert_eq!(forge_state.rari_threshold, 500);
+        assert_eq!(forge_state.threshold_effective_at, 0);
+    }
+
+    #[test]
+    fn fed_claims_are_already_claimed() {
+        let mut claim_record = ClaimRecord {
+            prefunded: true,
+            ..Default::default()
+        };
+        require_unclaimed(&claim_record).unwrap();
+
+        claim_record.feed_count = 1;
+        claim_record.claimed_at = 1_700_000_000;
+        let err = require_unclaimed(&claim_record).unwrap_err();
+        assert_eq!(err, ErrorCode::OrbAlreadyClaimed.into());
+    }
+
+    #[test]
+    fn log_event_instruction_wraps_the_serialized_event() {
+        let event = OrbFedEvent {
+            schema_version: EVENT_SCHEMA_VERSION,
+            seq: 4,
+            orb_mint: Pubkey::new_unique(),
+            payer: Pubkey::new_unique(),
+            claimer: Pubkey::new_unique(),
+            target_chain: 10,
+            rari_burned: 100,
+            wormhole_sequence: Some(7),
+            name_hash: [1; 32],
+            route: ROUTE_CROSS_CHAIN,
+            forge_version: FORGE_VERSION,
+            claim_index: 3,
+            burn_proof: [2; 32],
+            wormhole_fee: 100,
+            memo: [0; MEMO_LEN],
+            batch_index: 0,
+            batch_id: [0; 16],
+        };
+        let (event_sink, forge_state) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let instruction = log_event_instruction(event_sink, forge_state, &event).unwrap();
+        assert_eq!(instruction.program_id, event_sink);
+        assert_eq!(
+            instruction.accounts,
+            vec![AccountMeta::new_readonly(forge_state, false)]
+        );
+
+        let payload = event.try_to_vec().unwrap();
+        assert_eq!(instruction.data[..8], LOG_EVENT_DISCRIMINATOR);
+        assert_eq!(
+            instruction.data[8..12],
+            (payload.len() as u32).to_le_bytes()
+        );
+        assert_eq!(instruction.data[12..], payload[..]);
+    }
+
+    #[test]
+    fn early_bonus_stops_after_the_first_feeds() {
+        let mut forge_state = ForgeState {
+            early_bonus_count: 2,
+            early_bonus_mint: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        assert!(forge_state.grants_early_bonus());
+        forge_state.record_feed(0).unwrap();
+        assert!(forge_state.grants_early_bonus());
+        forge_state.record_feed(0).unwrap();
+        assert!(!forge_state.grants_early_bonus());
+
+        forge_state.total_claimed = 0;
+        forge_state.early_bonus_mint = Pubkey::default();
+        assert!(!forge_state.grants_early_bonus());
+    }
+
+    #[test]
+    fn acks_must_match_a_pending_claims_burn_proof() {
+        let orb_mint = Pubkey::new_unique();
+        let mut claim_record = ClaimRecord {
+            orb_mint,
+            feed_count: 1,
+            status: ClaimStatus::Pending,
+            burn_proof: [7; 32],
+            ..Default::default()
+        };
+        let ack = ClaimAckMessage {
+            orb_mint,
+            burn_proof: [7; 32],
+        };
+        claim_record.check_ack(&ack).unwrap();
+
+        let err = claim_record
+            .check_ack(&ClaimAckMessage {
+                burn_proof: [8; 32],
+                ..ack.clone()
+            })
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::BurnProofMismatch.into());
+        let err = claim_record
+            .check_ack(&ClaimAckMessage {
+                orb_mint: Pubkey::new_unique(),
+                ..ack.clone()
+            })
+            .unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidVaaPayload.into());
+
+        claim_record.status = ClaimStatus::Confirmed;
+        let err = claim_record.check_ack(&ack).unwrap_err();
+        assert_eq!(err, ErrorCode::ClaimNotPending.into());
+    }
+
+    #[test]
+    fn claim_record_migration_grows_to_the_current_layout() {
+        assert_eq!(
+            ClaimRecord::LEN,
+            UNVERSIONED_CLAIM_RECORD_LEN + 1 + 32 + MEMO_LEN + 1 + 1 + 2
+        );
+        let unversioned = 8 + UNVERSIONED_CLAIM_RECORD_LEN;
+        assert!(ClaimRecord::is_unversioned(unversioned));
+        assert!(ClaimRecord::is_unversioned(8 + 100));
+        assert!(!ClaimRecord::is_unversioned(8 + ClaimRecord::LEN));
+
+        assert_eq!(ClaimRecord::migrated_len(unversioned), 8 + ClaimRecord::LEN);
+        assert_eq!(ClaimRecord::migrated_len(8 + 100), 8 + ClaimRecord::LEN);
+        assert_eq!(
+            ClaimRecord::migrated_len(8 + ClaimRecord::LEN),
+            8 + ClaimRecord::LEN
+        );
+    }
+
+    #[test]
+    fn unversioned_claim_record_shifts_past_the_version_byte() {
+        let claim_record = ClaimRecord {
+            version: CLAIM_RECORD_VERSION,
+            orb_mint: Pubkey::new_unique(),
+            claimer: Pubkey::new_unique(),
+            wormhole_sequence: Some(7),
+            feed_count: 2,
+            rari_burned: 100,
+            ..Default::default()
+        };
+        let mut current = Vec::new();
+        claim_record.try_serialize(&mut current).unwrap();
+
+        // The same record as written before `version` existed
+        let mut data = vec![0; ClaimRecord::migrated_len(8 + UNVERSIONED_CLAIM_RECORD_LEN)];
+        data[..8].copy_from_slice(&current[..8]);
+        data[8..current.len() - 1].copy_from_slice(&current[9..]);
+        insert_claim_version(&mut data, 8 + UNVERSIONED_CLAIM_RECORD_LEN);
+
+        let migrated = ClaimRecord::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(migrated.version, 0);
+        assert_eq!(migrated.orb_mint, claim_record.orb_mint);
+        assert_eq!(migrated.claimer, claim_record.claimer);
+        assert_eq!(migrated.wormhole_sequence, Some(7));
+        assert_eq!(migrated.feed_count, 2);
+        assert_eq!(migrated.rari_burned, 100);
+    }
+
+    #[test]
+    fn burn_proof_is_stable_for_fixed_inputs() {
+        let orb_mint = Pubkey::new_from_array([1; 32]);
+        let claimer = Pubkey::new_from_array([2; 32]);
+        let burn_proof = burn_proof_for(&orb_mint, &claimer, 100, 1_700_000_000, 8453);
+        assert_eq!(
+            burn_proof,
+            [
+                50, 202, 139, 142, 177, 80, 102, 56, 24, 188, 180, 88, 221, 250, 245, 183, 181,
+                172, 214, 255, 127, 20, 32, 72, 189, 248, 149, 219, 157, 125, 108, 85
+            ]
+        );
+        // Every input is committed to
+        assert_ne!(
+            burn_proof,
+            burn_proof_for(&orb_mint, &claimer, 100, 1_700_000_000, 10)
+        );
+        assert_ne!(
+            burn_proof,
+            burn_proof_for(&claimer, &orb_mint, 100, 1_700_000_000, 8453)
+        );
+    }
+
+    #[test]
+    fn batch_id_is_shared_within_an_instruction_only() {
+        let namespace = [0; 16];
+        let batch_id = batch_id_for(&namespace, 7);
+        assert_eq!(batch_id, batch_id_for(&namespace, 7));
+        assert_ne!(batch_id, batch_id_for(&namespace, 8));
+        assert_ne!(batch_id, batch_id_for(&[1; 16], 7));
+    }
+
+    #[test]
+    fn timed_pause_lapses_at_its_expiry() {
+        let mut forge_state = ForgeState {
+            pause_flags: PAUSE_FEED,
+            pause_expires_at: 1_000,
+            ..Default::default()
+        };
+        assert!(forge_state.is_paused_at(PAUSE_FEED, 999));
+        assert!(!forge_state.is_paused_at(PAUSE_FEED, 1_000));
+        assert!(!forge_state.is_paused_at(PAUSE_CROSS_CHAIN_FEED, 999));
+
+        // Without an expiry the pause holds until cleared
+        forge_state.pause_expires_at = 0;
+        assert!(forge_state.is_paused_at(PAUSE_FEED, i64::MAX));
+    }
+
+    #[test]
+    fn points_accrue_until_overflow() {
+        let mut points_account = PointsAccount::default();
+        points_account.accrue(10).unwrap();
+        points_account.accrue(10).unwrap();
+        assert_eq!(points_account.points, 20);
+
+        points_account.points = u64::MAX - 5;
+        let err = points_account.accrue(10).unwrap_err();
+        assert_eq!(err, ErrorCode::MathOverflow.into());
+    }
+
+    #[test]
+    fn counters_are_seeded_once() {
+        let mut forge_state = ForgeState::default();
+        forge_state.seed_counters(500, 7_000, 512).unwrap();
+        assert_eq!(
+            (
+                forge_state.total_claimed,
+                forge_state.total_rari_burned,
+                forge_state.next_claim_index
+            ),
+            (500, 7_000, 512)
+        );
+        assert_eq!(forge_state.assign_claim_index().unwrap(), 512);
+
+        let err = forge_state.seed_counters(0, 0, 0).unwrap_err();
+        assert_eq!(err, ErrorCode::CountersAlreadySeeded.into());
+        assert_eq!(forge_state.total_claimed, 500);
+    }
+
+    #[test]
+    fn text_memos_must_be_utf8() {
+        let mut forge_state = ForgeState::default();
+        let mut memo = [0; MEMO_LEN];
+        memo[..4].copy_from_slice(&[0xff, 0xfe, 0xfd, 0xfc]);
+        forge_state.require_valid_memo(&memo).unwrap();
+
+        forge_state.memo_is_text = true;
+        let err = forge_state.require_valid_memo(&memo).unwrap_err();
+        assert_eq!(err, ErrorCode::InvalidMemo.into());
+
+        memo[..4].copy_from_slice(b"q3-a");
+        forge_state.require_valid_memo(&memo).unwrap();
+        forge_state.require_valid_memo(&[0; MEMO_LEN]).unwrap();
+        forge_state.require_valid_memo(&[b'x'; MEMO_LEN]).unwrap();
+    }
+
+    #[test]
+    fn feed_storm_trips_the_circuit_breaker() {
+        let mut forge_state = ForgeState {
+            anomaly_threshold: 2,
+            ..Default::default()
+        };
+        assert!(!forge_state.record_minute_feed(1_000).unwrap());
+        assert!(!forge_state.record_minute_feed(1_030).unwrap());
+        assert!(forge_state.record_minute_feed(1_059).unwrap());
+        assert_eq!(forge_state.feeds_this_minute, 3);
+        assert!(forge_state.is_paused_at(PAUSE_FEED, i64::MAX));
+
+        // A new window starts the count over
+        forge_state.pause_flags = 0;
+        assert!(!forge_state.record_minute_feed(1_060).unwrap());
+        assert_eq!(
+            (forge_state.minute_start, forge_state.feeds_this_minute),
+            (1_060, 1)
+        );
+
+        // A zero threshold never trips
+        forge_state.anomaly_threshold = 0;
+        for _ in 0..10 {
+            assert!(!forge_state.record_minute_feed(1_061).unwrap());
+        }
+        assert!(!forge_state.is_paused(PAUSE_FEED));
+    }
+
+    #[test]
+    fn namespaces_keep_forge_addresses_apart() {
+        let original = [0; 16];
+        let partner = *b"partner-forge-01";
+        assert_eq!(
+            derive_forge_state_pda(&original).0,
+            Pubkey::find_program_address(&[b"forge_state"], &crate::ID).0
+        );
+        assert_ne!(
+            derive_forge_state_pda(&original).0,
+            derive_forge_state_pda(&partner).0
+        );
+
+        let orb_mint = Pubkey::new_unique();
+        assert_eq!(
+            derive_claim_pda(&original, &orb_mint).0,
+            Pubkey::find_program_address(&[b"claim", orb_mint.as_ref()], &crate::ID).0
+        );
+        assert_ne!(
+            derive_claim_pda(&original, &orb_mint).0,
+            derive_claim_pda(&partner, &orb_mint).0
+        );
+        assert_ne!(
+            derive_chain_config_pda(&original, 8453).0,
+            derive_chain_config_pda(&partner, 8453).0
+        );
+
+        let forge_state = ForgeState {
+            namespace: partner,
+            ..Default::default()
+        };
+        assert_ne!(
+            forge_state.emitter_address(),
+            ForgeState::default().emitter_address()
+        );
+    }
+
+    #[test]
+    fn same_chain_feeds_can_skip_the_sol_fee() {
+        let mut forge_state = ForgeState {
+            sol_fee_lamports: 5_000,
+            charge_sol_fee_same_chain: true,
+            ..Default::default()
+        };
+        assert_eq!(forge_state.sol_fee_for(SOLANA_CHAIN_ID), 5_000);
+        assert_eq!(forge_state.sol_fee_for(8453), 5_000);
+
+        forge_state.charge_sol_fee_same_chain = false;
+        assert_eq!(forge_state.sol_fee_for(SOLANA_CHAIN_ID), 0);
+        assert_eq!(forge_state.sol_fee_for(8453), 5_000);
+    }
+
+    #[test]
+    fn wormhole_fee_must_top_up_the_last_recorded_balance() {
+        assert!(wormhole_fee_paid(1_100, 1_000, 100));
+        assert!(wormhole_fee_paid(1_200, 1_000, 100));
+        assert!(!wormhole_fee_paid(1_099, 1_000, 100));
+        assert!(wormhole_fee_paid(1_000, 1_000, 0));
+        assert!(!wormhole_fee_paid(u64::MAX, u64::MAX, 1));
+    }
+
+    #[test]
+    fn sweeps_spare_the_treasury_and_escrow() {
+        let forge_state = ForgeState {
+            treasury: Pubkey::new_unique(),
+            namespace: [3; 16],
+            ..Default::default()
+        };
+        let mint = Pubkey::new_unique();
+        let (escrow, _) = derive_escrow_pda(&forge_state.namespace, &mint);
+        assert!(forge_state.protects_token_account(&forge_state.treasury, &mint));
+        assert!(forge_state.protects_token_account(&escrow, &mint));
+        assert!(!forge_state.protects_token_account(&Pubkey::new_unique(), &mint));
+        // Another forge's escrow is not this forge's to protect
+        let (other_escrow, _) = derive_escrow_pda(&[0; 16], &mint);
+        assert!(!forge_state.protects_token_account(&other_escrow, &mint));
+    }
+
+    #[test]
+    fn clock_skew_tolerance_applies_to_every_window() {
+        let mut forge_state = ForgeState {
+            start_ts: 100,
+            end_ts: 200,
+            clock_skew_tolerance: 5,
+            ..Default::default()
+        };
+        assert!(forge_state.is_after(100, 95));
+        assert!(!forge_state.is_after(100, 94));
+        assert!(forge_state.is_after(i64::MAX, i64::MAX));
+
+        // The window opens and closes 5 seconds early
+        forge_state.require_open(95).unwrap();
+        forge_state.require_open(195).unwrap();
+        assert_eq!(
+            forge_state.require_open(196).unwrap_err(),
+            ErrorCode::ForgeClosed.into()
+        );
+
+        forge_state.user_cooldown_seconds = 60;
+        let mut feeder_stats = FeederStats::default();
+        feeder_stats.record(&forge_state, 1_000).unwrap();
+        assert!(feeder_stats.cooling_down(&forge_state, 1_054));
+        assert!(!feeder_stats.cooling_down(&forge_state, 1_055));
+
+        forge_state.halt(1_000).unwrap();
+        forge_state
+            .clear_halt(1_000 + EMERGENCY_HALT_TIMELOCK - 5)
+            .unwrap();
+    }
+
+    #[test]
+    fn orb_burn_replaces_the_threshold_only_when_set() {
+        let mut forge_state = ForgeState {
+            orb_burn_replaces_rari: true,
+            ..Default::default()
+        };
+        assert!(!forge_state.orb_burn_replaces_threshold());
+        forge_state.orb_burn_amount = 3;
+        assert!(forge_state.orb_burn_replaces_threshold());
+        forge_state.orb_burn_replaces_rari = false;
+        assert!(!forge_state.orb_burn_replaces_threshold());
+    }
+
+    #[test]
+    fn event_seq_advances_by_one_and_never_wraps() {
+        let mut forge_state = ForgeState::default();
+        assert_eq!(forge_state.next_event_seq().unwrap(), 1);
+        assert_eq!(forge_state.next_event_seq().unwrap(), 2);
+        assert_eq!(forge_state.event_seq, 2);
+
+        forge_state.event_seq = u64::MAX;
+        assert_eq!(
+            forge_state.next_event_seq().unwrap_err(),
+            ErrorCode::MathOverflow.into()
+        );
+    }
+
+    #[test]
+    fn partner_share_is_a_bps_cut_of_the_threshold() {
+        let mut forge_state = ForgeState {
+            rari_threshold: 1_000,
+            partner_bps: 2_500,
+            ..Default::default()
+        };
+        assert_eq!(forge_state.partner_share(), 0);
+        forge_state.partner_reward_mint = Pubkey::new_unique();
+        assert_eq!(forge_state.partner_share(), 250);
+        forge_state.partner_bps = MAX_BPS;
+        assert_eq!(forge_state.partner_share(), 1_000);
+    }
+
+    #[test]
+    fn zero_claim_cap_is_unlimited() {
+        let mut forge_state = ForgeState {
+            total_claimed: 10,
+            ..Default::default()
+        };
+        forge_state.record_feed(0).unwrap();
+        assert_eq!(forge_state.total_claimed, 11);
+    }
+}
@@ -6,201 +6,655 @@ import {
   Keypair, 
   LAMPORTS_PER_SOL,
   SystemProgram,
-  SYSVAR_RENT_PUBKEY 
+  SYSVAR_CLOCK_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  SYSVAR_RENT_PUBKEY,
+  Ed25519Program,
+  Transaction,
 } from "@solana/web3.js";
 import { 
   TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  ExtensionType,
   createMint,
   createAccount,
+  createInitializeMintInstruction,
+  createInitializeNonTransferableMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createInitializeTransferHookInstruction,
+  getExtraAccountMetaAddress,
+  getMintLen,
   mintTo,
   getAccount,
+  getAssociatedTokenAddressSync,
+  createApproveInstruction,
+  NATIVE_MINT,
 } from "@solana/spl-token";
+import { Metaplex, keypairIdentity, token } from "@metaplex-foundation/js";
+import {
+  deriveWormholeBridgeDataKey,
+  deriveFeeCollectorKey,
+  deriveEmitterSequenceKey,
+  getPostedMessage,
+  getSequenceTracker,
+} from "@certusone/wormhole-sdk/lib/cjs/solana/wormhole";
+import { keccak_256 } from "js-sha3";
+import { createHash } from "crypto";
 import { expect } from "chai";
 
+const WORMHOLE_PROGRAM_ID = new PublicKey("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+
+// SPL noop program, cloned for Bubblegum; it accepts any instruction, so it
+// stands in as a mock transfer-hook program
+const NOOP_PROGRAM_ID = new PublicKey("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+// Set RARI_TOKEN_PROGRAM=token-2022 to run the suite against a Token-2022 RARI
+// mint with a transfer-fee extension instead of a classic SPL mint.
+const RARI_TOKEN_2022 = process.env.RARI_TOKEN_PROGRAM === "token-2022";
+const RARI_TOKEN_PROGRAM_ID = RARI_TOKEN_2022 ? TOKEN_2022_PROGRAM_ID : TOKEN_PROGRAM_ID;
+const RARI_TRANSFER_FEE_BPS = RARI_TOKEN_2022 ? 100 : 0;
+const RARI_MAX_TRANSFER_FEE = BigInt(1_000 * LAMPORTS_PER_SOL);
+
+// Fee withheld by Token-2022 on a transfer of `amount` RARI
+const rariTransferFee = (amount: bigint) => {
+  const fee = (amount * BigInt(RARI_TRANSFER_FEE_BPS) + BigInt(9_999)) / BigInt(10_000);
+  return fee > RARI_MAX_TRANSFER_FEE ? RARI_MAX_TRANSFER_FEE : fee;
+};
+
+// ForgeState.pause_flags bits
+const PAUSE_FEED = 1 << 0;
+const PAUSE_CROSS_CHAIN_FEED = 1 << 1;
+
+// CRC-32 (IEEE), as carried at the end of OrbFedCompact
+const crc32 = (bytes: Buffer) => {
+  let crc = ~0 >>> 0;
+  for (const byte of bytes) {
+    crc ^= byte;
+    for (let bit = 0; bit < 8; bit++) {
+      crc = crc & 1 ? (crc >>> 1) ^ 0xedb88320 : crc >>> 1;
+    }
+  }
+  return ~crc >>> 0;
+};
+
+// `max_burn` that disables feed_orb's slippage guard
+const NO_MAX_BURN = new anchor.BN("18446744073709551615");
+
+// The all-zero namespace keeps the original single-forge PDAs
+const DEFAULT_NAMESPACE = new Array(16).fill(0);
+
+const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+type Orb = { orbMint: PublicKey; orbMetadata: PublicKey };
+
 describe("orb_forge", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
 
   const program = anchor.workspace.OrbForge as Program<OrbForge>;
   const authority = provider.wallet.publicKey;
+  const metaplex = Metaplex.make(provider.connection).use(
+    keypairIdentity(provider.wallet.payer)
+  );
   
   let rariMint: PublicKey;
-  let orbMint: PublicKey;
+  let orbCollection: PublicKey;
   let userRariAccount: PublicKey;
+  let treasuryTokenAccount: PublicKey;
   let forgeState: PublicKey;
+  let pendingQueue: PublicKey;
 
-  before(async () => {
-    // Create RARI token mint
-    rariMint = await createMint(
-      provider.connection,
-      provider.wallet.payer,
-      authority,
-      null,
-      9 // 9 decimals for RARI
+  const wormholeBridge = deriveWormholeBridgeDataKey(WORMHOLE_PROGRAM_ID);
+  // Version 0 is the original `[b"emitter"]` emitter; `rotate_emitter`
+  // suffixes later versions to the seeds
+  const emitterFor = (emitterSeedVersion: number) =>
+    PublicKey.findProgramAddressSync(
+      emitterSeedVersion === 0
+        ? [Buffer.from("emitter")]
+        : [Buffer.from("emitter"), Buffer.from([emitterSeedVersion])],
+      program.programId
+    )[0];
+
+  // Accounts required by feed_orb for posting through the Wormhole core bridge.
+  // The message PDA is seeded by the current emitter's sequence.
+  const wormholeAccounts = async () => {
+    const { emitterSeedVersion } = await program.account.forgeState.fetch(forgeState);
+    const wormholeEmitter = emitterFor(emitterSeedVersion);
+    let sequence = BigInt(0);
+    try {
+      const tracker = await getSequenceTracker(
+        provider.connection,
+        wormholeEmitter,
+        WORMHOLE_PROGRAM_ID
+      );
+      sequence = tracker.sequence;
+    } catch {
+      // Sequence tracker is created by the core bridge on the first post
+    }
+    const sequenceBytes = Buffer.alloc(8);
+    sequenceBytes.writeBigUInt64LE(sequence);
+    const [wormholeMessage] = PublicKey.findProgramAddressSync(
+      [Buffer.from("sent"), sequenceBytes],
+      program.programId
     );
 
-    // Create Orb NFT mint
-    orbMint = await createMint(
-      provider.connection,
-      provider.wallet.payer,
-      authority,
-      null,
-      0 // NFT has 0 decimals
+    return {
+      wormholeBridge,
+      wormholeFeeCollector: deriveFeeCollectorKey(WORMHOLE_PROGRAM_ID),
+      wormholeEmitter,
+      wormholeSequence: deriveEmitterSequenceKey(wormholeEmitter, WORMHOLE_PROGRAM_ID),
+      wormholeMessage,
+      wormholeProgram: WORMHOLE_PROGRAM_ID,
+      clock: SYSVAR_CLOCK_PUBKEY,
+      rent: SYSVAR_RENT_PUBKEY,
+    };
+  };
+
+  // Mints an Orb NFT through Metaplex, verified into `collection`
+  const createOrb = async (
+    collection: PublicKey = orbCollection,
+    tokenOwner: PublicKey = authority
+  ): Promise<Orb> => {
+    const { nft } = await metaplex.nfts().create({
+      name: "Orb",
+      uri: "https://arweave.net/orb.json",
+      sellerFeeBasisPoints: 0,
+      collection,
+      collectionAuthority: provider.wallet.payer,
+      tokenOwner,
+    });
+    return { orbMint: nft.address, orbMetadata: nft.metadataAddress };
+  };
+
+  const claimRecordFor = (orbMint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("claim"), orbMint.toBuffer()],
+      program.programId
+    )[0];
+
+  const feederStatsFor = (user: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("feeder"), user.toBuffer()],
+      program.programId
+    )[0];
+
+  const pointsAccountFor = (user: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("points"), user.toBuffer()],
+      program.programId
+    )[0];
+
+  const denyEntryFor = (address: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("deny"), address.toBuffer()],
+      program.programId
+    )[0];
+
+  const chainConfigFor = (chainId: number) => {
+    const chainIdBytes = Buffer.alloc(2);
+    chainIdBytes.writeUInt16LE(chainId);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("chain"), chainIdBytes],
+      program.programId
+    )[0];
+  };
+
+  const chainCounterFor = (chainId: number) => {
+    const chainIdBytes = Buffer.alloc(2);
+    chainIdBytes.writeUInt16LE(chainId);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("chain_counter"), chainIdBytes],
+      program.programId
+    )[0];
+  };
+
+  // Ed25519 instruction carrying the backend's `user || orb_mint || expiry || rarity_tier`
+  const feedAuthorization = (orb: Orb, signer: Keypair, expiry: number, rarityTier = 0) => {
+    const expiryBytes = Buffer.alloc(8);
+    expiryBytes.writeBigInt64LE(BigInt(expiry));
+    return Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: signer.secretKey,
+      message: Buffer.concat([
+        authority.toBuffer(),
+        orb.orbMint.toBuffer(),
+        expiryBytes,
+        Buffer.from([rarityTier]),
+      ]),
+    });
+  };
+
+  const createRariMint = async () => {
+    if (!RARI_TOKEN_2022) {
+      return createMint(provider.connection, provider.wallet.payer, authority, null, 9);
+    }
+
+    const mint = Keypair.generate();
+    const space = getMintLen([ExtensionType.TransferFeeConfig]);
+    const lamports = await provider.connection.getMinimumBalanceForRentExemption(space);
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: authority,
+          newAccountPubkey: mint.publicKey,
+          space,
+          lamports,
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+          mint.publicKey,
+          authority,
+          authority,
+          RARI_TRANSFER_FEE_BPS,
+          RARI_MAX_TRANSFER_FEE,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(mint.publicKey, 9, authority, null, TOKEN_2022_PROGRAM_ID)
+      ),
+      [mint]
     );
+    return mint.publicKey;
+  };
 
-    // Create user RARI token account
-    userRariAccount = await createAccount(
+  const createRariAccount = (owner: PublicKey, keypair?: Keypair) =>
+    createAccount(
       provider.connection,
       provider.wallet.payer,
       rariMint,
-      authority
+      owner,
+      keypair,
+      undefined,
+      RARI_TOKEN_PROGRAM_ID
     );
 
-    // Mint RARI tokens to user
-    await mintTo(
+  const mintRari = (account: PublicKey, amount: number) =>
+    mintTo(
       provider.connection,
       provider.wallet.payer,
       rariMint,
-      userRariAccount,
+      account,
       authority,
-      1000 * LAMPORTS_PER_SOL // 1000 RARI tokens
+      amount,
+      [],
+      undefined,
+      RARI_TOKEN_PROGRAM_ID
+    );
+
+  const getRariAccount = (account: PublicKey) =>
+    getAccount(provider.connection, account, undefined, RARI_TOKEN_PROGRAM_ID);
+
+  // Creates a funded wallet holding `rari` RARI tokens
+  const createFeeder = async (rari: number) => {
+    const wallet = Keypair.generate();
+    const signature = await provider.connection.requestAirdrop(
+      wallet.publicKey,
+      10 * LAMPORTS_PER_SOL
     );
+    await provider.connection.confirmTransaction(signature);
+
+    const rariAccount = await createRariAccount(wallet.publicKey);
+    await mintRari(rariAccount, rari * LAMPORTS_PER_SOL);
+    return { wallet, rariAccount };
+  };
+
+  // Full account set for feed_orb; tests override what they exercise
+  const feedAccounts = async (
+    orb: Orb,
+    overrides: Record<string, PublicKey | null> = {},
+    chainId = 1
+  ) => ({
+    forgeState,
+    pendingQueue,
+    claimRecord: claimRecordFor(orb.orbMint),
+    feederStats: feederStatsFor(overrides.user ?? authority),
+    orbMint: orb.orbMint,
+    orbMetadata: orb.orbMetadata,
+    chainConfig: chainConfigFor(chainId),
+    chainCounter: chainCounterFor(chainId),
+    denyEntry: denyEntryFor(overrides.user ?? authority),
+    rariMint,
+    paymentMintConfig: null,
+    escrowTokenAccount: null,
+    relayerFeeVault: null,
+    orbTokenAccount: getAssociatedTokenAddressSync(orb.orbMint, overrides.user ?? authority),
+    orbEdition: null,
+    orbFreezeAuthority: null,
+    orbTokenProgram: null,
+    tokenMetadataProgram: null,
+    receiptMint: null,
+    userReceiptAccount: null,
+    receiptTokenProgram: null,
+    earlyBonusMint: null,
+    claimerBonusAccount: null,
+    bonusTokenProgram: null,
+    partnerRewardMint: null,
+    partnerRewardAccount: null,
+    partnerTokenProgram: null,
+    pointsAccount: null,
+    instructionsSysvar: null,
+    govMetadata: null,
+    govTokenAccount: null,
+    delegate: null,
+    eventSinkProgram: null,
+    userRariAccount,
+    treasuryTokenAccount,
+    user: authority,
+    payer: overrides.payer ?? overrides.user ?? authority,
+    feeRecipient: authority,
+    tokenProgram: RARI_TOKEN_PROGRAM_ID,
+    systemProgram: SystemProgram.programId,
+    ...(await wormholeAccounts()),
+    ...overrides,
+  });
+
+  before(async () => {
+    // Create RARI token mint (9 decimals)
+    rariMint = await createRariMint();
+
+    // Create the approved Orb collection
+    const { nft: collectionNft } = await metaplex.nfts().create({
+      name: "Orbs",
+      uri: "https://arweave.net/orbs.json",
+      sellerFeeBasisPoints: 0,
+      isCollection: true,
+    });
+    orbCollection = collectionNft.address;
+
+    // Create user RARI token account
+    userRariAccount = await createRariAccount(authority);
+
+    // Mint RARI tokens to user
+    await mintRari(userRariAccount, 1000 * LAMPORTS_PER_SOL); // 1000 RARI tokens
 
     // Derive forge state PDA
     [forgeState] = PublicKey.findProgramAddressSync(
       [Buffer.from("forge_state")],
       program.programId
     );
+    [pendingQueue] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pending_queue")],
+      program.programId
+    );
+
+    // Treasury token account owned by the forge PDA
+    treasuryTokenAccount = await createRariAccount(forgeState, Keypair.generate());
+  });
+
+  it("Rejects feeds before the forge is initialized", async () => {
+    try {
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb()))
+        .rpc();
+      expect.fail("Should have failed before initialize");
+    } catch (error) {
+      // A missing forge state surfaces as Anchor's AccountNotInitialized;
+      // an allocated but unset one as NotInitialized
+      expect(error.message).to.include("NotInitialized");
+    }
   });
 
   it("Initializes the forge state", async () => {
-    const wormholeBridge = new PublicKey("Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o");
     const rariThreshold = new anchor.BN(100 * LAMPORTS_PER_SOL); // 100 RARI
 
-    await program.methods
+    const initializeTx = await program.methods
       .initialize({
         wormholeBridge,
         rariMint,
         rariThreshold,
+        approvedCollection: orbCollection,
+        treasury: treasuryTokenAccount,
+        burnBps: 10000,
+        requiredCreator: PublicKey.default,
+        allowFreeFeed: false,
+        startTs: new anchor.BN(0),
+        endTs: new anchor.BN(0),
+        pendingQueueSize: 64,
+        namespace: DEFAULT_NAMESPACE,
       })
       .accounts({
         forgeState,
+        pendingQueue,
+        rariMint,
         authority,
         systemProgram: SystemProgram.programId,
       })
-      .rpc();
+      .rpc({ commitment: "confirmed" });
+
+    // Indexers discover the event schema from the deploy-time initialize
+    const initializeTxInfo = await provider.connection.getTransaction(initializeTx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const eventParser = new anchor.EventParser(program.programId, program.coder);
+    const schemaEvents = [...eventParser.parseLogs(initializeTxInfo.meta.logMessages)].filter(
+      (event) => event.name === "schemaVersionEvent"
+    );
+    expect(schemaEvents).to.have.length(1);
+    expect(schemaEvents[0].data.schemaVersion).to.equal(12);
 
     const forgeStateAccount = await program.account.forgeState.fetch(forgeState);
     
     expect(forgeStateAccount.authority.toBase58()).to.equal(authority.toBase58());
     expect(forgeStateAccount.rariMint.toBase58()).to.equal(rariMint.toBase58());
     expect(forgeStateAccount.rariThreshold.toNumber()).to.equal(rariThreshold.toNumber());
+    expect(forgeStateAccount.approvedCollection.toBase58()).to.equal(orbCollection.toBase58());
     expect(forgeStateAccount.totalClaimed.toNumber()).to.equal(0);
-    expect(forgeStateAccount.paused).to.be.false;
-  });
+    expect(forgeStateAccount.totalRariBurned.toNumber()).to.equal(0);
+    expect(forgeStateAccount.pauseFlags).to.equal(0);
+    expect(forgeStateAccount.isInitialized).to.be.true;
+    expect(forgeStateAccount.rariDecimals).to.equal(9);
 
-  it("Feeds an orb for same-chain minting", async () => {
-    const chainId = 1; // Solana chain ID
-    
-    // Derive claim record PDA
-    const [claimRecord] = PublicKey.findProgramAddressSync(
-      [Buffer.from("claim"), orbMint.toBuffer()],
+    try {
+      await program.methods
+        .initialize({
+          wormholeBridge,
+          rariMint,
+          rariThreshold,
+          approvedCollection: orbCollection,
+          treasury: treasuryTokenAccount,
+          burnBps: 10000,
+          requiredCreator: PublicKey.default,
+          allowFreeFeed: false,
+          startTs: new anchor.BN(0),
+          endTs: new anchor.BN(0),
+          pendingQueueSize: 64,
+          namespace: DEFAULT_NAMESPACE,
+        })
+        .accounts({
+          forgeState,
+          pendingQueue,
+          rariMint,
+          authority,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      expect.fail("Should have rejected a second initialize");
+    } catch (error) {
+      expect(error.message).to.include("AlreadyInitialized");
+    }
+
+    // A namespaced forge is guarded the same way
+    const namespace = [...Buffer.from("reinit-forge0001")];
+    const [namespacedForgeState] = PublicKey.findProgramAddressSync(
+      [Buffer.from("forge_state"), Buffer.from(namespace)],
       program.programId
     );
-
-    // Mock Orb metadata account (in production, this would be from Metaplex)
-    const [orbMetadata] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("metadata"),
-        new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s").toBuffer(),
-        orbMint.toBuffer(),
-      ],
-      new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s")
+    const [namespacedPendingQueue] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pending_queue"), Buffer.from(namespace)],
+      program.programId
     );
+    const initializeNamespaced = () =>
+      program.methods
+        .initialize({
+          wormholeBridge,
+          rariMint,
+          rariThreshold,
+          approvedCollection: orbCollection,
+          treasury: treasuryTokenAccount,
+          burnBps: 10000,
+          requiredCreator: PublicKey.default,
+          allowFreeFeed: false,
+          startTs: new anchor.BN(0),
+          endTs: new anchor.BN(0),
+          pendingQueueSize: 8,
+          namespace,
+        })
+        .accounts({
+          forgeState: namespacedForgeState,
+          pendingQueue: namespacedPendingQueue,
+          rariMint,
+          authority,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    await initializeNamespaced();
+    try {
+      await initializeNamespaced();
+      expect.fail("Should have rejected a second initialize of a namespaced forge");
+    } catch (error) {
+      expect(error.message).to.include("AlreadyInitialized");
+    }
+  });
+
+  it("Feeds an orb for same-chain minting", async () => {
+    const chainId = 1; // Solana chain ID
+    const orb = await createOrb();
+    const claimRecord = claimRecordFor(orb.orbMint);
 
     // Get initial RARI balance
-    const initialBalance = await getAccount(provider.connection, userRariAccount);
+    const initialBalance = await getRariAccount(userRariAccount);
     
     await program.methods
-      .feedOrb(chainId)
-      .accounts({
-        forgeState,
-        claimRecord,
-        orbMint,
-        orbMetadata,
-        rariMint,
-        userRariAccount,
-        user: authority,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        systemProgram: SystemProgram.programId,
-      })
+      .feedOrb(chainId, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(orb))
       .rpc();
 
     // Verify claim record was created
     const claimRecordAccount = await program.account.claimRecord.fetch(claimRecord);
-    expect(claimRecordAccount.orbMint.toBase58()).to.equal(orbMint.toBase58());
+    expect(claimRecordAccount.orbMint.toBase58()).to.equal(orb.orbMint.toBase58());
     expect(claimRecordAccount.claimer.toBase58()).to.equal(authority.toBase58());
     expect(claimRecordAccount.targetChain).to.equal(chainId);
+    expect(claimRecordAccount.wormholeSequence).to.be.null;
+    // Hashed without Metaplex's trailing null padding
+    expect(Buffer.from(claimRecordAccount.nameHash)).to.deep.equal(
+      Buffer.from(keccak_256.arrayBuffer("Orb"))
+    );
 
     // Verify RARI tokens were burned
-    const finalBalance = await getAccount(provider.connection, userRariAccount);
+    const finalBalance = await getRariAccount(userRariAccount);
     const burnedAmount = Number(initialBalance.amount) - Number(finalBalance.amount);
     expect(burnedAmount).to.equal(100 * LAMPORTS_PER_SOL);
 
     // Verify forge state was updated
     const forgeStateAccount = await program.account.forgeState.fetch(forgeState);
     expect(forgeStateAccount.totalClaimed.toNumber()).to.equal(1);
+    expect(forgeStateAccount.totalRariBurned.toNumber()).to.equal(100 * LAMPORTS_PER_SOL);
   });
 
   it("Feeds an orb for cross-chain minting", async () => {
     const chainId = 8453; // Base chain ID
     
     // Create another orb for testing
-    const orbMint2 = await createMint(
-      provider.connection,
-      provider.wallet.payer,
-      authority,
-      null,
-      0
-    );
-
-    const [claimRecord2] = PublicKey.findProgramAddressSync(
-      [Buffer.from("claim"), orbMint2.toBuffer()],
-      program.programId
-    );
-
-    const [orbMetadata2] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("metadata"),
-        new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s").toBuffer(),
-        orbMint2.toBuffer(),
-      ],
-      new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s")
-    );
+    const orb = await createOrb();
+    const accounts = await feedAccounts(orb, {}, chainId);
 
     await program.methods
-      .feedOrb(chainId)
-      .accounts({
-        forgeState,
-        claimRecord: claimRecord2,
-        orbMint: orbMint2,
-        orbMetadata: orbMetadata2,
-        rariMint,
-        userRariAccount,
-        user: authority,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        systemProgram: SystemProgram.programId,
-      })
+      .feedOrb(chainId, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(accounts)
       .rpc();
 
-    const claimRecordAccount = await program.account.claimRecord.fetch(claimRecord2);
+    const claimRecordAccount = await program.account.claimRecord.fetch(accounts.claimRecord);
     expect(claimRecordAccount.targetChain).to.equal(chainId);
 
+    // Verify the Wormhole message carries the Borsh-encoded feed payload
+    const expectedPayload = Buffer.alloc(32 + 32 + 2 + 8);
+    orb.orbMint.toBuffer().copy(expectedPayload, 0);
+    authority.toBuffer().copy(expectedPayload, 32);
+    expectedPayload.writeUInt16LE(chainId, 64);
+    expectedPayload.writeBigUInt64LE(BigInt(100 * LAMPORTS_PER_SOL), 66);
+
+    const posted = await getPostedMessage(provider.connection, accounts.wormholeMessage);
+    expect(posted.message.emitterAddress.equals(accounts.wormholeEmitter.toBuffer())).to.be.true;
+    expect(posted.message.payload.equals(expectedPayload)).to.be.true;
+
+    // The claim record carries the sequence relayers need to fetch the VAA
+    expect(claimRecordAccount.wormholeSequence.toString()).to.equal(
+      posted.message.sequence.toString()
+    );
+
     // Verify total claimed count increased
     const forgeStateAccount = await program.account.forgeState.fetch(forgeState);
     expect(forgeStateAccount.totalClaimed.toNumber()).to.equal(2);
   });
 
+  it("Fails when the orb is not in the approved collection", async () => {
+    const { nft: otherCollection } = await metaplex.nfts().create({
+      name: "Not Orbs",
+      uri: "https://arweave.net/other.json",
+      sellerFeeBasisPoints: 0,
+      isCollection: true,
+    });
+    const orb = await createOrb(otherCollection.address);
+
+    try {
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(orb))
+        .rpc();
+
+      expect.fail("Should have failed for an orb outside the collection");
+    } catch (error) {
+      expect(error.message).to.include("OrbNotInCollection");
+    }
+  });
+
+  it("Rejects metadata that is not the Orb's canonical Metaplex account", async () => {
+    const orb = await createOrb();
+    const otherOrb = await createOrb();
+
+    // Not owned by Token Metadata, and a genuine metadata account of another Orb
+    for (const orbMetadata of [forgeState, otherOrb.orbMetadata]) {
+      try {
+        await program.methods
+          .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+          .accounts(await feedAccounts({ ...orb, orbMetadata }))
+          .rpc();
+        expect.fail("Should have rejected a spoofed metadata account");
+      } catch (error) {
+        expect(error.message).to.include("InvalidOrbMetadata");
+      }
+    }
+  });
+
+  it("Rejects feeding an Orb held by another wallet", async () => {
+    const feeder = await createFeeder(1000);
+    const orb = await createOrb();
+
+    // The Orb sits in the authority's account, not the feeder's
+    for (const orbTokenAccount of [
+      getAssociatedTokenAddressSync(orb.orbMint, feeder.wallet.publicKey),
+      getAssociatedTokenAddressSync(orb.orbMint, authority),
+    ]) {
+      try {
+        await program.methods
+          .feedOrb(1, [], NO_MAX_BURN, 0, feeder.wallet.publicKey, null)
+          .accounts(
+            await feedAccounts(orb, {
+              userRariAccount: feeder.rariAccount,
+              user: feeder.wallet.publicKey,
+              orbTokenAccount,
+            })
+          )
+          .signers([feeder.wallet])
+          .rpc();
+        expect.fail("Should have rejected an Orb the feeder doesn't hold");
+      } catch (error) {
+        expect(error.message).to.match(/OrbNotOwned|AccountNotInitialized/);
+      }
+    }
+    expect(await provider.connection.getAccountInfo(claimRecordFor(orb.orbMint))).to.be.null;
+  });
+
   it("Fails when user has insufficient RARI balance", async () => {
     // Create a user with insufficient RARI
     const poorUser = Keypair.generate();
@@ -208,65 +662,28 @@ describe("orb_forge", () => {
     // Airdrop SOL for transaction fees
     await provider.connection.requestAirdrop(poorUser.publicKey, LAMPORTS_PER_SOL);
     
-    const poorUserRariAccount = await createAccount(
-      provider.connection,
-      provider.wallet.payer,
-      rariMint,
-      poorUser.publicKey
-    );
+    const poorUserRariAccount = await createRariAccount(poorUser.publicKey);
 
     // Mint only 50 RARI (less than threshold of 100)
-    await mintTo(
-      provider.connection,
-      provider.wallet.payer,
-      rariMint,
-      poorUserRariAccount,
-      authority,
-      50 * LAMPORTS_PER_SOL
-    );
-
-    const orbMint3 = await createMint(
-      provider.connection,
-      provider.wallet.payer,
-      authority,
-      null,
-      0
-    );
-
-    const [claimRecord3] = PublicKey.findProgramAddressSync(
-      [Buffer.from("claim"), orbMint3.toBuffer()],
-      program.programId
-    );
+    await mintRari(poorUserRariAccount, 50 * LAMPORTS_PER_SOL);
 
-    const [orbMetadata3] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("metadata"),
-        new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s").toBuffer(),
-        orbMint3.toBuffer(),
-      ],
-      new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s")
-    );
+    const orb = await createOrb(orbCollection, poorUser.publicKey);
 
     try {
       await program.methods
-        .feedOrb(1)
-        .accounts({
-          forgeState,
-          claimRecord: claimRecord3,
-          orbMint: orbMint3,
-          orbMetadata: orbMetadata3,
-          rariMint,
-          userRariAccount: poorUserRariAccount,
-          user: poorUser.publicKey,
-          tokenProgram: TOKEN_PROGRAM_ID,
-          systemProgram: SystemProgram.programId,
-        })
+        .feedOrb(1, [], NO_MAX_BURN, 0, poorUser.publicKey, null)
+        .accounts(
+          await feedAccounts(orb, {
+            userRariAccount: poorUserRariAccount,
+            user: poorUser.publicKey,
+          })
+        )
         .signers([poorUser])
         .rpc();
       
       expect.fail("Should have failed with insufficient balance");
     } catch (error) {
-      expect(error.message).to.include("insufficient");
+      expect(error.message).to.include("InsufficientRariBalance");
     }
   });
 
@@ -275,46 +692,20 @@ describe("orb_forge", () => {
       .togglePause()
       .accounts({
         forgeState,
-        authority,
+        signer: authority,
       })
       .rpc();
 
     let forgeStateAccount = await program.account.forgeState.fetch(forgeState);
-    expect(forgeStateAccount.paused).to.be.true;
+    expect(forgeStateAccount.pauseFlags).to.equal(PAUSE_FEED);
 
     // Try to feed orb while paused (should fail)
-    const orbMint4 = await createMint(
-      provider.connection,
-      provider.wallet.payer,
-      authority,
-      null,
-      0
-    );
+    const orb = await createOrb();
 
     try {
       await program.methods
-        .feedOrb(1)
-        .accounts({
-          forgeState,
-          claimRecord: PublicKey.findProgramAddressSync(
-            [Buffer.from("claim"), orbMint4.toBuffer()],
-            program.programId
-          )[0],
-          orbMint: orbMint4,
-          orbMetadata: PublicKey.findProgramAddressSync(
-            [
-              Buffer.from("metadata"),
-              new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s").toBuffer(),
-              orbMint4.toBuffer(),
-            ],
-            new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s")
-          )[0],
-          rariMint,
-          userRariAccount,
-          user: authority,
-          tokenProgram: TOKEN_PROGRAM_ID,
-          systemProgram: SystemProgram.programId,
-        })
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(orb))
         .rpc();
       
       expect.fail("Should have failed while paused");
@@ -327,12 +718,12 @@ describe("orb_forge", () => {
       .togglePause()
       .accounts({
         forgeState,
-        authority,
+        signer: authority,
       })
       .rpc();
 
     forgeStateAccount = await program.account.forgeState.fetch(forgeState);
-    expect(forgeStateAccount.paused).to.be.false;
+    expect(forgeStateAccount.pauseFlags).to.equal(0);
   });
 
   it("Allows authority to update threshold", async () => {
@@ -342,12 +733,24 @@ describe("orb_forge", () => {
       .updateThreshold(newThreshold)
       .accounts({
         forgeState,
+        rariMint,
         authority,
       })
       .rpc();
 
     const forgeStateAccount = await program.account.forgeState.fetch(forgeState);
     expect(forgeStateAccount.rariThreshold.toNumber()).to.equal(newThreshold.toNumber());
+
+    // The decimals check reads the forge's own RARI mint
+    try {
+      await program.methods
+        .updateThreshold(newThreshold)
+        .accounts({ forgeState, rariMint: await createRariMint(), authority })
+        .rpc();
+      expect.fail("Should have rejected another mint");
+    } catch (error) {
+      expect(error.message).to.include("InvalidRariMint");
+    }
   });
 
   it("Fails when non-authority tries to pause", async () => {
@@ -358,14 +761,4323 @@ describe("orb_forge", () => {
         .togglePause()
         .accounts({
           forgeState,
-          authority: nonAuthority.publicKey,
+          signer: nonAuthority.publicKey,
         })
         .signers([nonAuthority])
         .rpc();
       
       expect.fail("Should have failed with unauthorized access");
     } catch (error) {
-      expect(error.message).to.include("constraint");
+      expect(error.message).to.include("UnauthorizedOperator");
+    }
+  });
+
+  it("Hands off authority in two steps", async () => {
+    const newAuthority = Keypair.generate();
+
+    await program.methods
+      .transferAuthority(newAuthority.publicKey)
+      .accounts({ forgeState, authority })
+      .rpc();
+
+    let forgeStateAccount = await program.account.forgeState.fetch(forgeState);
+    expect(forgeStateAccount.pendingAuthority.toBase58()).to.equal(
+      newAuthority.publicKey.toBase58()
+    );
+
+    // Only the pending key may accept
+    const impostor = Keypair.generate();
+    try {
+      await program.methods
+        .acceptAuthority()
+        .accounts({ forgeState, pendingAuthority: impostor.publicKey })
+        .signers([impostor])
+        .rpc();
+      expect.fail("Should have failed for a non-pending signer");
+    } catch (error) {
+      expect(error.message).to.include("UnauthorizedPendingAuthority");
+    }
+
+    await program.methods
+      .acceptAuthority()
+      .accounts({ forgeState, pendingAuthority: newAuthority.publicKey })
+      .signers([newAuthority])
+      .rpc();
+
+    forgeStateAccount = await program.account.forgeState.fetch(forgeState);
+    expect(forgeStateAccount.authority.toBase58()).to.equal(newAuthority.publicKey.toBase58());
+    expect(forgeStateAccount.pendingAuthority).to.be.null;
+
+    // Hand authority back, then confirm a pending transfer can be cancelled
+    await program.methods
+      .transferAuthority(authority)
+      .accounts({ forgeState, authority: newAuthority.publicKey })
+      .signers([newAuthority])
+      .rpc();
+    await program.methods
+      .acceptAuthority()
+      .accounts({ forgeState, pendingAuthority: authority })
+      .rpc();
+
+    await program.methods
+      .transferAuthority(newAuthority.publicKey)
+      .accounts({ forgeState, authority })
+      .rpc();
+    await program.methods
+      .transferAuthority(null)
+      .accounts({ forgeState, authority })
+      .rpc();
+
+    forgeStateAccount = await program.account.forgeState.fetch(forgeState);
+    expect(forgeStateAccount.authority.toBase58()).to.equal(authority.toBase58());
+    expect(forgeStateAccount.pendingAuthority).to.be.null;
+  });
+
+  it("Rate limits feeds per user within a window", async () => {
+    const feeder = await createFeeder(1000);
+
+    await program.methods
+      .updateRateLimit(new anchor.BN(1), new anchor.BN(3600))
+      .accounts({ forgeState, authority })
+      .rpc();
+
+    const feedAs = async (orb: Orb) =>
+      program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, feeder.wallet.publicKey, null)
+        .accounts(
+          await feedAccounts(orb, {
+            userRariAccount: feeder.rariAccount,
+            user: feeder.wallet.publicKey,
+          })
+        )
+        .signers([feeder.wallet])
+        .rpc();
+
+    await feedAs(await createOrb(orbCollection, feeder.wallet.publicKey));
+
+    try {
+      await feedAs(await createOrb(orbCollection, feeder.wallet.publicKey));
+      expect.fail("Should have failed once the window limit was reached");
+    } catch (error) {
+      expect(error.message).to.include("FeedRateLimited");
+    }
+
+    const stats = await program.account.feederStats.fetch(
+      feederStatsFor(feeder.wallet.publicKey)
+    );
+    expect(stats.count.toNumber()).to.equal(1);
+
+    // Zero restores unlimited feeding
+    await program.methods
+      .updateRateLimit(new anchor.BN(0), new anchor.BN(0))
+      .accounts({ forgeState, authority })
+      .rpc();
+    await feedAs(await createOrb());
+  });
+
+  it("Splits the threshold between burn and treasury", async () => {
+    try {
+      await program.methods
+        .updateFeeSplit(10001)
+        .accounts({ forgeState, authority })
+        .rpc();
+      expect.fail("Should have rejected more than 10000 bps");
+    } catch (error) {
+      expect(error.message).to.include("InvalidBps");
+    }
+
+    await program.methods
+      .updateFeeSplit(7500)
+      .accounts({ forgeState, authority })
+      .rpc();
+
+    const { rariThreshold } = await program.account.forgeState.fetch(forgeState);
+    const userBefore = await getRariAccount(userRariAccount);
+    const treasuryBefore = await getRariAccount(treasuryTokenAccount);
+    const supplyBefore = (await provider.connection.getTokenSupply(rariMint)).value.amount;
+
+    await program.methods
+      .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(await createOrb()))
+      .rpc();
+
+    const userAfter = await getRariAccount(userRariAccount);
+    const treasuryAfter = await getRariAccount(treasuryTokenAccount);
+    const supplyAfter = (await provider.connection.getTokenSupply(rariMint)).value.amount;
+
+    // Burns are never subject to transfer fees; the treasury share is
+    // received net of any Token-2022 transfer fee
+    const threshold = BigInt(rariThreshold.toString());
+    const burned = (threshold * BigInt(7500)) / BigInt(10000);
+    const treasuryShare = threshold - burned;
+    expect(userBefore.amount - userAfter.amount).to.equal(threshold);
+    expect(treasuryAfter.amount - treasuryBefore.amount).to.equal(
+      treasuryShare - rariTransferFee(treasuryShare)
+    );
+    expect(BigInt(supplyBefore) - BigInt(supplyAfter)).to.equal(burned);
+
+    await program.methods
+      .updateFeeSplit(10000)
+      .accounts({ forgeState, authority })
+      .rpc();
+  });
+
+  it("Closes a claim record after the close delay", async () => {
+    const orb = await createOrb();
+    const claimRecord = claimRecordFor(orb.orbMint);
+
+    await program.methods
+      .updateCloseDelay(new anchor.BN(3600))
+      .accounts({ forgeState, authority })
+      .rpc();
+
+    const { activeClaims, totalClaimed } = await program.account.forgeState.fetch(forgeState);
+    await program.methods
+      .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(orb))
+      .rpc();
+    const fed = await program.account.forgeState.fetch(forgeState);
+    expect(fed.activeClaims.toString()).to.equal(activeClaims.addn(1).toString());
+
+    try {
+      await program.methods
+        .closeClaim()
+        .accounts({ forgeState, pendingQueue, claimRecord, claimer: authority })
+        .rpc();
+      expect.fail("Should have failed before the close delay elapsed");
+    } catch (error) {
+      expect(error.message).to.include("CloseDelayActive");
+    }
+
+    await program.methods
+      .updateCloseDelay(new anchor.BN(0))
+      .accounts({ forgeState, authority })
+      .rpc();
+    await sleep(2000);
+
+    await program.methods
+      .closeClaim()
+      .accounts({ forgeState, pendingQueue, claimRecord, claimer: authority })
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(claimRecord)).to.be.null;
+    // Closing uncounts the live claim but not the all-time total
+    const closed = await program.account.forgeState.fetch(forgeState);
+    expect(closed.activeClaims.toString()).to.equal(activeClaims.toString());
+    expect(closed.totalClaimed.toString()).to.equal(totalClaimed.addn(1).toString());
+  });
+
+  it("Feeds a batch of orbs in one instruction", async () => {
+    const orbs = await Promise.all([createOrb(), createOrb(), createOrb()]);
+    const { rariThreshold, totalClaimed } = await program.account.forgeState.fetch(forgeState);
+    const supplyBefore = (await provider.connection.getTokenSupply(rariMint)).value.amount;
+
+    const { claimRecord, orbMint, orbMetadata, chainConfig, wormholeMessage, ...shared } =
+      await feedAccounts(orbs[0]);
+    const remainingAccounts = orbs.flatMap((orb) => [
+      { pubkey: orb.orbMint, isSigner: false, isWritable: false },
+      { pubkey: orb.orbMetadata, isSigner: false, isWritable: false },
+      { pubkey: claimRecordFor(orb.orbMint), isSigner: false, isWritable: true },
+      { pubkey: chainConfigFor(1), isSigner: false, isWritable: false },
+      { pubkey: denyEntryFor(authority), isSigner: false, isWritable: false },
+    ]);
+
+    await program.methods
+      .feedOrbsBatch([1, 1, 1], [[], [], []])
+      .accounts(shared)
+      .remainingAccounts(remainingAccounts)
+      .rpc();
+
+    for (const orb of orbs) {
+      const record = await program.account.claimRecord.fetch(claimRecordFor(orb.orbMint));
+      expect(record.orbMint.toBase58()).to.equal(orb.orbMint.toBase58());
+      expect(record.claimer.toBase58()).to.equal(authority.toBase58());
+    }
+
+    const forgeStateAccount = await program.account.forgeState.fetch(forgeState);
+    expect(forgeStateAccount.totalClaimed.toNumber()).to.equal(totalClaimed.toNumber() + 3);
+
+    const supplyAfter = (await provider.connection.getTokenSupply(rariMint)).value.amount;
+    expect(BigInt(supplyBefore) - BigInt(supplyAfter)).to.equal(
+      BigInt(rariThreshold.toString()) * BigInt(3)
+    );
+
+    try {
+      await program.methods
+        .feedOrbsBatch(new Array(11).fill(1), new Array(11).fill([]))
+        .accounts(shared)
+        .rpc();
+      expect.fail("Should have rejected an oversized batch");
+    } catch (error) {
+      expect(error.message).to.include("BatchTooLarge");
+    }
+  });
+
+  it("Pauses cross-chain feeds independently of same-chain feeds", async () => {
+    await program.methods
+      .setPauseFlags(PAUSE_CROSS_CHAIN_FEED)
+      .accounts({ forgeState, signer: authority })
+      .rpc();
+
+    try {
+      await program.methods
+        .feedOrb(8453, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb(), {}, 8453))
+        .rpc();
+      expect.fail("Should have failed while cross-chain feeds are paused");
+    } catch (error) {
+      expect(error.message).to.include("ProgramPaused");
+    }
+
+    // Same-chain feeds stay live
+    await program.methods
+      .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(await createOrb()))
+      .rpc();
+
+    await program.methods
+      .setPauseFlags(0)
+      .accounts({ forgeState, signer: authority })
+      .rpc();
+  });
+
+
+  it("Applies per-chain threshold overrides", async () => {
+    const chainId = 10; // Optimism chain ID
+    const chainConfig = chainConfigFor(chainId);
+    const overrideThreshold = new anchor.BN(50 * LAMPORTS_PER_SOL);
+    // A left-padded EVM token address on the destination chain
+    const destinationMint = [
+      ...Buffer.alloc(12),
+      ...Keypair.generate().publicKey.toBuffer().subarray(0, 20),
+    ];
+
+    // Cross-chain targets name their destination mint before they can be enabled
+    try {
+      await program.methods
+        .setChainConfig(chainId, overrideThreshold, true)
+        .accounts({ forgeState, chainConfig, authority, systemProgram: SystemProgram.programId })
+        .rpc();
+      expect.fail("Should have required a destination mint");
+    } catch (error) {
+      expect(error.message).to.include("MissingDestinationMint");
+    }
+
+    await program.methods
+      .setDestinationMint(chainId, destinationMint)
+      .accounts({ forgeState, chainConfig, authority, systemProgram: SystemProgram.programId })
+      .rpc();
+    const { events } = await program.methods
+      .setChainConfig(chainId, overrideThreshold, true)
+      .accounts({ forgeState, chainConfig, authority, systemProgram: SystemProgram.programId })
+      .simulate();
+    const updated = events.find((event) => event.name === "chainConfigUpdated");
+    expect(updated.data.enabled).to.be.true;
+    expect(updated.data.destinationMint).to.deep.equal(destinationMint);
+    await program.methods
+      .setChainConfig(chainId, overrideThreshold, true)
+      .accounts({ forgeState, chainConfig, authority, systemProgram: SystemProgram.programId })
+      .rpc();
+    const config = await program.account.chainConfig.fetch(chainConfig);
+    expect(config.destinationMint).to.deep.equal(destinationMint);
+
+    const supplyBefore = (await provider.connection.getTokenSupply(rariMint)).value.amount;
+    await program.methods
+      .feedOrb(chainId, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(await createOrb(), {}, chainId))
+      .rpc();
+    const supplyAfter = (await provider.connection.getTokenSupply(rariMint)).value.amount;
+    expect(BigInt(supplyBefore) - BigInt(supplyAfter)).to.equal(
+      BigInt(overrideThreshold.toString())
+    );
+
+    await program.methods
+      .setChainConfig(chainId, overrideThreshold, false)
+      .accounts({ forgeState, chainConfig, authority, systemProgram: SystemProgram.programId })
+      .rpc();
+
+    try {
+      await program.methods
+        .feedOrb(chainId, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb(), {}, chainId))
+        .rpc();
+      expect.fail("Should have failed for a disabled chain");
+    } catch (error) {
+      expect(error.message).to.include("ChainDisabled");
+    }
+
+    await program.methods
+      .removeChainConfig(chainId)
+      .accounts({ forgeState, chainConfig, authority })
+      .rpc();
+    expect(await provider.connection.getAccountInfo(chainConfig)).to.be.null;
+  });
+
+
+  it("Requires the configured creator to be verified on the Orb", async () => {
+    await program.methods
+      .updateRequiredCreator(Keypair.generate().publicKey)
+      .accounts({ forgeState, authority })
+      .rpc();
+
+    try {
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb()))
+        .rpc();
+      expect.fail("Should have failed without the required creator");
+    } catch (error) {
+      expect(error.message).to.include("CreatorNotVerified");
+    }
+
+    // Metaplex verifies the minting identity as the Orb's creator
+    await program.methods
+      .updateRequiredCreator(authority)
+      .accounts({ forgeState, authority })
+      .rpc();
+    await program.methods
+      .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(await createOrb()))
+      .rpc();
+
+    await program.methods
+      .updateRequiredCreator(PublicKey.default)
+      .accounts({ forgeState, authority })
+      .rpc();
+  });
+
+
+  it("Lets the authority withdraw treasury RARI", async () => {
+    const treasuryBefore = await getRariAccount(treasuryTokenAccount);
+    const userBefore = await getRariAccount(userRariAccount);
+    const withdrawAccounts = {
+      forgeState,
+      rariMint,
+      treasuryTokenAccount,
+      destination: userRariAccount,
+      authority,
+      tokenProgram: RARI_TOKEN_PROGRAM_ID,
+    };
+
+    try {
+      await program.methods
+        .withdrawTreasury(new anchor.BN((treasuryBefore.amount + BigInt(1)).toString()))
+        .accounts(withdrawAccounts)
+        .rpc();
+      expect.fail("Should have failed to overdraw the treasury");
+    } catch (error) {
+      expect(error.message).to.include("InsufficientTreasuryBalance");
+    }
+
+    const amount = treasuryBefore.amount;
+    await program.methods
+      .withdrawTreasury(new anchor.BN(amount.toString()))
+      .accounts(withdrawAccounts)
+      .rpc();
+
+    const treasuryAfter = await getRariAccount(treasuryTokenAccount);
+    const userAfter = await getRariAccount(userRariAccount);
+    expect(treasuryAfter.amount).to.equal(BigInt(0));
+    expect(userAfter.amount - userBefore.amount).to.equal(amount - rariTransferFee(amount));
+  });
+
+
+  it("Accepts a configured partner payment mint", async () => {
+    const partnerMint = await createMint(
+      provider.connection,
+      provider.wallet.payer,
+      authority,
+      null,
+      6
+    );
+    const userPartnerAccount = await createAccount(
+      provider.connection,
+      provider.wallet.payer,
+      partnerMint,
+      authority
+    );
+    const partnerTreasury = await createAccount(
+      provider.connection,
+      provider.wallet.payer,
+      partnerMint,
+      forgeState,
+      Keypair.generate()
+    );
+    await mintTo(
+      provider.connection,
+      provider.wallet.payer,
+      partnerMint,
+      userPartnerAccount,
+      authority,
+      100_000_000
+    );
+
+    const [paymentMintConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("payment"), partnerMint.toBuffer()],
+      program.programId
+    );
+    const partnerAccounts = {
+      rariMint: partnerMint,
+      paymentMintConfig,
+      userRariAccount: userPartnerAccount,
+      treasuryTokenAccount: partnerTreasury,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    // Unconfigured mints are rejected
+    try {
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(
+          await feedAccounts(await createOrb(), { ...partnerAccounts, paymentMintConfig: null })
+        )
+        .rpc();
+      expect.fail("Should have rejected an unconfigured payment mint");
+    } catch (error) {
+      expect(error.message).to.include("WrongRariMint");
+    }
+
+    const partnerThreshold = new anchor.BN(10_000_000); // 10 partner tokens
+    await program.methods
+      .addPaymentMint(partnerThreshold)
+      .accounts({
+        forgeState,
+        mint: partnerMint,
+        paymentMintConfig,
+        authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const { totalRariBurned } = await program.account.forgeState.fetch(forgeState);
+    const supplyBefore = (await provider.connection.getTokenSupply(partnerMint)).value.amount;
+    await program.methods
+      .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(await createOrb(), partnerAccounts))
+      .rpc();
+    const supplyAfter = (await provider.connection.getTokenSupply(partnerMint)).value.amount;
+    expect(BigInt(supplyBefore) - BigInt(supplyAfter)).to.equal(
+      BigInt(partnerThreshold.toString())
+    );
+
+    // Partner burns do not count towards the RARI burn total
+    const forgeStateAccount = await program.account.forgeState.fetch(forgeState);
+    expect(forgeStateAccount.totalRariBurned.toString()).to.equal(totalRariBurned.toString());
+
+    await program.methods
+      .disablePaymentMint()
+      .accounts({ forgeState, paymentMintConfig, authority })
+      .rpc();
+
+    try {
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb(), partnerAccounts))
+        .rpc();
+      expect.fail("Should have rejected a disabled payment mint");
+    } catch (error) {
+      expect(error.message).to.include("PaymentMintNotAccepted");
+    }
+  });
+
+
+  it("Caps feeds across all users per epoch", async () => {
+    await program.methods
+      .updateEpochConfig(new anchor.BN(3), new anchor.BN(1))
+      .accounts({ forgeState, authority })
+      .rpc();
+    // Let any epoch opened by earlier feeds lapse
+    await sleep(3500);
+
+    await program.methods
+      .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(await createOrb()))
+      .rpc();
+
+    try {
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb()))
+        .rpc();
+      expect.fail("Should have hit the epoch cap");
+    } catch (error) {
+      expect(error.message).to.include("EpochCapReached");
+    }
+
+    // Crossing the epoch boundary resets the count
+    await sleep(3500);
+    await program.methods
+      .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(await createOrb()))
+      .rpc();
+
+    await program.methods
+      .updateEpochConfig(new anchor.BN(0), new anchor.BN(0))
+      .accounts({ forgeState, authority })
+      .rpc();
+  });
+
+
+  it("Re-feeds an orb after the cooldown", async () => {
+    const orb = await createOrb();
+    await program.methods
+      .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(orb))
+      .rpc();
+
+    try {
+      await program.methods
+        .updateRefeedCooldown(new anchor.BN(-1))
+        .accounts({ forgeState, authority })
+        .rpc();
+      expect.fail("Should have rejected a negative cooldown");
+    } catch (error) {
+      expect(error.message).to.include("InvalidDuration");
+    }
+    await program.methods
+      .updateRefeedCooldown(new anchor.BN(2))
+      .accounts({ forgeState, authority })
+      .rpc();
+
+    try {
+      await program.methods
+        .refeedOrb(1)
+        .accounts(await feedAccounts(orb))
+        .rpc();
+      expect.fail("Should have failed during the cooldown");
+    } catch (error) {
+      expect(error.message).to.include("RefeedCooldownActive");
+    }
+
+    await sleep(2500);
+    await program.methods
+      .refeedOrb(1)
+      .accounts(await feedAccounts(orb))
+      .rpc();
+
+    const record = await program.account.claimRecord.fetch(claimRecordFor(orb.orbMint));
+    expect(record.feedCount).to.equal(2);
+    expect(record.lastFedAt.toNumber()).to.be.greaterThan(record.claimedAt.toNumber());
+
+    await program.methods
+      .updateRefeedCooldown(new anchor.BN(0))
+      .accounts({ forgeState, authority })
+      .rpc();
+  });
+
+
+  it("Restricts feeds to a Merkle allowlist of orb mints", async () => {
+    const hash = (...parts: Buffer[]) => Buffer.from(keccak_256.arrayBuffer(Buffer.concat(parts)));
+    // Sibling pairs are hashed in sorted order, matching the program
+    const hashPair = (a: Buffer, b: Buffer) => (Buffer.compare(a, b) <= 0 ? hash(a, b) : hash(b, a));
+
+    const orbs = await Promise.all([createOrb(), createOrb(), createOrb(), createOrb()]);
+    const leaves = orbs.map((orb) => hash(orb.orbMint.toBuffer()));
+    const left = hashPair(leaves[0], leaves[1]);
+    const right = hashPair(leaves[2], leaves[3]);
+    const root = hashPair(left, right);
+
+    await program.methods
+      .updateMerkleRoot([...root])
+      .accounts({ forgeState, authority })
+      .rpc();
+
+    // Not on the list
+    const outsider = await createOrb();
+    try {
+      await program.methods
+        .feedOrb(1, [[...leaves[1]], [...right]], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(outsider))
+        .rpc();
+      expect.fail("Should have rejected an orb outside the allowlist");
+    } catch (error) {
+      expect(error.message).to.include("OrbNotAllowlisted");
+    }
+
+    // On the list, but with the wrong proof
+    try {
+      await program.methods
+        .feedOrb(1, [[...leaves[2]], [...left]], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(orbs[0]))
+        .rpc();
+      expect.fail("Should have rejected an invalid proof");
+    } catch (error) {
+      expect(error.message).to.include("OrbNotAllowlisted");
+    }
+
+    await program.methods
+      .feedOrb(1, [[...leaves[1]], [...right]], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(orbs[0]))
+      .rpc();
+    await program.methods
+      .feedOrb(1, [[...leaves[3]], [...left]], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(orbs[2]))
+      .rpc();
+
+    await program.methods
+      .updateMerkleRoot(new Array(32).fill(0))
+      .accounts({ forgeState, authority })
+      .rpc();
+  });
+
+
+  it("Rejects a zero threshold unless free feeding is enabled", async () => {
+    const { rariThreshold } = await program.account.forgeState.fetch(forgeState);
+
+    try {
+      await program.methods
+        .updateThreshold(new anchor.BN(0))
+        .accounts({ forgeState, rariMint, authority })
+        .rpc();
+      expect.fail("Should have rejected a zero threshold");
+    } catch (error) {
+      expect(error.message).to.include("ZeroThreshold");
+    }
+
+    await program.methods
+      .setFreeFeed(true)
+      .accounts({ forgeState, authority })
+      .rpc();
+    await program.methods
+      .updateThreshold(new anchor.BN(0))
+      .accounts({ forgeState, rariMint, authority })
+      .rpc();
+
+    // Free feeds burn nothing
+    const supplyBefore = (await provider.connection.getTokenSupply(rariMint)).value.amount;
+    await program.methods
+      .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(await createOrb()))
+      .rpc();
+    const supplyAfter = (await provider.connection.getTokenSupply(rariMint)).value.amount;
+    expect(supplyAfter).to.equal(supplyBefore);
+
+    // Free feeding stays on while the threshold is zero
+    try {
+      await program.methods
+        .setFreeFeed(false)
+        .accounts({ forgeState, authority })
+        .rpc();
+      expect.fail("Should have refused to disable free feeding at a zero threshold");
+    } catch (error) {
+      expect(error.message).to.include("ZeroThreshold");
+    }
+
+    await program.methods
+      .updateThreshold(rariThreshold)
+      .accounts({ forgeState, rariMint, authority })
+      .rpc();
+    await program.methods
+      .setFreeFeed(false)
+      .accounts({ forgeState, authority })
+      .rpc();
+  });
+
+
+  it("Expires stale cross-chain claims and reclaims their rent", async () => {
+    const rentRecipient = Keypair.generate().publicKey;
+    await program.methods
+      .updateExpiryConfig(new anchor.BN(1), rentRecipient)
+      .accounts({ forgeState, authority })
+      .rpc();
+
+    const sameChainOrb = await createOrb();
+    await program.methods
+      .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(sameChainOrb))
+      .rpc();
+    const crossChainOrb = await createOrb();
+    await program.methods
+      .feedOrb(8453, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(crossChainOrb, {}, 8453))
+      .rpc();
+
+    const claimRecord = claimRecordFor(crossChainOrb.orbMint);
+    const record = await program.account.claimRecord.fetch(claimRecord);
+    expect(record.status).to.deep.equal({ pending: {} });
+    // Relayers find the pending claim in the queue; the confirmed one is never listed
+    const queued = (await program.account.pendingQueue.fetch(pendingQueue)).claims;
+    expect(queued.map((claim) => claim.toBase58())).to.include(claimRecord.toBase58());
+    expect(queued.map((claim) => claim.toBase58())).to.not.include(
+      claimRecordFor(sameChainOrb.orbMint).toBase58()
+    );
+    await sleep(2000);
+
+    // Same-chain claims are confirmed on feed and never expire
+    try {
+      await program.methods
+        .expireClaim()
+        .accounts({
+          forgeState,
+          pendingQueue,
+          claimRecord: claimRecordFor(sameChainOrb.orbMint),
+          rentRecipient,
+          authority,
+        })
+        .rpc();
+      expect.fail("Should have refused to expire a confirmed claim");
+    } catch (error) {
+      expect(error.message).to.include("ClaimNotPending");
+    }
+
+    await program.methods
+      .expireClaim()
+      .accounts({ forgeState, pendingQueue, claimRecord, rentRecipient, authority })
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(claimRecord)).to.be.null;
+    const { claims } = await program.account.pendingQueue.fetch(pendingQueue);
+    expect(claims.map((claim) => claim.toBase58())).to.not.include(claimRecord.toBase58());
+    expect(await provider.connection.getBalance(rentRecipient)).to.be.greaterThan(0);
+
+    await program.methods
+      .updateExpiryConfig(new anchor.BN(0), authority)
+      .accounts({ forgeState, authority })
+      .rpc();
+  });
+
+
+  it("Registers trusted emitters and rejects unposted VAAs", async () => {
+    const chainId = 8453;
+    const chainIdBytes = Buffer.alloc(2);
+    chainIdBytes.writeUInt16LE(chainId);
+    const [trustedEmitter] = PublicKey.findProgramAddressSync(
+      [Buffer.from("emitter"), chainIdBytes],
+      program.programId
+    );
+    const emitterAddress = Keypair.generate().publicKey.toBuffer();
+
+    await program.methods
+      .setTrustedEmitter(chainId, [...emitterAddress])
+      .accounts({ forgeState, trustedEmitter, authority, systemProgram: SystemProgram.programId })
+      .rpc();
+
+    const emitter = await program.account.trustedEmitter.fetch(trustedEmitter);
+    expect(emitter.chainId).to.equal(chainId);
+    expect(Buffer.from(emitter.emitterAddress).equals(emitterAddress)).to.be.true;
+
+    // The cloned mainnet guardian set can't sign test VAAs, so only the
+    // rejection of a VAA the core bridge never posted is exercised here
+    const vaaHash = Buffer.alloc(32, 7);
+    const [postedVaa] = PublicKey.findProgramAddressSync(
+      [Buffer.from("PostedVAA"), vaaHash],
+      WORMHOLE_PROGRAM_ID
+    );
+    const [inboundClaim] = PublicKey.findProgramAddressSync(
+      [Buffer.from("redeemed"), vaaHash],
+      program.programId
+    );
+    try {
+      await program.methods
+        .redeemClaim([...vaaHash])
+        .accounts({
+          forgeState,
+          postedVaa,
+          trustedEmitter,
+          inboundClaim,
+          payer: authority,
+          wormholeProgram: WORMHOLE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      expect.fail("Should have rejected an unposted VAA");
+    } catch (error) {
+      expect(error.message).to.include("AccountNotInitialized");
+    }
+  });
+
+
+  it("Reports forge stats through a simulated get_stats", async () => {
+    const forgeStateAccount = await program.account.forgeState.fetch(forgeState);
+    const { events } = await program.methods
+      .getStats()
+      .accounts({ forgeState })
+      .simulate();
+
+    const snapshot = events.find((event) => event.name === "forgeStatsSnapshot");
+    expect(snapshot).to.not.be.undefined;
+    expect(snapshot.data.schemaVersion).to.equal(12);
+    expect(snapshot.data.totalClaimed.toString()).to.equal(
+      forgeStateAccount.totalClaimed.toString()
+    );
+    expect(snapshot.data.totalRariBurned.toString()).to.equal(
+      forgeStateAccount.totalRariBurned.toString()
+    );
+    expect(snapshot.data.activeClaims.toString()).to.equal(
+      forgeStateAccount.activeClaims.toString()
+    );
+    expect(snapshot.data.rariThreshold.toString()).to.equal(
+      forgeStateAccount.rariThreshold.toString()
+    );
+    expect(snapshot.data.rariDecimals).to.equal(9);
+    expect(snapshot.data.paused).to.be.false;
+    expect(snapshot.data.thresholdEffectiveAt.toNumber()).to.equal(
+      forgeStateAccount.thresholdEffectiveAt.toNumber()
+    );
+  });
+
+
+  it("Charges the configured SOL fee per feed", async () => {
+    const feeRecipient = Keypair.generate().publicKey;
+    const solFee = 1_000_000; // Above the rent-exempt minimum for a new account
+    await program.methods
+      .updateSolFee(new anchor.BN(solFee), feeRecipient)
+      .accounts({ forgeState, authority })
+      .rpc();
+
+    try {
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb()))
+        .rpc();
+      expect.fail("Should have rejected the wrong fee recipient");
+    } catch (error) {
+      expect(error.message).to.include("InvalidFeeRecipient");
+    }
+
+    const balanceBefore = await provider.connection.getBalance(feeRecipient);
+    await program.methods
+      .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(await createOrb(), { feeRecipient }))
+      .rpc();
+    const balanceAfter = await provider.connection.getBalance(feeRecipient);
+    expect(balanceAfter - balanceBefore).to.equal(solFee);
+
+    await program.methods
+      .updateSolFee(new anchor.BN(0), authority)
+      .accounts({ forgeState, authority })
+      .rpc();
+  });
+
+
+  it("Reports feed eligibility without feeding", async () => {
+    const errorCode = (name: string) =>
+      program.idl.errors.find((error) => error.name.toLowerCase() === name.toLowerCase()).code;
+    const checkEligible = async (orb: Orb) => {
+      const {
+        wormholeBridge,
+        wormholeFeeCollector,
+        wormholeEmitter,
+        wormholeSequence,
+        wormholeMessage,
+        wormholeProgram,
+        systemProgram,
+        clock,
+        rent,
+        feeRecipient,
+        escrowTokenAccount,
+        orbTokenAccount,
+        orbEdition,
+        orbFreezeAuthority,
+        orbTokenProgram,
+        tokenMetadataProgram,
+        chainCounter,
+        receiptMint,
+        userReceiptAccount,
+        receiptTokenProgram,
+        earlyBonusMint,
+        claimerBonusAccount,
+        bonusTokenProgram,
+        partnerRewardMint,
+        partnerRewardAccount,
+        partnerTokenProgram,
+        pointsAccount,
+        denyEntry,
+        relayerFeeVault,
+        instructionsSysvar,
+        govMetadata,
+        govTokenAccount,
+        delegate,
+        payer,
+        eventSinkProgram,
+        ...accounts
+      } = await feedAccounts(orb);
+      const { events } = await program.methods
+        .checkFeedEligible(1, [])
+        .accounts(accounts)
+        .simulate();
+      return events.find((event) => event.name === "eligibilityResult").data;
+    };
+
+    const orb = await createOrb();
+    const eligible = await checkEligible(orb);
+    expect(eligible.eligible).to.be.true;
+    expect(eligible.reason.toNumber()).to.equal(0);
+
+    // Nothing was fed
+    expect(await provider.connection.getAccountInfo(claimRecordFor(orb.orbMint))).to.be.null;
+
+    const { nft: otherCollection } = await metaplex.nfts().create({
+      name: "Not Orbs",
+      uri: "https://arweave.net/not-orbs.json",
+      sellerFeeBasisPoints: 0,
+      isCollection: true,
+    });
+    const outsider = await checkEligible(await createOrb(otherCollection.address));
+    expect(outsider.eligible).to.be.false;
+    expect(outsider.reason.toNumber()).to.equal(errorCode("OrbNotInCollection"));
+
+    await program.methods
+      .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(orb))
+      .rpc();
+    const fed = await checkEligible(orb);
+    expect(fed.reason.toNumber()).to.equal(errorCode("OrbAlreadyClaimed"));
+  });
+
+
+  it("Freezes fed Orbs in freeze mode and thaws them on unfreeze", async () => {
+    const [orbFreezeAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("freeze_authority")],
+      program.programId
+    );
+    const orb = await createOrb();
+    const orbTokenAccount = getAssociatedTokenAddressSync(orb.orbMint, authority);
+    const orbEdition = metaplex.nfts().pdas().masterEdition({ mint: orb.orbMint });
+    const freezeAccounts = {
+      orbTokenAccount,
+      orbEdition,
+      orbFreezeAuthority,
+      orbTokenProgram: TOKEN_PROGRAM_ID,
+      tokenMetadataProgram: metaplex.programs().getTokenMetadata().address,
+    };
+
+    // The forge can only freeze Orbs it has been delegated
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        createApproveInstruction(orbTokenAccount, orbFreezeAuthority, authority, 1)
+      )
+    );
+
+    await program.methods
+      .setFreezeOrb(true)
+      .accounts({ forgeState, authority })
+      .rpc();
+
+    try {
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(orb, freezeAccounts))
+        .rpc();
+      expect((await getAccount(provider.connection, orbTokenAccount)).isFrozen).to.be.true;
+
+      await program.methods
+        .unfreezeOrb()
+        .accounts({
+          forgeState,
+          orbMint: orb.orbMint,
+          authority,
+          ...freezeAccounts,
+        })
+        .rpc();
+      expect((await getAccount(provider.connection, orbTokenAccount)).isFrozen).to.be.false;
+    } finally {
+      await program.methods
+        .setFreezeOrb(false)
+        .accounts({ forgeState, authority })
+        .rpc();
+    }
+  });
+
+
+  it("Stops feeding exactly at the lifetime claim cap", async () => {
+    const { totalClaimed } = await program.account.forgeState.fetch(forgeState);
+    await program.methods
+      .setMaxTotalClaims(totalClaimed.addn(1))
+      .accounts({ forgeState, authority })
+      .rpc();
+
+    try {
+      // The feed that reaches the cap is still accepted
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb()))
+        .rpc();
+      const atCap = await program.account.forgeState.fetch(forgeState);
+      expect(atCap.totalClaimed.toString()).to.equal(totalClaimed.addn(1).toString());
+
+      try {
+        await program.methods
+          .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+          .accounts(await feedAccounts(await createOrb()))
+          .rpc();
+        expect.fail("Should have stopped at the claim cap");
+      } catch (error) {
+        expect(error.message).to.include("ClaimCapReached");
+      }
+    } finally {
+      await program.methods
+        .setMaxTotalClaims(new anchor.BN(0))
+        .accounts({ forgeState, authority })
+        .rpc();
+    }
+  });
+
+
+  it("Lets the operator pause but not change thresholds", async () => {
+    const operator = Keypair.generate();
+    await program.methods
+      .setOperator(operator.publicKey)
+      .accounts({ forgeState, authority })
+      .rpc();
+
+    await program.methods
+      .setPauseFlags(PAUSE_FEED)
+      .accounts({ forgeState, signer: operator.publicKey })
+      .signers([operator])
+      .rpc();
+    expect((await program.account.forgeState.fetch(forgeState)).pauseFlags).to.equal(PAUSE_FEED);
+
+    await program.methods
+      .togglePause()
+      .accounts({ forgeState, signer: operator.publicKey })
+      .signers([operator])
+      .rpc();
+    expect((await program.account.forgeState.fetch(forgeState)).pauseFlags).to.equal(0);
+
+    try {
+      await program.methods
+        .updateThreshold(new anchor.BN(1))
+        .accounts({ forgeState, rariMint, authority: operator.publicKey })
+        .signers([operator])
+        .rpc();
+      expect.fail("Operator should not be able to change the threshold");
+    } catch (error) {
+      expect(error.message).to.include("constraint");
+    }
+
+    await program.methods
+      .setOperator(PublicKey.default)
+      .accounts({ forgeState, authority })
+      .rpc();
+  });
+
+
+  it("Tags OrbFedEvent with its route and the forge version", async () => {
+    const eventParser = new anchor.EventParser(program.programId, program.coder);
+    const feedEvent = async (chainId: number) => {
+      const tx = await program.methods
+        .feedOrb(chainId, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb(), {}, chainId))
+        .rpc({ commitment: "confirmed" });
+      const txInfo = await provider.connection.getTransaction(tx, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      return [...eventParser.parseLogs(txInfo.meta.logMessages)].find(
+        (event) => event.name === "orbFedEvent"
+      ).data;
+    };
+
+    const sameChain = await feedEvent(1);
+    expect(sameChain.route).to.equal(0);
+    expect(sameChain.forgeVersion).to.equal(1);
+
+    const crossChain = await feedEvent(8453);
+    expect(crossChain.route).to.equal(1);
+    expect(crossChain.forgeVersion).to.equal(1);
+  });
+
+
+  it("Migrates the forge state idempotently", async () => {
+    const sizeBefore = (await provider.connection.getAccountInfo(forgeState)).data.length;
+
+    for (let run = 0; run < 2; run++) {
+      await program.methods
+        .migrateState(DEFAULT_NAMESPACE)
+        .accounts({
+          forgeState,
+          authority,
+          rariMint: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    const forgeStateAccount = await program.account.forgeState.fetch(forgeState);
+    expect(forgeStateAccount.layoutVersion).to.equal(37);
+    expect((await provider.connection.getAccountInfo(forgeState)).data.length).to.equal(sizeBefore);
+
+    const nonAuthority = Keypair.generate();
+    try {
+      await program.methods
+        .migrateState(DEFAULT_NAMESPACE)
+        .accounts({
+          forgeState,
+          authority: nonAuthority.publicKey,
+          rariMint: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([nonAuthority])
+        .rpc();
+      expect.fail("Should have rejected a non-authority migration");
+    } catch (error) {
+      expect(error.message).to.include("ConstraintHasOne");
+    }
+  });
+
+
+  it("Rejects target chains outside the supported set", async () => {
+    const { supportedChains } = await program.account.forgeState.fetch(forgeState);
+    expect(supportedChains).to.deep.equal([1, 10, 8453]);
+
+    const unsupportedChain = 999;
+    try {
+      await program.methods
+        .feedOrb(unsupportedChain, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb(), {}, unsupportedChain))
+        .rpc();
+      expect.fail("Should have rejected an unsupported chain");
+    } catch (error) {
+      expect(error.message).to.include("UnsupportedChain");
+    }
+
+    await program.methods
+      .updateSupportedChains([...supportedChains, unsupportedChain])
+      .accounts({ forgeState, authority })
+      .rpc();
+    try {
+      await program.methods
+        .feedOrb(unsupportedChain, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb(), {}, unsupportedChain))
+        .rpc();
+    } finally {
+      await program.methods
+        .updateSupportedChains(supportedChains)
+        .accounts({ forgeState, authority })
+        .rpc();
+    }
+  });
+
+
+  it("Escrows feeds until they are settled or cancelled", async () => {
+    const [escrowTokenAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), rariMint.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .initEscrow()
+      .accounts({
+        forgeState,
+        rariMint,
+        escrowTokenAccount,
+        authority,
+        tokenProgram: RARI_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods
+      .setEscrowMode(true)
+      .accounts({ forgeState, authority })
+      .rpc();
+
+    try {
+      const { rariThreshold, totalClaimed } = await program.account.forgeState.fetch(forgeState);
+      const escrowedFeed = async () => {
+        const orb = await createOrb();
+        await program.methods
+          .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+          .accounts(await feedAccounts(orb, { escrowTokenAccount }))
+          .rpc();
+        return claimRecordFor(orb.orbMint);
+      };
+
+      // Settling drains the escrow and confirms the claim
+      const threshold = BigInt(rariThreshold.toString());
+      const escrowedAmount = threshold - rariTransferFee(threshold);
+      const settledRecord = await escrowedFeed();
+      expect((await getRariAccount(escrowTokenAccount)).amount).to.equal(escrowedAmount);
+      const escrowed = await program.account.claimRecord.fetch(settledRecord);
+      expect(escrowed.status).to.deep.equal({ escrowed: {} });
+      expect(escrowed.escrowedAmount.toString()).to.equal(escrowedAmount.toString());
+
+      await program.methods
+        .settleFeed()
+        .accounts({
+          forgeState,
+          claimRecord: settledRecord,
+          rariMint,
+          escrowTokenAccount,
+          treasuryTokenAccount,
+          claimer: authority,
+          tokenProgram: RARI_TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      expect((await getRariAccount(escrowTokenAccount)).amount.toString()).to.equal("0");
+      const settled = await program.account.claimRecord.fetch(settledRecord);
+      expect(settled.status).to.deep.equal({ confirmed: {} });
+      expect(settled.escrowedAmount.toNumber()).to.equal(0);
+
+      // Cancelling refunds the claimer, less transfer fees, and closes the record
+      const balanceBefore = (await getRariAccount(userRariAccount)).amount;
+      const cancelledRecord = await escrowedFeed();
+      await program.methods
+        .cancelFeed()
+        .accounts({
+          forgeState,
+          claimRecord: cancelledRecord,
+          rariMint,
+          escrowTokenAccount,
+          claimerRariAccount: userRariAccount,
+          claimer: authority,
+          tokenProgram: RARI_TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      expect((await getRariAccount(userRariAccount)).amount).to.equal(
+        balanceBefore - threshold + escrowedAmount - rariTransferFee(escrowedAmount)
+      );
+      expect(await provider.connection.getAccountInfo(cancelledRecord)).to.be.null;
+
+      const forgeStateAccount = await program.account.forgeState.fetch(forgeState);
+      expect(forgeStateAccount.totalClaimed.toString()).to.equal(totalClaimed.addn(1).toString());
+    } finally {
+      await program.methods
+        .setEscrowMode(false)
+        .accounts({ forgeState, authority })
+        .rpc();
+    }
+  });
+
+
+  // Raising the halt would lock this shared forge for 48 hours, so the
+  // timelock boundary itself is covered by the program's unit tests
+  it("Restricts the emergency halt to the emergency authority", async () => {
+    const emergencyAuthority = Keypair.generate();
+    await program.methods
+      .setEmergencyAuthority(emergencyAuthority.publicKey)
+      .accounts({ forgeState, authority })
+      .rpc();
+    const forgeStateAccount = await program.account.forgeState.fetch(forgeState);
+    expect(forgeStateAccount.emergencyAuthority.toBase58()).to.equal(
+      emergencyAuthority.publicKey.toBase58()
+    );
+    expect(forgeStateAccount.halted).to.be.false;
+
+    try {
+      await program.methods
+        .emergencyHalt()
+        .accounts({ forgeState, emergencyAuthority: authority })
+        .rpc();
+      expect.fail("Authority should not be able to raise the halt");
+    } catch (error) {
+      expect(error.message).to.include("ConstraintHasOne");
+    }
+
+    try {
+      await program.methods
+        .clearEmergencyHalt()
+        .accounts({ forgeState, emergencyAuthority: emergencyAuthority.publicKey })
+        .signers([emergencyAuthority])
+        .rpc();
+      expect.fail("Should have failed without an active halt");
+    } catch (error) {
+      expect(error.message).to.include("NotHalted");
+    }
+
+    await program.methods
+      .setEmergencyAuthority(PublicKey.default)
+      .accounts({ forgeState, authority })
+      .rpc();
+  });
+
+
+  it("Rejects a compressed Orb whose asset id does not match its tree", async () => {
+    const BUBBLEGUM_PROGRAM_ID = new PublicKey("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+    const COMPRESSION_PROGRAM_ID = new PublicKey("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KSNpi");
+
+    // An (uninitialized) account owned by the compression program stands in for the tree
+    const tree = Keypair.generate();
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: authority,
+          newAccountPubkey: tree.publicKey,
+          lamports: await provider.connection.getMinimumBalanceForRentExemption(0),
+          space: 0,
+          programId: COMPRESSION_PROGRAM_ID,
+        })
+      ),
+      [tree]
+    );
+    const merkleTree = tree.publicKey;
+    const nonce = new anchor.BN(0);
+    const [assetId] = PublicKey.findProgramAddressSync(
+      [Buffer.from("asset"), merkleTree.toBuffer(), nonce.toArrayLike(Buffer, "le", 8)],
+      BUBBLEGUM_PROGRAM_ID
+    );
+    // Any key other than the tree's asset id for `nonce`
+    const otherAssetId = Keypair.generate().publicKey;
+    expect(otherAssetId.equals(assetId)).to.be.false;
+
+    const {
+      orbMint,
+      orbMetadata,
+      orbTokenAccount,
+      orbEdition,
+      orbFreezeAuthority,
+      orbTokenProgram,
+      tokenMetadataProgram,
+      chainCounter,
+      receiptMint,
+      userReceiptAccount,
+      receiptTokenProgram,
+      earlyBonusMint,
+      claimerBonusAccount,
+      bonusTokenProgram,
+      partnerRewardMint,
+      partnerRewardAccount,
+      partnerTokenProgram,
+      pointsAccount,
+      instructionsSysvar,
+      govMetadata,
+      govTokenAccount,
+      delegate,
+      payer,
+      eventSinkProgram,
+      ...accounts
+    } = await feedAccounts(await createOrb());
+    const orb = {
+      root: new Array(32).fill(0),
+      nonce,
+      index: 0,
+      metadata: {
+        name: "Orb",
+        symbol: "",
+        uri: "https://arweave.net/orb.json",
+        sellerFeeBasisPoints: 0,
+        primarySaleHappened: false,
+        isMutable: true,
+        editionNonce: null,
+        tokenStandard: { nonFungible: {} },
+        collection: { verified: true, key: orbCollection },
+        uses: null,
+        tokenProgramVersion: { original: {} },
+        creators: [],
+      },
+    };
+
+    try {
+      await program.methods
+        .feedCompressedOrb(otherAssetId, 1, [], orb)
+        .accounts({
+          ...accounts,
+          claimRecord: claimRecordFor(otherAssetId),
+          merkleTree,
+          leafDelegate: authority,
+          compressionProgram: COMPRESSION_PROGRAM_ID,
+        })
+        .rpc();
+      expect.fail("Should have rejected a mismatched asset id");
+    } catch (error) {
+      expect(error.message).to.include("InvalidCompressedOrb");
+    }
+  });
+
+
+  it("Only accepts feeds inside the drop window", async () => {
+    // The validator clock, which can run ahead of or behind wall time
+    const now = await provider.connection.getBlockTime(await provider.connection.getSlot());
+    const setWindow = (startTs: number, endTs: number) =>
+      program.methods
+        .updateWindow(new anchor.BN(startTs), new anchor.BN(endTs))
+        .accounts({ forgeState, authority })
+        .rpc();
+    const feed = async () =>
+      program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb()))
+        .rpc();
+
+    try {
+      // Just before the window opens
+      await setWindow(now + 3600, now + 7200);
+      try {
+        await feed();
+        expect.fail("Should have rejected a feed before the window");
+      } catch (error) {
+        expect(error.message).to.include("ForgeNotOpen");
+      }
+
+      // Inside the window
+      await setWindow(now - 3600, now + 3600);
+      await feed();
+
+      // After the window closed
+      await setWindow(now - 7200, now - 3600);
+      try {
+        await feed();
+        expect.fail("Should have rejected a feed after the window");
+      } catch (error) {
+        expect(error.message).to.include("ForgeClosed");
+      }
+
+      try {
+        await setWindow(now + 10, now);
+        expect.fail("Should have rejected an inverted window");
+      } catch (error) {
+        expect(error.message).to.include("InvalidForgeWindow");
+      }
+    } finally {
+      await setWindow(0, 0);
+    }
+  });
+
+  it("Counts feeds per target chain", async () => {
+    const chainId = 10; // Optimism
+    const chainCounter = chainCounterFor(chainId);
+    const countBefore = await program.account.chainCounter
+      .fetchNullable(chainCounter)
+      .then((counter) => (counter ? counter.count.toNumber() : 0));
+
+    const signature = await program.methods
+      .feedOrb(chainId, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(await createOrb(), {}, chainId))
+      .rpc({ commitment: "confirmed" });
+
+    const counter = await program.account.chainCounter.fetch(chainCounter);
+    expect(counter.chainId).to.equal(chainId);
+    expect(counter.count.toNumber()).to.equal(countBefore + 1);
+
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const eventParser = new anchor.EventParser(program.programId, program.coder);
+    const updates = [...eventParser.parseLogs(tx.meta.logMessages)].filter(
+      (event) => event.name === "chainCounterUpdated"
+    );
+    expect(updates).to.have.length(1);
+    expect(updates[0].data.chainId).to.equal(chainId);
+    expect(updates[0].data.count.toNumber()).to.equal(countBefore + 1);
+  });
+
+  it("Rejects a feeder without SOL for the claim record's rent", async () => {
+    // Enough SOL to open feeder stats, one lamport short of the claim record
+    const feederStatsRent = await provider.connection.getMinimumBalanceForRentExemption(8 + 16);
+    const claimRecordRent = await provider.connection.getMinimumBalanceForRentExemption(
+      program.account.claimRecord.size
+    );
+    const wallet = Keypair.generate();
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: authority,
+          toPubkey: wallet.publicKey,
+          lamports: feederStatsRent + claimRecordRent - 1,
+        })
+      )
+    );
+    const rariAccount = await createRariAccount(wallet.publicKey);
+    await mintRari(rariAccount, 1000 * LAMPORTS_PER_SOL);
+
+    const orb = await createOrb(orbCollection, wallet.publicKey);
+    try {
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, wallet.publicKey, null)
+        .accounts(
+          await feedAccounts(orb, {
+            userRariAccount: rariAccount,
+            user: wallet.publicKey,
+          })
+        )
+        .signers([wallet])
+        .rpc();
+      expect.fail("Should have rejected a feeder short on SOL");
+    } catch (error) {
+      expect(error.message).to.include("InsufficientSolForRent");
+    }
+    expect(await provider.connection.getAccountInfo(claimRecordFor(orb.orbMint))).to.be.null;
+  });
+
+  it("Mints a soulbound receipt for cross-chain feeds", async () => {
+    // Non-transferable Token-2022 mint under the forge's mint authority
+    const receiptMintKeypair = Keypair.generate();
+    const receiptMint = receiptMintKeypair.publicKey;
+    const space = getMintLen([ExtensionType.NonTransferable]);
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: authority,
+          newAccountPubkey: receiptMint,
+          space,
+          lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeNonTransferableMintInstruction(receiptMint, TOKEN_2022_PROGRAM_ID),
+        createInitializeMintInstruction(receiptMint, 0, forgeState, null, TOKEN_2022_PROGRAM_ID)
+      ),
+      [receiptMintKeypair]
+    );
+    const userReceiptAccount = await createAccount(
+      provider.connection,
+      provider.wallet.payer,
+      receiptMint,
+      authority,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const receiptAccounts = {
+      receiptMint,
+      userReceiptAccount,
+      receiptTokenProgram: TOKEN_2022_PROGRAM_ID,
+    };
+    const receiptBalance = async () =>
+      (await getAccount(provider.connection, userReceiptAccount, undefined, TOKEN_2022_PROGRAM_ID))
+        .amount;
+
+    await program.methods
+      .setReceiptMint()
+      .accounts({ forgeState, receiptMint, authority })
+      .rpc();
+
+    try {
+      try {
+        await program.methods
+          .feedOrb(8453, [], NO_MAX_BURN, 0, authority, null)
+          .accounts(await feedAccounts(await createOrb(), {}, 8453))
+          .rpc();
+        expect.fail("Should have required the receipt accounts");
+      } catch (error) {
+        expect(error.message).to.include("ReceiptAccountsMissing");
+      }
+
+      await program.methods
+        .feedOrb(8453, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb(), receiptAccounts, 8453))
+        .rpc();
+      expect(await receiptBalance()).to.equal(BigInt(1));
+
+      // Same-chain feeds settle immediately and get no receipt
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb(), receiptAccounts))
+        .rpc();
+      expect(await receiptBalance()).to.equal(BigInt(1));
+
+      // Receipts are only burned against a redeemed return leg
+      const vaaHash = Buffer.alloc(32, 9);
+      const [inboundClaim] = PublicKey.findProgramAddressSync(
+        [Buffer.from("redeemed"), vaaHash],
+        program.programId
+      );
+      try {
+        await program.methods
+          .burnReceipt([...vaaHash])
+          .accounts({
+            forgeState,
+            inboundClaim,
+            receiptMint,
+            claimerReceiptAccount: userReceiptAccount,
+            claimer: authority,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .rpc();
+        expect.fail("Should have required a redeemed claim");
+      } catch (error) {
+        expect(error.message).to.include("AccountNotInitialized");
+      }
+      expect(await receiptBalance()).to.equal(BigInt(1));
+    } finally {
+      await program.methods
+        .setReceiptMint()
+        .accounts({ forgeState, receiptMint: null, authority })
+        .rpc();
+    }
+
+    const { receiptMint: cleared } = await program.account.forgeState.fetch(forgeState);
+    expect(cleared.equals(PublicKey.default)).to.be.true;
+  });
+
+  it("Emits OrbFedCompact with a fixed layout and trailing CRC32", async () => {
+    const orb = await createOrb();
+    const tx = await program.methods
+      .feedOrb(8453, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(orb, {}, 8453))
+      .rpc({ commitment: "confirmed" });
+    const txInfo = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+
+    // Decoded by hand from the raw log, as an indexer without the IDL would
+    const discriminator = createHash("sha256").update("event:OrbFedCompact").digest().subarray(0, 8);
+    const payloads = txInfo.meta.logMessages
+      .filter((log) => log.startsWith("Program data: "))
+      .map((log) => Buffer.from(log.slice("Program data: ".length), "base64"))
+      .filter((data) => data.subarray(0, 8).equals(discriminator))
+      .map((data) => data.subarray(8));
+    expect(payloads).to.have.length(1);
+    const [payload] = payloads;
+
+    expect(payload.length).to.equal(131);
+    expect(payload.readUInt8(0)).to.equal(12);
+    expect(payload.subarray(1, 33).equals(orb.orbMint.toBuffer())).to.be.true;
+    expect(payload.subarray(33, 65).equals(authority.toBuffer())).to.be.true;
+    expect(payload.readUInt16LE(65)).to.equal(8453);
+    expect(payload.readUInt8(75)).to.equal(1);
+    expect(payload.readUInt8(116)).to.equal(1);
+    expect(payload.readUInt16LE(117)).to.equal(1);
+    expect(payload.readUInt32LE(127)).to.equal(crc32(payload.subarray(0, 127)));
+
+    const fed = [...new anchor.EventParser(program.programId, program.coder).parseLogs(
+      txInfo.meta.logMessages
+    )].find((event) => event.name === "orbFedEvent").data;
+    expect(payload.readBigUInt64LE(67)).to.equal(BigInt(fed.rariBurned.toString()));
+    expect(payload.readBigUInt64LE(76)).to.equal(BigInt(fed.wormholeSequence.toString()));
+    expect(payload.readBigUInt64LE(119)).to.equal(BigInt(fed.seq.toString()));
+  });
+
+  it("Rejects feeds once the threshold rises above max_burn", async () => {
+    const { rariThreshold } = await program.account.forgeState.fetch(forgeState);
+    // Quoted against the current threshold
+    const maxBurn = rariThreshold;
+
+    await program.methods
+      .feedOrb(1, [], maxBurn, 0, authority, null)
+      .accounts(await feedAccounts(await createOrb()))
+      .rpc();
+
+    await program.methods
+      .updateThreshold(rariThreshold.addn(1))
+      .accounts({ forgeState, rariMint, authority })
+      .rpc();
+    try {
+      try {
+        await program.methods
+          .feedOrb(1, [], maxBurn, 0, authority, null)
+          .accounts(await feedAccounts(await createOrb()))
+          .rpc();
+        expect.fail("Should have rejected a threshold above max_burn");
+      } catch (error) {
+        expect(error.message).to.include("BurnExceedsMax");
+      }
+
+      // u64::MAX opts out of the guard
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb()))
+        .rpc();
+    } finally {
+      await program.methods
+        .updateThreshold(rariThreshold)
+        .accounts({ forgeState, rariMint, authority })
+        .rpc();
+    }
+  });
+
+  it("Pauses a chain with a reason shown to clients", async () => {
+    const chainId = 10; // Optimism chain ID
+    const chainConfig = chainConfigFor(chainId);
+    const reason = "Bridge congested";
+
+    try {
+      await program.methods
+        .pauseChain(chainId, Buffer.from([0xff, 0xfe]))
+        .accounts({ forgeState, chainConfig, authority, systemProgram: SystemProgram.programId })
+        .rpc();
+      expect.fail("Should have rejected a reason that is not UTF-8");
+    } catch (error) {
+      expect(error.message).to.include("InvalidDisabledReason");
+    }
+
+    await program.methods
+      .pauseChain(chainId, Buffer.from(reason))
+      .accounts({ forgeState, chainConfig, authority, systemProgram: SystemProgram.programId })
+      .rpc();
+    try {
+      const config = await program.account.chainConfig.fetch(chainConfig);
+      expect(config.enabled).to.be.false;
+      const disabledReason = Buffer.from(config.disabledReason);
+      expect(disabledReason.length).to.equal(64);
+      expect(disabledReason.subarray(0, reason.length).toString()).to.equal(reason);
+      expect(disabledReason.subarray(reason.length).every((byte) => byte === 0)).to.be.true;
+
+      try {
+        await program.methods
+          .feedOrb(chainId, [], NO_MAX_BURN, 0, authority, null)
+          .accounts(await feedAccounts(await createOrb(), {}, chainId))
+          .rpc();
+        expect.fail("Should have failed for a paused chain");
+      } catch (error) {
+        expect(error.message).to.include("ChainDisabled");
+        expect(error.logs.join("\n")).to.include(`Chain ${chainId} disabled: ${reason}`);
+      }
+    } finally {
+      await program.methods
+        .removeChainConfig(chainId)
+        .accounts({ forgeState, chainConfig, authority })
+        .rpc();
+    }
+  });
+
+  it("Enforces a cooldown between feeds by the same user", async () => {
+    const feeder = await createFeeder(1000);
+    const feedAs = async () =>
+      program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, feeder.wallet.publicKey, null)
+        .accounts(
+          await feedAccounts(await createOrb(orbCollection, feeder.wallet.publicKey), {
+            userRariAccount: feeder.rariAccount,
+            user: feeder.wallet.publicKey,
+          })
+        )
+        .signers([feeder.wallet])
+        .rpc();
+
+    await program.methods
+      .updateUserCooldown(new anchor.BN(3600))
+      .accounts({ forgeState, authority })
+      .rpc();
+    try {
+      await feedAs();
+      try {
+        await feedAs();
+        expect.fail("Should have rejected a feed during the cooldown");
+      } catch (error) {
+        expect(error.message).to.include("UserCooldownActive");
+      }
+    } finally {
+      await program.methods
+        .updateUserCooldown(new anchor.BN(0))
+        .accounts({ forgeState, authority })
+        .rpc();
+    }
+
+    // 0 disables the cooldown
+    await feedAs();
+    const stats = await program.account.feederStats.fetch(feederStatsFor(feeder.wallet.publicKey));
+    expect(stats.lastFeedAt.toNumber()).to.be.greaterThan(0);
+  });
+
+  it("Stamps feeds with sequential claim indices", async () => {
+    const eventParser = new anchor.EventParser(program.programId, program.coder);
+    const { nextClaimIndex } = await program.account.forgeState.fetch(forgeState);
+
+    for (let offset = 0; offset < 3; offset++) {
+      const orb = await createOrb();
+      const tx = await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(orb))
+        .rpc({ commitment: "confirmed" });
+      const expected = nextClaimIndex.addn(offset).toNumber();
+
+      const record = await program.account.claimRecord.fetch(claimRecordFor(orb.orbMint));
+      expect(record.claimIndex.toNumber()).to.equal(expected);
+
+      const txInfo = await provider.connection.getTransaction(tx, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const fed = [...eventParser.parseLogs(txInfo.meta.logMessages)].find(
+        (event) => event.name === "orbFedEvent"
+      ).data;
+      expect(fed.claimIndex.toNumber()).to.equal(expected);
+    }
+
+    const forgeStateAccount = await program.account.forgeState.fetch(forgeState);
+    expect(forgeStateAccount.nextClaimIndex.toNumber()).to.equal(nextClaimIndex.addn(3).toNumber());
+  });
+
+  it("Feeds with native SOL through a temporary WSOL account", async () => {
+    const [paymentMintConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("payment"), NATIVE_MINT.toBuffer()],
+      program.programId
+    );
+    const [wsolAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("wsol"), authority.toBuffer()],
+      program.programId
+    );
+    const wsolTreasury = await createAccount(
+      provider.connection,
+      provider.wallet.payer,
+      NATIVE_MINT,
+      forgeState,
+      Keypair.generate()
+    );
+    const solAccounts = async (orb: Orb) => {
+      const {
+        chainCounter,
+        rariMint,
+        escrowTokenAccount,
+        orbTokenAccount,
+        orbEdition,
+        orbFreezeAuthority,
+        orbTokenProgram,
+        tokenMetadataProgram,
+        receiptMint,
+        userReceiptAccount,
+        receiptTokenProgram,
+        earlyBonusMint,
+        claimerBonusAccount,
+        bonusTokenProgram,
+        partnerRewardMint,
+        partnerRewardAccount,
+        partnerTokenProgram,
+        pointsAccount,
+        instructionsSysvar,
+        govMetadata,
+        govTokenAccount,
+        delegate,
+        payer,
+        eventSinkProgram,
+        userRariAccount,
+        ...accounts
+      } = await feedAccounts(orb, {
+        paymentMintConfig,
+        treasuryTokenAccount: wsolTreasury,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      });
+      return {
+        ...accounts,
+        nativeMint: NATIVE_MINT,
+        wsolAccount,
+        rariMint: null,
+        userRariAccount: null,
+        rariTokenProgram: null,
+      };
+    };
+
+    // The canonical RARI mint is not accepted in place of the native mint
+    try {
+      await program.methods
+        .feedOrbWithSol(1, [], NO_MAX_BURN)
+        .accounts({ ...(await solAccounts(await createOrb())), nativeMint: rariMint })
+        .rpc();
+      expect.fail("Should have rejected a non-native mint");
+    } catch (error) {
+      expect(error.message).to.include("NotNativeMint");
+    }
+
+    const solThreshold = new anchor.BN(LAMPORTS_PER_SOL / 10);
+    await program.methods
+      .addPaymentMint(solThreshold)
+      .accounts({
+        forgeState,
+        mint: NATIVE_MINT,
+        paymentMintConfig,
+        authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    try {
+      const orb = await createOrb();
+      await program.methods
+        .feedOrbWithSol(1, [], NO_MAX_BURN)
+        .accounts(await solAccounts(orb))
+        .rpc();
+
+      // The whole threshold lands in the treasury, since native SOL cannot be burned
+      const treasury = await getAccount(provider.connection, wsolTreasury);
+      expect(treasury.amount.toString()).to.equal(solThreshold.toString());
+
+      // The WSOL account is closed back to the user
+      expect(await provider.connection.getAccountInfo(wsolAccount)).to.be.null;
+      const record = await program.account.claimRecord.fetch(claimRecordFor(orb.orbMint));
+      expect(record.claimer.toString()).to.equal(authority.toString());
+    } finally {
+      await program.methods
+        .disablePaymentMint()
+        .accounts({ forgeState, paymentMintConfig, authority })
+        .rpc();
+    }
+  });
+
+  it("Pins accepted metadata update authorities", async () => {
+    const otherAuthority = Keypair.generate().publicKey;
+    const updateAuthorityAccounts = { forgeState, authority };
+    await program.methods
+      .addUpdateAuthority(otherAuthority)
+      .accounts(updateAuthorityAccounts)
+      .rpc();
+    try {
+      // Orbs are minted with the wallet as update authority
+      try {
+        await program.methods
+          .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+          .accounts(await feedAccounts(await createOrb()))
+          .rpc();
+        expect.fail("Should have rejected an unlisted update authority");
+      } catch (error) {
+        expect(error.message).to.include("UpdateAuthorityNotAccepted");
+      }
+
+      await program.methods
+        .addUpdateAuthority(authority)
+        .accounts(updateAuthorityAccounts)
+        .rpc();
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb()))
+        .rpc();
+
+      const { acceptedUpdateAuthorities } = await program.account.forgeState.fetch(forgeState);
+      expect(acceptedUpdateAuthorities.map((key) => key.toString())).to.deep.equal([
+        otherAuthority.toString(),
+        authority.toString(),
+      ]);
+    } finally {
+      const { acceptedUpdateAuthorities } = await program.account.forgeState.fetch(forgeState);
+      for (const updateAuthority of acceptedUpdateAuthorities) {
+        await program.methods
+          .removeUpdateAuthority(updateAuthority)
+          .accounts(updateAuthorityAccounts)
+          .rpc();
+      }
+    }
+  });
+
+  it("Refuses to close the forge while feeding or with pending claims", async () => {
+    const closeAccounts = { forgeState, pendingQueue, authority };
+    try {
+      await program.methods.closeForge().accounts(closeAccounts).rpc();
+      expect.fail("Should have rejected closing an unpaused forge");
+    } catch (error) {
+      expect(error.message).to.include("ForgeNotPaused");
+    }
+
+    const { pendingClaims } = await program.account.forgeState.fetch(forgeState);
+    await program.methods
+      .feedOrb(8453, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(await createOrb(), {}, 8453))
+      .rpc();
+    const forgeStateAccount = await program.account.forgeState.fetch(forgeState);
+    expect(forgeStateAccount.pendingClaims.toNumber()).to.equal(pendingClaims.toNumber() + 1);
+
+    await program.methods
+      .setPauseFlags(PAUSE_FEED)
+      .accounts({ forgeState, signer: authority })
+      .rpc();
+    try {
+      await program.methods.closeForge().accounts(closeAccounts).rpc();
+      expect.fail("Should have rejected closing with pending claims");
+    } catch (error) {
+      expect(error.message).to.include("PendingClaimsRemain");
+    } finally {
+      await program.methods
+        .setPauseFlags(0)
+        .accounts({ forgeState, signer: authority })
+        .rpc();
+    }
+  });
+
+  it("Configures pruning of redemption markers", async () => {
+    try {
+      await program.methods
+        .updateRedeemedTtl(new anchor.BN(-1))
+        .accounts({ forgeState, authority })
+        .rpc();
+      expect.fail("Should have rejected a negative TTL");
+    } catch (error) {
+      expect(error.message).to.include("InvalidRedeemedTtl");
+    }
+
+    await program.methods
+      .updateRedeemedTtl(new anchor.BN(86_400))
+      .accounts({ forgeState, authority })
+      .rpc();
+    try {
+      const forgeStateAccount = await program.account.forgeState.fetch(forgeState);
+      expect(forgeStateAccount.redeemedTtl.toNumber()).to.equal(86_400);
+      expect(forgeStateAccount.prunedThrough.toNumber()).to.equal(0);
+
+      // No test VAA can be redeemed (see above), so only a missing marker is exercised
+      const vaaHash = Buffer.alloc(32, 9);
+      const [inboundClaim] = PublicKey.findProgramAddressSync(
+        [Buffer.from("redeemed"), vaaHash],
+        program.programId
+      );
+      try {
+        await program.methods
+          .pruneRedeemed([...vaaHash])
+          .accounts({ forgeState, inboundClaim, caller: authority })
+          .rpc();
+        expect.fail("Should have rejected a missing marker");
+      } catch (error) {
+        expect(error.message).to.include("AccountNotInitialized");
+      }
+    } finally {
+      await program.methods
+        .updateRedeemedTtl(new anchor.BN(0))
+        .accounts({ forgeState, authority })
+        .rpc();
+    }
+  });
+
+  it("Applies threshold changes only after M-of-N governance approval", async () => {
+    const cosigner = Keypair.generate();
+    const proposalFor = (proposalId: anchor.BN) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("proposal"), proposalId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+    const propose = async (change: any) => {
+      const { nextProposalId } = await program.account.forgeState.fetch(forgeState);
+      await program.methods
+        .proposeChange(change)
+        .accounts({
+          forgeState,
+          proposal: proposalFor(nextProposalId),
+          proposer: authority,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      return nextProposalId;
+    };
+    const approve = (proposalId: anchor.BN) =>
+      program.methods
+        .approveChange(proposalId)
+        .accounts({ forgeState, proposal: proposalFor(proposalId), signer: cosigner.publicKey })
+        .signers([cosigner])
+        .rpc();
+
+    const { rariThreshold } = await program.account.forgeState.fetch(forgeState);
+    await program.methods
+      .setGovernance([authority, cosigner.publicKey, Keypair.generate().publicKey], 2)
+      .accounts({ forgeState, authority })
+      .rpc();
+    try {
+      try {
+        await program.methods
+          .updateThreshold(rariThreshold.addn(1))
+          .accounts({ forgeState, rariMint, authority })
+          .rpc();
+        expect.fail("Should have required governance");
+      } catch (error) {
+        expect(error.message).to.include("GovernanceRequired");
+      }
+      // Chain thresholds override the global one, so they are locked too
+      try {
+        await program.methods
+          .setChainConfig(8453, new anchor.BN(1), true)
+          .accounts({
+            forgeState,
+            chainConfig: chainConfigFor(8453),
+            authority,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail("Should have required governance for a chain threshold");
+      } catch (error) {
+        expect(error.message).to.include("GovernanceRequired");
+      }
+
+      // The proposer's own approval is one of the two needed
+      const proposalId = await propose({ rariThreshold: { rariThreshold: rariThreshold.addn(1) } });
+      let forgeStateAccount = await program.account.forgeState.fetch(forgeState);
+      expect(forgeStateAccount.rariThreshold.toString()).to.equal(rariThreshold.toString());
+
+      await approve(proposalId);
+      forgeStateAccount = await program.account.forgeState.fetch(forgeState);
+      expect(forgeStateAccount.rariThreshold.toString()).to.equal(rariThreshold.addn(1).toString());
+      const proposal = await program.account.proposalAccount.fetch(proposalFor(proposalId));
+      expect(proposal.executed).to.be.true;
+    } finally {
+      // Hand changes back to the authority and restore the threshold
+      await approve(await propose({ governance: { signers: [], threshold: 0 } }));
+      await program.methods
+        .updateThreshold(rariThreshold)
+        .accounts({ forgeState, rariMint, authority })
+        .rpc();
+    }
+  });
+
+  it("Logs a FeedRejected event only for failed feeds", async () => {
+    const eventParser = new anchor.EventParser(program.programId, program.coder);
+    const orb = await createOrb();
+
+    try {
+      await program.methods
+        .feedOrb(8453, [], new anchor.BN(0), 0, authority, null)
+        .accounts(await feedAccounts(orb, {}, 8453))
+        .rpc();
+      expect.fail("Should have rejected a zero max_burn");
+    } catch (error) {
+      expect(error.message).to.include("BurnExceedsMax");
+      const rejected = [...eventParser.parseLogs(error.logs)].find(
+        (event) => event.name === "feedRejected"
+      ).data;
+      expect(rejected.errorCode).to.equal(error.error.errorCode.number);
+      expect(rejected.orbMint.toString()).to.equal(orb.orbMint.toString());
+      expect(rejected.user.toString()).to.equal(authority.toString());
+      expect(rejected.targetChain).to.equal(8453);
+    }
+
+    const tx = await program.methods
+      .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(orb))
+      .rpc({ commitment: "confirmed" });
+    const txInfo = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const names = [...eventParser.parseLogs(txInfo.meta.logMessages)].map((event) => event.name);
+    expect(names).to.not.include("feedRejected");
+  });
+
+  it("Requires a backend-signed authorization for signed feeds", async () => {
+    const feedSigner = Keypair.generate();
+    const now = Math.floor(Date.now() / 1000);
+    const signedFeed = async (orb: Orb, preInstructions = []) =>
+      program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(orb, { instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY }))
+        .preInstructions(preInstructions)
+        .rpc();
+
+    await program.methods
+      .setFeedSigner(feedSigner.publicKey, true)
+      .accounts({ forgeState, authority })
+      .rpc();
+    try {
+      const orb = await createOrb();
+      for (const [preInstructions, expected] of [
+        [[], "InvalidFeedSignature"],
+        [[feedAuthorization(orb, Keypair.generate(), now + 600)], "InvalidFeedSignature"],
+        [[feedAuthorization(orb, feedSigner, now - 600)], "FeedSignatureExpired"],
+      ] as const) {
+        try {
+          await signedFeed(orb, [...preInstructions]);
+          expect.fail(`Should have rejected with ${expected}`);
+        } catch (error) {
+          expect(error.message).to.include(expected);
+        }
+      }
+
+      // Unsigned paths stay closed while signatures are required
+      try {
+        await program.methods
+          .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+          .accounts(await feedAccounts(orb))
+          .rpc();
+        expect.fail("Should have required the instructions sysvar");
+      } catch (error) {
+        expect(error.message).to.include("InvalidFeedSignature");
+      }
+
+      await signedFeed(orb, [feedAuthorization(orb, feedSigner, now + 600)]);
+      const record = await program.account.claimRecord.fetch(claimRecordFor(orb.orbMint));
+      expect(record.claimer.toString()).to.equal(authority.toString());
+    } finally {
+      await program.methods
+        .setFeedSigner(PublicKey.default, false)
+        .accounts({ forgeState, authority })
+        .rpc();
+    }
+  });
+
+  it("Charges signed rarity tiers their own threshold", async () => {
+    const feedSigner = Keypair.generate();
+    const expiry = Math.floor(Date.now() / 1000) + 600;
+    const baseThreshold = (await program.account.forgeState.fetch(forgeState)).rariThreshold;
+    const tierOne = baseThreshold.muln(2);
+    const tierFeed = async (orb: Orb, rarityTier: number, signed: boolean) => {
+      const before = (await getRariAccount(userRariAccount)).amount;
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, rarityTier, authority, null)
+        .accounts(await feedAccounts(orb, { instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY }))
+        .preInstructions(signed ? [feedAuthorization(orb, feedSigner, expiry, rarityTier)] : [])
+        .rpc();
+      return (before - (await getRariAccount(userRariAccount)).amount).toString();
+    };
+
+    await mintRari(userRariAccount, baseThreshold.muln(4).toNumber());
+    await program.methods
+      .setFeedSigner(feedSigner.publicKey, false)
+      .accounts({ forgeState, authority })
+      .rpc();
+    await program.methods
+      .updateTierThresholds([tierOne, new anchor.BN(0), baseThreshold.muln(3), baseThreshold.muln(4)])
+      .accounts({ forgeState, authority })
+      .rpc();
+    try {
+      // A tier above zero must be vouched for by the backend
+      try {
+        await tierFeed(await createOrb(), 1, false);
+        expect.fail("Should have required a tier signature");
+      } catch (error) {
+        expect(error.message).to.include("InvalidFeedSignature");
+      }
+
+      expect(await tierFeed(await createOrb(), 1, true)).to.equal(tierOne.toString());
+      // An unset tier falls back to the base threshold
+      expect(await tierFeed(await createOrb(), 2, true)).to.equal(baseThreshold.toString());
+      expect(await tierFeed(await createOrb(), 0, false)).to.equal(baseThreshold.toString());
+
+      try {
+        await tierFeed(await createOrb(), 5, true);
+        expect.fail("Should have rejected an unknown tier");
+      } catch (error) {
+        expect(error.message).to.include("InvalidRarityTier");
+      }
+    } finally {
+      await program.methods
+        .updateTierThresholds([0, 0, 0, 0].map((threshold) => new anchor.BN(threshold)))
+        .accounts({ forgeState, authority })
+        .rpc();
+      await program.methods
+        .setFeedSigner(PublicKey.default, false)
+        .accounts({ forgeState, authority })
+        .rpc();
+    }
+  });
+
+  it("Feeds a seeded claim without charging the threshold", async () => {
+    const seed = async (orb: Orb, claimer: PublicKey) =>
+      program.methods
+        .seedClaim(orb.orbMint, claimer)
+        .accounts({
+          forgeState,
+          claimRecord: claimRecordFor(orb.orbMint),
+          authority,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    // A claim seeded for someone else cannot be fed
+    const otherOrb = await createOrb();
+    await seed(otherOrb, Keypair.generate().publicKey);
+    try {
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(otherOrb))
+        .rpc();
+      expect.fail("Should have rejected another claimer's seeded claim");
+    } catch (error) {
+      expect(error.message).to.include("SeededClaimerMismatch");
+    }
+
+    const orb = await createOrb();
+    await seed(orb, authority);
+    const balanceBefore = (await getRariAccount(userRariAccount)).amount;
+    const supplyBefore = (await provider.connection.getTokenSupply(rariMint)).value.amount;
+
+    await program.methods
+      .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(orb))
+      .rpc();
+
+    // No burn and no treasury transfer
+    expect((await getRariAccount(userRariAccount)).amount).to.equal(balanceBefore);
+    expect((await provider.connection.getTokenSupply(rariMint)).value.amount).to.equal(
+      supplyBefore
+    );
+    const record = await program.account.claimRecord.fetch(claimRecordFor(orb.orbMint));
+    expect(record.prefunded).to.equal(true);
+    expect(record.feedCount).to.equal(1);
+
+    // The prefunded feed is spent
+    try {
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(orb))
+        .rpc();
+      expect.fail("Should have rejected a second feed");
+    } catch (error) {
+      expect(error.message).to.include("OrbAlreadyClaimed");
+    }
+  });
+
+  it("Rotates the Wormhole emitter for outbound messages", async () => {
+    const { emitterSeedVersion } = await program.account.forgeState.fetch(forgeState);
+    const previousEmitter = emitterFor(emitterSeedVersion);
+
+    const nonAuthority = Keypair.generate();
+    try {
+      await program.methods
+        .rotateEmitter()
+        .accounts({ forgeState, authority: nonAuthority.publicKey })
+        .signers([nonAuthority])
+        .rpc();
+      expect.fail("Should have failed with a non-authority signer");
+    } catch (error) {
+      expect(error.message).to.include("ConstraintHasOne");
+    }
+
+    await program.methods
+      .rotateEmitter()
+      .accounts({ forgeState, authority })
+      .rpc();
+    const rotated = await program.account.forgeState.fetch(forgeState);
+    expect(rotated.emitterSeedVersion).to.equal(emitterSeedVersion + 1);
+
+    // The next cross-chain feed posts from the new emitter, starting a fresh sequence
+    const chainId = 8453;
+    const accounts = await feedAccounts(await createOrb(), {}, chainId);
+    expect(accounts.wormholeEmitter.equals(previousEmitter)).to.be.false;
+    await program.methods
+      .feedOrb(chainId, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(accounts)
+      .rpc();
+
+    const posted = await getPostedMessage(provider.connection, accounts.wormholeMessage);
+    expect(posted.message.emitterAddress.equals(accounts.wormholeEmitter.toBuffer())).to.be.true;
+    expect(posted.message.sequence.toString()).to.equal("0");
+  });
+
+  it("Stops re-feeding an orb at the per-orb feed limit", async () => {
+    const orb = await createOrb();
+    await program.methods
+      .updateMaxFeedsPerOrb(2)
+      .accounts({ forgeState, authority })
+      .rpc();
+    try {
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(orb))
+        .rpc();
+      await program.methods
+        .refeedOrb(1)
+        .accounts(await feedAccounts(orb))
+        .rpc();
+
+      try {
+        await program.methods
+          .refeedOrb(1)
+          .accounts(await feedAccounts(orb))
+          .rpc();
+        expect.fail("Should have rejected a feed past the limit");
+      } catch (error) {
+        expect(error.message).to.include("OrbFeedLimitReached");
+      }
+      const record = await program.account.claimRecord.fetch(claimRecordFor(orb.orbMint));
+      expect(record.feedCount).to.equal(2);
+    } finally {
+      await program.methods
+        .updateMaxFeedsPerOrb(0)
+        .accounts({ forgeState, authority })
+        .rpc();
+    }
+  });
+
+  it("Discounts the threshold for governance NFT holders", async () => {
+    const { nft: govCollection } = await metaplex.nfts().create({
+      name: "Orb Council",
+      uri: "https://arweave.net/council.json",
+      sellerFeeBasisPoints: 0,
+      isCollection: true,
+    });
+    const govNft = await createOrb(govCollection.address);
+    const govProof = (nft: Orb) => ({
+      govMetadata: nft.orbMetadata,
+      govTokenAccount: getAssociatedTokenAddressSync(nft.orbMint, authority),
+    });
+    const { rariThreshold } = await program.account.forgeState.fetch(forgeState);
+
+    await program.methods
+      .updateDiscount(govCollection.address, 2500)
+      .accounts({ forgeState, authority })
+      .rpc();
+    try {
+      // An NFT outside the discount collection, or a half-given proof, is refused
+      const outsider = await createOrb();
+      for (const overrides of [govProof(outsider), { govMetadata: govNft.orbMetadata }]) {
+        try {
+          await program.methods
+            .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+            .accounts(await feedAccounts(await createOrb(), overrides))
+            .rpc();
+          expect.fail("Should have rejected the discount proof");
+        } catch (error) {
+          expect(error.message).to.include("InvalidDiscountProof");
+        }
+      }
+
+      const balanceBefore = (await getRariAccount(userRariAccount)).amount;
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb(), govProof(govNft)))
+        .rpc();
+      const charged = balanceBefore - (await getRariAccount(userRariAccount)).amount;
+      expect(charged.toString()).to.equal(rariThreshold.muln(3).divn(4).toString());
+    } finally {
+      await program.methods
+        .updateDiscount(PublicKey.default, 0)
+        .accounts({ forgeState, authority })
+        .rpc();
+    }
+  });
+
+  it("Prefixes operational logs for the log pipeline", async () => {
+    const orb = await createOrb();
+    const tx = await program.methods
+      .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(orb))
+      .rpc({ commitment: "confirmed" });
+    const txInfo = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+
+    const { totalClaimed } = await program.account.forgeState.fetch(forgeState);
+    const feedLogs = txInfo.meta.logMessages.filter((log) =>
+      log.startsWith("Program log: [FORGE][FEED] ")
+    );
+    expect(feedLogs).to.have.length(1);
+    expect(feedLogs[0]).to.include(`orb=${orb.orbMint.toBase58()}`);
+    expect(feedLogs[0]).to.include(`total_claimed=${totalClaimed.toString()}`);
+  });
+
+  it("Records each claim's burn and refunds only an over-burn", async () => {
+    const orb = await createOrb();
+    await program.methods
+      .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(orb))
+      .rpc();
+
+    const { rariThreshold, burnBps } = await program.account.forgeState.fetch(forgeState);
+    const claimRecord = claimRecordFor(orb.orbMint);
+    const record = await program.account.claimRecord.fetch(claimRecord);
+    expect(record.rariBurned.toString()).to.equal(
+      rariThreshold.muln(burnBps).divn(10000).toString()
+    );
+
+    const refundAccounts = {
+      forgeState,
+      claimRecord,
+      chainConfig: chainConfigFor(1),
+      rariMint,
+      claimerRariAccount: userRariAccount,
+      tokenProgram: RARI_TOKEN_PROGRAM_ID,
+    };
+    const nonAuthority = Keypair.generate();
+    try {
+      await program.methods
+        .refundClaim()
+        .accounts({ ...refundAccounts, authority: nonAuthority.publicKey })
+        .signers([nonAuthority])
+        .rpc();
+      expect.fail("Should have failed with a non-authority signer");
+    } catch (error) {
+      expect(error.message).to.include("ConstraintHasOne");
+    }
+
+    // Fed at the current threshold, so there is nothing to give back
+    try {
+      await program.methods
+        .refundClaim()
+        .accounts({ ...refundAccounts, authority })
+        .rpc();
+      expect.fail("Should have found nothing to refund");
+    } catch (error) {
+      expect(error.message).to.include("NothingToRefund");
+    }
+  });
+
+  it("Applies a proposed threshold only after its timelock", async () => {
+    const { rariThreshold } = await program.account.forgeState.fetch(forgeState);
+    const proposed = rariThreshold.muln(2);
+    await program.methods
+      .updateTimelock(new anchor.BN(3))
+      .accounts({ forgeState, authority })
+      .rpc();
+
+    try {
+      // Direct changes are refused while a timelock is set
+      try {
+        await program.methods
+          .updateThreshold(proposed)
+          .accounts({ forgeState, rariMint, authority })
+          .rpc();
+        expect.fail("Should have required propose_threshold");
+      } catch (error) {
+        expect(error.message).to.include("ThresholdTimelocked");
+      }
+
+      await program.methods
+        .proposeThreshold(proposed)
+        .accounts({ forgeState, rariMint, authority })
+        .rpc();
+      const queued = await program.account.forgeState.fetch(forgeState);
+      expect(queued.pendingThreshold.toString()).to.equal(proposed.toString());
+      expect(queued.thresholdEffectiveAt.toNumber()).to.be.greaterThan(0);
+
+      const { events } = await program.methods
+        .getStats()
+        .accounts({ forgeState })
+        .simulate();
+      const snapshot = events.find((event) => event.name === "forgeStatsSnapshot");
+      expect(snapshot.data.pendingThreshold.toString()).to.equal(proposed.toString());
+      expect(snapshot.data.thresholdEffectiveAt.toNumber()).to.equal(
+        queued.thresholdEffectiveAt.toNumber()
+      );
+
+      try {
+        await program.methods.applyThreshold().accounts({ forgeState, authority }).rpc();
+        expect.fail("Should have waited out the timelock");
+      } catch (error) {
+        expect(error.message).to.include("ThresholdTimelockActive");
+      }
+
+      // Feeds keep paying the active threshold until the change is applied
+      const supplyBefore = (await provider.connection.getTokenSupply(rariMint)).value.amount;
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb()))
+        .rpc();
+      const supplyAfter = (await provider.connection.getTokenSupply(rariMint)).value.amount;
+      expect(BigInt(supplyBefore) - BigInt(supplyAfter)).to.equal(
+        BigInt(rariThreshold.toString())
+      );
+
+      await sleep(4000);
+      await program.methods.applyThreshold().accounts({ forgeState, authority }).rpc();
+      const applied = await program.account.forgeState.fetch(forgeState);
+      expect(applied.rariThreshold.toString()).to.equal(proposed.toString());
+      expect(applied.thresholdEffectiveAt.toNumber()).to.equal(0);
+    } finally {
+      await program.methods
+        .updateTimelock(new anchor.BN(0))
+        .accounts({ forgeState, authority })
+        .rpc();
+      await program.methods
+        .updateThreshold(rariThreshold)
+        .accounts({ forgeState, rariMint, authority })
+        .rpc();
+    }
+  });
+
+  it("Reports an already-claimed Orb with when it was claimed", async () => {
+    const orb = await createOrb();
+    await program.methods
+      .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(orb))
+      .rpc();
+    const { claimedAt } = await program.account.claimRecord.fetch(claimRecordFor(orb.orbMint));
+
+    const { claimRecord, orbMint, orbMetadata, chainConfig, wormholeMessage, ...shared } =
+      await feedAccounts(orb);
+    try {
+      await program.methods
+        .feedOrbsBatch([1], [[]])
+        .accounts(shared)
+        .remainingAccounts([
+          { pubkey: orb.orbMint, isSigner: false, isWritable: false },
+          { pubkey: orb.orbMetadata, isSigner: false, isWritable: false },
+          { pubkey: claimRecord, isSigner: false, isWritable: true },
+          { pubkey: chainConfigFor(1), isSigner: false, isWritable: false },
+          { pubkey: denyEntryFor(authority), isSigner: false, isWritable: false },
+        ])
+        .rpc();
+      expect.fail("Should have rejected an Orb that was already claimed");
+    } catch (error) {
+      expect(error.message).to.include("OrbAlreadyClaimed");
+      expect(error.logs.join("\n")).to.include(
+        `[FORGE][CLAIM] orb ${orb.orbMint.toBase58()} already claimed at ${claimedAt.toString()}`
+      );
+    }
+  });
+
+  it("Lets an approved delegate feed for a custodial user without its signature", async () => {
+    const feeder = await createFeeder(1000);
+    const custodian = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(custodian.publicKey, 10 * LAMPORTS_PER_SOL)
+    );
+    const { rariThreshold } = await program.account.forgeState.fetch(forgeState);
+    const approve = (amount: anchor.BN) =>
+      provider.sendAndConfirm(
+        new Transaction().add(
+          createApproveInstruction(
+            feeder.rariAccount,
+            custodian.publicKey,
+            feeder.wallet.publicKey,
+            BigInt(amount.toString()),
+            [],
+            RARI_TOKEN_PROGRAM_ID
+          )
+        ),
+        [feeder.wallet]
+      );
+    // The custodian pays the fees; the user never signs
+    const feedAs = async (
+      orb: Orb,
+      delegate: Keypair | null,
+      beneficiary: PublicKey = feeder.wallet.publicKey
+    ) =>
+      program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, beneficiary, null)
+        .accounts(
+          await feedAccounts(orb, {
+            userRariAccount: feeder.rariAccount,
+            user: feeder.wallet.publicKey,
+            payer: custodian.publicKey,
+            delegate: delegate?.publicKey ?? null,
+          })
+        )
+        .signers(
+          delegate === null || delegate === custodian ? [custodian] : [custodian, delegate]
+        )
+        .rpc();
+
+    const orb = await createOrb(orbCollection, feeder.wallet.publicKey);
+    try {
+      await feedAs(orb, null);
+      expect.fail("Should have required the user's signature without a delegate");
+    } catch (error) {
+      expect(error.message).to.include("UserSignatureMissing");
+    }
+
+    // The allowance must cover the whole threshold
+    await approve(rariThreshold.subn(1));
+    try {
+      await feedAs(orb, custodian);
+      expect.fail("Should have rejected a short allowance");
+    } catch (error) {
+      expect(error.message).to.include("InsufficientDelegatedAmount");
+    }
+
+    await approve(rariThreshold);
+    try {
+      await feedAs(orb, Keypair.generate());
+      expect.fail("Should have rejected a signer that is not the delegate");
+    } catch (error) {
+      expect(error.message).to.include("InvalidDelegate");
+    }
+
+    try {
+      await feedAs(orb, custodian, custodian.publicKey);
+      expect.fail("Should have kept the delegate from crediting itself");
+    } catch (error) {
+      expect(error.message).to.include("DelegateBeneficiaryMismatch");
+    }
+
+    const balanceBefore = (await getRariAccount(feeder.rariAccount)).amount;
+    await feedAs(orb, custodian);
+    const account = await getRariAccount(feeder.rariAccount);
+    expect(balanceBefore - account.amount).to.equal(BigInt(rariThreshold.toString()));
+    expect(account.delegatedAmount).to.equal(BigInt(0));
+    const record = await program.account.claimRecord.fetch(claimRecordFor(orb.orbMint));
+    expect(record.claimer.toBase58()).to.equal(feeder.wallet.publicKey.toBase58());
+  });
+
+  it("Skips an uncallable event sink unless it is fatal", async () => {
+    const eventSink = Keypair.generate().publicKey;
+    await program.methods
+      .setEventSink(eventSink, false)
+      .accounts({ forgeState, authority })
+      .rpc();
+
+    try {
+      // Without the sink program the feed goes through and logs the skip
+      const tx = await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb()))
+        .rpc({ commitment: "confirmed" });
+      const txInfo = await provider.connection.getTransaction(tx, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      expect(txInfo.meta.logMessages.join("\n")).to.include(
+        `[FORGE][SINK] skipped ${eventSink.toBase58()}`
+      );
+
+      await program.methods
+        .setEventSink(eventSink, true)
+        .accounts({ forgeState, authority })
+        .rpc();
+      try {
+        await program.methods
+          .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+          .accounts(await feedAccounts(await createOrb()))
+          .rpc();
+        expect.fail("Should have failed without the fatal sink");
+      } catch (error) {
+        expect(error.message).to.include("InvalidEventSink");
+      }
+    } finally {
+      await program.methods
+        .setEventSink(null, false)
+        .accounts({ forgeState, authority })
+        .rpc();
+    }
+  });
+
+  it("Credits a gifted feed to the beneficiary", async () => {
+    const eventParser = new anchor.EventParser(program.programId, program.coder);
+    const feeder = await createFeeder(1000);
+    const friend = Keypair.generate().publicKey;
+    const orb = await createOrb(orbCollection, feeder.wallet.publicKey);
+    const { rariThreshold } = await program.account.forgeState.fetch(forgeState);
+
+    const balanceBefore = (await getRariAccount(feeder.rariAccount)).amount;
+    const tx = await program.methods
+      .feedOrb(1, [], NO_MAX_BURN, 0, friend, null)
+      .accounts(
+        await feedAccounts(orb, {
+          userRariAccount: feeder.rariAccount,
+          user: feeder.wallet.publicKey,
+          denyEntry: denyEntryFor(friend),
+        })
+      )
+      .signers([feeder.wallet])
+      .rpc({ commitment: "confirmed" });
+
+    // The feeder pays and is rate limited, the friend holds the claim
+    const account = await getRariAccount(feeder.rariAccount);
+    expect(balanceBefore - account.amount).to.equal(BigInt(rariThreshold.toString()));
+    const record = await program.account.claimRecord.fetch(claimRecordFor(orb.orbMint));
+    expect(record.claimer.toBase58()).to.equal(friend.toBase58());
+    const stats = await program.account.feederStats.fetch(feederStatsFor(feeder.wallet.publicKey));
+    expect(stats.lastFeedAt.toNumber()).to.be.greaterThan(0);
+    expect(await provider.connection.getAccountInfo(feederStatsFor(friend))).to.be.null;
+
+    const txInfo = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const fed = [...eventParser.parseLogs(txInfo.meta.logMessages)].find(
+      (event) => event.name === "orbFedEvent"
+    ).data;
+    expect(fed.payer.toBase58()).to.equal(feeder.wallet.publicKey.toBase58());
+    expect(fed.claimer.toBase58()).to.equal(friend.toBase58());
+  });
+
+  it("Grants the early bonus to the first feeds only", async () => {
+    const bonusMint = await createMint(
+      provider.connection,
+      provider.wallet.payer,
+      forgeState,
+      null,
+      0
+    );
+    const claimerBonusAccount = await createAccount(
+      provider.connection,
+      provider.wallet.payer,
+      bonusMint,
+      authority
+    );
+    const bonusAccounts = {
+      earlyBonusMint: bonusMint,
+      claimerBonusAccount,
+      bonusTokenProgram: TOKEN_PROGRAM_ID,
+    };
+    const bonusBalance = async () =>
+      (await getAccount(provider.connection, claimerBonusAccount)).amount;
+
+    // Only the next feed is still early
+    const { totalClaimed } = await program.account.forgeState.fetch(forgeState);
+    const earlyBonusCount = totalClaimed.addn(1);
+    await program.methods
+      .setEarlyBonus(earlyBonusCount, new anchor.BN(50))
+      .accounts({ forgeState, earlyBonusMint: bonusMint, authority })
+      .rpc();
+
+    try {
+      try {
+        await program.methods
+          .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+          .accounts(await feedAccounts(await createOrb()))
+          .rpc();
+        expect.fail("Should have required the bonus accounts");
+      } catch (error) {
+        expect(error.message).to.include("BonusAccountsMissing");
+      }
+
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb(), bonusAccounts))
+        .rpc();
+      expect(await bonusBalance()).to.equal(BigInt(50));
+
+      // The (early_bonus_count + 1)-th feed gets nothing
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb(), bonusAccounts))
+        .rpc();
+      expect(await bonusBalance()).to.equal(BigInt(50));
+      const state = await program.account.forgeState.fetch(forgeState);
+      expect(state.totalClaimed.toNumber()).to.equal(earlyBonusCount.addn(1).toNumber());
+    } finally {
+      await program.methods
+        .setEarlyBonus(new anchor.BN(0), new anchor.BN(0))
+        .accounts({ forgeState, earlyBonusMint: null, authority })
+        .rpc();
+    }
+  });
+
+  it("Leaves a current claim record alone when migrating it", async () => {
+    const orb = await createOrb();
+    await program.methods
+      .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(orb))
+      .rpc();
+    const claimRecord = claimRecordFor(orb.orbMint);
+    const before = await provider.connection.getAccountInfo(claimRecord);
+    expect(before.data.readUInt8(8)).to.equal(2);
+
+    const stranger = Keypair.generate();
+    try {
+      await program.methods
+        .migrateClaim(orb.orbMint)
+        .accounts({ forgeState, claimRecord, claimer: stranger.publicKey })
+        .signers([stranger])
+        .rpc();
+      expect.fail("Should have required the claimer");
+    } catch (error) {
+      expect(error.message).to.include("ConstraintHasOne");
+    }
+
+    await program.methods
+      .migrateClaim(orb.orbMint)
+      .accounts({ forgeState, claimRecord, claimer: authority })
+      .rpc();
+    const after = await provider.connection.getAccountInfo(claimRecord);
+    expect(after.data.length).to.equal(program.account.claimRecord.size);
+    expect(after.data.equals(before.data)).to.be.true;
+    expect(after.lamports).to.equal(before.lamports);
+  });
+
+  it("Commits to each feed with a burn proof", async () => {
+    const eventParser = new anchor.EventParser(program.programId, program.coder);
+    const orb = await createOrb();
+    const tx = await program.methods
+      .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(orb))
+      .rpc({ commitment: "confirmed" });
+    const txInfo = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const fed = [...eventParser.parseLogs(txInfo.meta.logMessages)].find(
+      (event) => event.name === "orbFedEvent"
+    ).data;
+    const record = await program.account.claimRecord.fetch(claimRecordFor(orb.orbMint));
+
+    // keccak(orb_mint || claimer || rari_burned || claimed_at || target_chain)
+    const expected = Buffer.from(
+      keccak_256.arrayBuffer(
+        Buffer.concat([
+          orb.orbMint.toBuffer(),
+          authority.toBuffer(),
+          fed.rariBurned.toArrayLike(Buffer, "le", 8),
+          record.claimedAt.toTwos(64).toArrayLike(Buffer, "le", 8),
+          new anchor.BN(1).toArrayLike(Buffer, "le", 2),
+        ])
+      )
+    );
+    expect(Buffer.from(fed.burnProof).equals(expected)).to.be.true;
+    expect(Buffer.from(record.burnProof).equals(expected)).to.be.true;
+  });
+
+  it("Resumes feeding once a timed pause expires", async () => {
+    await program.methods
+      .pauseFor(new anchor.BN(2))
+      .accounts({ forgeState, signer: authority })
+      .rpc();
+
+    try {
+      try {
+        await program.methods
+          .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+          .accounts(await feedAccounts(await createOrb()))
+          .rpc();
+        expect.fail("Should have failed during the pause");
+      } catch (error) {
+        expect(error.message).to.include("ProgramPaused");
+      }
+
+      // Past the expiry the flag is still set, but feeds go through
+      await sleep(3000);
+      const { pauseFlags, pauseExpiresAt } = await program.account.forgeState.fetch(forgeState);
+      expect(pauseFlags).to.equal(1);
+      expect(pauseExpiresAt.toNumber()).to.be.greaterThan(0);
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb()))
+        .rpc();
+
+      // A zero duration holds until unpaused by hand
+      await program.methods
+        .pauseFor(new anchor.BN(0))
+        .accounts({ forgeState, signer: authority })
+        .rpc();
+      await sleep(3000);
+      try {
+        await program.methods
+          .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+          .accounts(await feedAccounts(await createOrb()))
+          .rpc();
+        expect.fail("Should have failed during the indefinite pause");
+      } catch (error) {
+        expect(error.message).to.include("ProgramPaused");
+      }
+    } finally {
+      await program.methods
+        .setPauseFlags(0)
+        .accounts({ forgeState, signer: authority })
+        .rpc();
+    }
+  });
+
+  it("Hosts a second forge under its own namespace", async () => {
+    const namespace = [...Buffer.from("partner-forge-01")];
+    const [partnerForgeState] = PublicKey.findProgramAddressSync(
+      [Buffer.from("forge_state"), Buffer.from(namespace)],
+      program.programId
+    );
+    const [partnerPendingQueue] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pending_queue"), Buffer.from(namespace)],
+      program.programId
+    );
+    expect(partnerForgeState.equals(forgeState)).to.be.false;
+    expect(partnerPendingQueue.equals(pendingQueue)).to.be.false;
+    const before = await program.account.forgeState.fetch(forgeState);
+
+    await program.methods
+      .initialize({
+        wormholeBridge,
+        rariMint,
+        rariThreshold: new anchor.BN(5),
+        approvedCollection: orbCollection,
+        treasury: treasuryTokenAccount,
+        burnBps: 5000,
+        requiredCreator: PublicKey.default,
+        allowFreeFeed: false,
+        startTs: new anchor.BN(0),
+        endTs: new anchor.BN(0),
+        pendingQueueSize: 8,
+        namespace,
+      })
+      .accounts({
+        forgeState: partnerForgeState,
+        pendingQueue: partnerPendingQueue,
+        rariMint,
+        authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const partner = await program.account.forgeState.fetch(partnerForgeState);
+    expect(partner.namespace).to.deep.equal(namespace);
+    expect(partner.rariThreshold.toNumber()).to.equal(5);
+    expect(partner.totalClaimed.toNumber()).to.equal(0);
+    const queue = await program.account.pendingQueue.fetch(partnerPendingQueue);
+    expect(queue.capacity).to.equal(8);
+
+    // The original forge is untouched
+    const after = await program.account.forgeState.fetch(forgeState);
+    expect(after.namespace).to.deep.equal(DEFAULT_NAMESPACE);
+    expect(after.rariThreshold.toString()).to.equal(before.rariThreshold.toString());
+    expect(after.burnBps).to.equal(before.burnBps);
+  });
+
+  it("Skips the SOL fee for same-chain feeds when configured", async () => {
+    const feeRecipient = Keypair.generate().publicKey;
+    await program.methods
+      .updateSolFee(new anchor.BN(1_000_000), feeRecipient)
+      .accounts({ forgeState, authority })
+      .rpc();
+    await program.methods
+      .setChargeSolFeeSameChain(false)
+      .accounts({ forgeState, authority })
+      .rpc();
+
+    try {
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb(), { feeRecipient }))
+        .rpc();
+      expect(await provider.connection.getBalance(feeRecipient)).to.equal(0);
+    } finally {
+      await program.methods
+        .setChargeSolFeeSameChain(true)
+        .accounts({ forgeState, authority })
+        .rpc();
+      await program.methods
+        .updateSolFee(new anchor.BN(0), authority)
+        .accounts({ forgeState, authority })
+        .rpc();
+    }
+  });
+
+  it("Reports the Wormhole fee paid by cross-chain feeds", async () => {
+    const eventParser = new anchor.EventParser(program.programId, program.coder);
+    const fedEvent = async (chainId: number) => {
+      const tx = await program.methods
+        .feedOrb(chainId, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb(), {}, chainId))
+        .rpc({ commitment: "confirmed" });
+      const txInfo = await provider.connection.getTransaction(tx, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      return [...eventParser.parseLogs(txInfo.meta.logMessages)].find(
+        (event) => event.name === "orbFedEvent"
+      ).data;
+    };
+    // BridgeData: guardian_set_index u32, last_lamports u64, expiration u32, fee u64
+    const bridge = await provider.connection.getAccountInfo(wormholeBridge);
+    const bridgeFee = bridge.data.readBigUInt64LE(16).toString();
+
+    expect((await fedEvent(8453)).wormholeFee.toString()).to.equal(bridgeFee);
+    expect((await fedEvent(1)).wormholeFee.toNumber()).to.equal(0);
+  });
+
+  it("Sweeps stray tokens but never the treasury", async () => {
+    const strayMint = await createMint(
+      provider.connection,
+      provider.wallet.payer,
+      authority,
+      null,
+      6
+    );
+    const strayAccount = await createAccount(
+      provider.connection,
+      provider.wallet.payer,
+      strayMint,
+      forgeState,
+      Keypair.generate()
+    );
+    const destination = await createAccount(
+      provider.connection,
+      provider.wallet.payer,
+      strayMint,
+      authority
+    );
+    await mintTo(
+      provider.connection,
+      provider.wallet.payer,
+      strayMint,
+      strayAccount,
+      authority,
+      5_000
+    );
+
+    await program.methods
+      .sweepTokens(new anchor.BN(5_000))
+      .accounts({
+        forgeState,
+        mint: strayMint,
+        source: strayAccount,
+        destination,
+        authority,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    expect(Number((await getAccount(provider.connection, strayAccount)).amount)).to.equal(0);
+    expect(Number((await getAccount(provider.connection, destination)).amount)).to.equal(5_000);
+
+    try {
+      await program.methods
+        .sweepTokens(new anchor.BN(1))
+        .accounts({
+          forgeState,
+          mint: rariMint,
+          source: treasuryTokenAccount,
+          destination: userRariAccount,
+          authority,
+          tokenProgram: RARI_TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      expect.fail("Should have refused to sweep the treasury");
+    } catch (error) {
+      expect(error.message).to.include("ProtectedAccount");
+    }
+  });
+
+  it("Bounds the clock skew tolerance", async () => {
+    try {
+      await program.methods
+        .setClockSkewTolerance(new anchor.BN(61))
+        .accounts({ forgeState, authority })
+        .rpc();
+      expect.fail("Should have rejected a tolerance above the maximum");
+    } catch (error) {
+      expect(error.message).to.include("InvalidClockSkewTolerance");
+    }
+
+    await program.methods
+      .setClockSkewTolerance(new anchor.BN(5))
+      .accounts({ forgeState, authority })
+      .rpc();
+    try {
+      const state = await program.account.forgeState.fetch(forgeState);
+      expect(state.clockSkewTolerance.toNumber()).to.equal(5);
+    } finally {
+      await program.methods
+        .setClockSkewTolerance(new anchor.BN(0))
+        .accounts({ forgeState, authority })
+        .rpc();
+    }
+  });
+
+  it("Burns Orb tokens from supply-1 and supply-N Orbs", async () => {
+    const createSftOrb = async (supply: number): Promise<Orb> => {
+      const { sft } = await metaplex.nfts().createSft({
+        name: "Orb",
+        uri: "https://arweave.net/orb.json",
+        sellerFeeBasisPoints: 0,
+        collection: orbCollection,
+        collectionAuthority: provider.wallet.payer,
+        tokenOwner: authority,
+        tokenAmount: token(supply),
+      });
+      return { orbMint: sft.address, orbMetadata: sft.metadataAddress };
+    };
+    const orbBalance = async (orb: Orb) =>
+      Number(
+        (
+          await getAccount(
+            provider.connection,
+            getAssociatedTokenAddressSync(orb.orbMint, authority)
+          )
+        ).amount
+      );
+    const feedBurning = async (orb: Orb) =>
+      program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(orb, { orbTokenProgram: TOKEN_PROGRAM_ID }))
+        .rpc();
+
+    try {
+      // Supply 1: the NFT itself is burned alongside the RARI threshold
+      await program.methods
+        .setOrbBurn(new anchor.BN(1), false)
+        .accounts({ forgeState, authority })
+        .rpc();
+      const nft = await createOrb();
+      const rariBefore = (await getRariAccount(userRariAccount)).amount;
+      await feedBurning(nft);
+      expect(await orbBalance(nft)).to.equal(0);
+      expect((await getRariAccount(userRariAccount)).amount < rariBefore).to.be.true;
+
+      // Supply N: part of the holding is burned in place of RARI
+      await program.methods
+        .setOrbBurn(new anchor.BN(3), true)
+        .accounts({ forgeState, authority })
+        .rpc();
+      const sftOrb = await createSftOrb(5);
+      const rariBeforeSft = (await getRariAccount(userRariAccount)).amount;
+      await feedBurning(sftOrb);
+      expect(await orbBalance(sftOrb)).to.equal(2);
+      expect((await getRariAccount(userRariAccount)).amount).to.equal(rariBeforeSft);
+
+      try {
+        await feedBurning(await createSftOrb(2));
+        expect.fail("Should have required enough Orb tokens to burn");
+      } catch (error) {
+        expect(error.message).to.include("InsufficientOrbBalance");
+      }
+    } finally {
+      await program.methods
+        .setOrbBurn(new anchor.BN(0), false)
+        .accounts({ forgeState, authority })
+        .rpc();
+    }
+  });
+
+  it("Stamps one increasing seq into the events of each instruction", async () => {
+    const eventParser = new anchor.EventParser(program.programId, program.coder);
+    const feedEvents = async () => {
+      const tx = await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb()))
+        .rpc({ commitment: "confirmed" });
+      const txInfo = await provider.connection.getTransaction(tx, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      return [...eventParser.parseLogs(txInfo.meta.logMessages)];
+    };
+
+    const first = await feedEvents();
+    const second = await feedEvents();
+    const seqs = (events) => new Set(events.map((event) => event.data.seq.toNumber()));
+    // Every event of one feed shares its seq, and the next feed takes the next one
+    expect(first.length).to.be.greaterThan(1);
+    expect(seqs(first).size).to.equal(1);
+    expect(seqs(second).size).to.equal(1);
+    const [firstSeq] = seqs(first);
+    expect([...seqs(second)]).to.deep.equal([firstSeq + 1]);
+    const { eventSeq } = await program.account.forgeState.fetch(forgeState);
+    expect(eventSeq.toNumber()).to.equal(firstSeq + 1);
+  });
+
+  it("Feeds only staked Orbs while frozen Orbs are required", async () => {
+    const staker = Keypair.generate();
+    const orb = await createOrb();
+    const orbTokenAccount = getAssociatedTokenAddressSync(orb.orbMint, authority);
+    const feed = async () =>
+      program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(orb))
+        .rpc();
+
+    await program.methods
+      .setRequireFrozenOrb(true)
+      .accounts({ forgeState, authority })
+      .rpc();
+    try {
+      try {
+        await feed();
+        expect.fail("Should have rejected an unstaked Orb");
+      } catch (error) {
+        expect(error.message).to.include("OrbNotStaked");
+      }
+
+      // An external staking program freezes the Orb through its delegate
+      await provider.sendAndConfirm(
+        new Transaction().add(
+          createApproveInstruction(orbTokenAccount, staker.publicKey, authority, 1)
+        )
+      );
+      await metaplex.nfts().freezeDelegatedNft({
+        mintAddress: orb.orbMint,
+        delegateAuthority: staker,
+        tokenOwner: authority,
+      });
+      expect((await getAccount(provider.connection, orbTokenAccount)).isFrozen).to.be.true;
+
+      await feed();
+      const record = await program.account.claimRecord.fetch(claimRecordFor(orb.orbMint));
+      expect(record.claimer.equals(authority)).to.be.true;
+    } finally {
+      await program.methods
+        .setRequireFrozenOrb(false)
+        .accounts({ forgeState, authority })
+        .rpc();
+    }
+  });
+
+  it("Mints the partner share of each feed", async () => {
+    const partner = Keypair.generate().publicKey;
+    const rewardMint = await createMint(
+      provider.connection,
+      provider.wallet.payer,
+      forgeState,
+      null,
+      0
+    );
+    const partnerRewardAccount = await createAccount(
+      provider.connection,
+      provider.wallet.payer,
+      rewardMint,
+      partner
+    );
+    const partnerAccounts = {
+      partnerRewardMint: rewardMint,
+      partnerRewardAccount,
+      partnerTokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    try {
+      await program.methods
+        .setPartnerShare(partner, 10_001)
+        .accounts({ forgeState, partnerRewardMint: rewardMint, authority })
+        .rpc();
+      expect.fail("Should have rejected a share above 100%");
+    } catch (error) {
+      expect(error.message).to.include("InvalidBps");
+    }
+
+    await program.methods
+      .setPartnerShare(partner, 2_500)
+      .accounts({ forgeState, partnerRewardMint: rewardMint, authority })
+      .rpc();
+    try {
+      const { rariThreshold } = await program.account.forgeState.fetch(forgeState);
+      const expected = BigInt(rariThreshold.muln(2_500).divn(10_000).toString());
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb(), partnerAccounts))
+        .rpc();
+      expect((await getAccount(provider.connection, partnerRewardAccount)).amount).to.equal(
+        expected
+      );
+
+      try {
+        await program.methods
+          .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+          .accounts(await feedAccounts(await createOrb()))
+          .rpc();
+        expect.fail("Should have required the partner accounts");
+      } catch (error) {
+        expect(error.message).to.include("PartnerAccountsMissing");
+      }
+    } finally {
+      await program.methods
+        .setPartnerShare(PublicKey.default, 0)
+        .accounts({ forgeState, partnerRewardMint: null, authority })
+        .rpc();
+    }
+  });
+
+  it("Batch-updates chain configs atomically", async () => {
+    const chainIds = [20001, 20002, 20003, 20004, 20005];
+    const updates = chainIds.map((chainId, i) => ({
+      chainId,
+      threshold: new anchor.BN(1_000 * (i + 1)),
+      enabled: false,
+    }));
+    const configAccounts = chainIds.map((chainId) => ({
+      pubkey: chainConfigFor(chainId),
+      isSigner: false,
+      isWritable: true,
+    }));
+
+    try {
+      await program.methods
+        .batchSetChainConfigs(updates)
+        .accounts({ forgeState, authority, systemProgram: SystemProgram.programId })
+        .remainingAccounts([...configAccounts.slice(0, 4), configAccounts[0]])
+        .rpc();
+      expect.fail("Should have rejected a config account for the wrong chain");
+    } catch (error) {
+      expect(error.message).to.include("InvalidChainConfig");
+    }
+    for (const chainId of chainIds) {
+      expect(await provider.connection.getAccountInfo(chainConfigFor(chainId))).to.be.null;
+    }
+
+    try {
+      await program.methods
+        .batchSetChainConfigs(updates)
+        .accounts({ forgeState, authority, systemProgram: SystemProgram.programId })
+        .remainingAccounts(configAccounts.slice(0, 4))
+        .rpc();
+      expect.fail("Should have required one config account per update");
+    } catch (error) {
+      expect(error.message).to.include("InvalidBatchAccounts");
+    }
+
+    await program.methods
+      .batchSetChainConfigs(updates)
+      .accounts({ forgeState, authority, systemProgram: SystemProgram.programId })
+      .remainingAccounts(configAccounts)
+      .rpc();
+    try {
+      for (const [i, chainId] of chainIds.entries()) {
+        const config = await program.account.chainConfig.fetch(chainConfigFor(chainId));
+        expect(config.chainId).to.equal(chainId);
+        expect(config.threshold.toNumber()).to.equal(1_000 * (i + 1));
+        expect(config.enabled).to.be.false;
+      }
+    } finally {
+      for (const chainId of chainIds) {
+        await program.methods
+          .removeChainConfig(chainId)
+          .accounts({ forgeState, chainConfig: chainConfigFor(chainId), authority })
+          .rpc();
+      }
+    }
+  });
+
+  it("Auto-pauses feeds once a minute's feeds exceed the anomaly threshold", async () => {
+    const eventParser = new anchor.EventParser(program.programId, program.coder);
+    const autoPausedEvent = async () => {
+      const tx = await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb()))
+        .rpc({ commitment: "confirmed" });
+      const txInfo = await provider.connection.getTransaction(tx, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      return [...eventParser.parseLogs(txInfo.meta.logMessages)].find(
+        (event) => event.name === "autoPaused"
+      )?.data;
+    };
+
+    await program.methods
+      .setAnomalyThreshold(new anchor.BN(1))
+      .accounts({ forgeState, authority })
+      .rpc();
+    try {
+      // Earlier tests may already have fed this minute, tripping it at once
+      let event = await autoPausedEvent();
+      if (!event) {
+        event = await autoPausedEvent();
+      }
+      expect(event).to.not.be.undefined;
+      expect(event.anomalyThreshold.toNumber()).to.equal(1);
+      expect(event.feedsThisMinute.toNumber()).to.be.greaterThan(1);
+
+      const state = await program.account.forgeState.fetch(forgeState);
+      expect(state.pauseFlags & 1).to.equal(1);
+      expect(state.pauseExpiresAt.toNumber()).to.equal(0);
+      try {
+        await program.methods
+          .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+          .accounts(await feedAccounts(await createOrb()))
+          .rpc();
+        expect.fail("Should have failed while auto-paused");
+      } catch (error) {
+        expect(error.message).to.include("ProgramPaused");
+      }
+    } finally {
+      await program.methods
+        .setAnomalyThreshold(new anchor.BN(0))
+        .accounts({ forgeState, authority })
+        .rpc();
+      await program.methods
+        .setPauseFlags(0)
+        .accounts({ forgeState, signer: authority })
+        .rpc();
+    }
+  });
+
+  it("Routes payments through a Token-2022 transfer hook", async () => {
+    const mint = Keypair.generate();
+    const space = getMintLen([ExtensionType.TransferHook]);
+    const lamports = await provider.connection.getMinimumBalanceForRentExemption(space);
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: authority,
+          newAccountPubkey: mint.publicKey,
+          space,
+          lamports,
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferHookInstruction(
+          mint.publicKey,
+          authority,
+          NOOP_PROGRAM_ID,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(mint.publicKey, 6, authority, null, TOKEN_2022_PROGRAM_ID)
+      ),
+      [mint]
+    );
+    const hookedMint = mint.publicKey;
+    const userHookedAccount = await createAccount(
+      provider.connection,
+      provider.wallet.payer,
+      hookedMint,
+      authority,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const hookedTreasury = await createAccount(
+      provider.connection,
+      provider.wallet.payer,
+      hookedMint,
+      forgeState,
+      Keypair.generate(),
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await mintTo(
+      provider.connection,
+      provider.wallet.payer,
+      hookedMint,
+      userHookedAccount,
+      authority,
+      100_000_000,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const [paymentMintConfig] = PublicKey.findProgramAddressSync(
+      [Buffer.from("payment"), hookedMint.toBuffer()],
+      program.programId
+    );
+    const hookedAccounts = {
+      rariMint: hookedMint,
+      paymentMintConfig,
+      userRariAccount: userHookedAccount,
+      treasuryTokenAccount: hookedTreasury,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    };
+    // The mock hook has no extra-account-metas account, so it lists no extras
+    const hookAccounts = [
+      { pubkey: NOOP_PROGRAM_ID, isSigner: false, isWritable: false },
+      {
+        pubkey: getExtraAccountMetaAddress(hookedMint, NOOP_PROGRAM_ID),
+        isSigner: false,
+        isWritable: false,
+      },
+    ];
+
+    const threshold = new anchor.BN(10_000_000);
+    await program.methods
+      .addPaymentMint(threshold)
+      .accounts({
+        forgeState,
+        mint: hookedMint,
+        paymentMintConfig,
+        authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    // Route half the threshold to the treasury so the payment is transferred
+    await program.methods
+      .updateFeeSplit(5000)
+      .accounts({ forgeState, authority })
+      .rpc();
+    try {
+      try {
+        await program.methods
+          .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+          .accounts(await feedAccounts(await createOrb(), hookedAccounts))
+          .rpc();
+        expect.fail("Should have required the transfer hook accounts");
+      } catch (error) {
+        expect(error.message).to.include("TransferHookAccountsMissing");
+      }
+
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb(), hookedAccounts))
+        .remainingAccounts(hookAccounts)
+        .rpc();
+      const treasury = await getAccount(
+        provider.connection,
+        hookedTreasury,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect(treasury.amount).to.equal(BigInt(threshold.divn(2).toString()));
+    } finally {
+      await program.methods
+        .updateFeeSplit(10000)
+        .accounts({ forgeState, authority })
+        .rpc();
+      await program.methods
+        .disablePaymentMint()
+        .accounts({ forgeState, paymentMintConfig, authority })
+        .rpc();
+    }
+  });
+
+  it("Round-trips a feed memo through the claim record and event", async () => {
+    const eventParser = new anchor.EventParser(program.programId, program.coder);
+    const feedWithMemo = async (orb: Orb, memo: number[] | null) => {
+      const tx = await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, memo)
+        .accounts(await feedAccounts(orb))
+        .rpc({ commitment: "confirmed" });
+      const txInfo = await provider.connection.getTransaction(tx, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      return [...eventParser.parseLogs(txInfo.meta.logMessages)].find(
+        (event) => event.name === "orbFedEvent"
+      ).data;
+    };
+    const memoOf = (bytes: Buffer) => {
+      const memo = Buffer.alloc(32);
+      bytes.copy(memo);
+      return [...memo];
+    };
+
+    const campaign = memoOf(Buffer.from("campaign:spring-drop"));
+    const orb = await createOrb();
+    const event = await feedWithMemo(orb, campaign);
+    expect(event.memo).to.deep.equal(campaign);
+    const record = await program.account.claimRecord.fetch(claimRecordFor(orb.orbMint));
+    expect(record.memo).to.deep.equal(campaign);
+
+    // An absent memo is zero-filled
+    const untagged = await feedWithMemo(await createOrb(), null);
+    expect(untagged.memo).to.deep.equal(new Array(32).fill(0));
+
+    // Opaque bytes pass until memos are required to be text
+    const binary = memoOf(Buffer.from([0xff, 0xfe, 0x00, 0x01]));
+    await feedWithMemo(await createOrb(), binary);
+    await program.methods.setMemoIsText(true).accounts({ forgeState, authority }).rpc();
+    try {
+      try {
+        await feedWithMemo(await createOrb(), binary);
+        expect.fail("Should have rejected a memo that is not UTF-8");
+      } catch (error) {
+        expect(error.message).to.include("InvalidMemo");
+      }
+      expect((await feedWithMemo(await createOrb(), campaign)).memo).to.deep.equal(campaign);
+    } finally {
+      await program.methods.setMemoIsText(false).accounts({ forgeState, authority }).rpc();
+    }
+  });
+
+  it("Seeds a new forge's counters from a legacy forge once", async () => {
+    const namespace = [...Buffer.from("migrated-forge01")];
+    const [migratedForgeState] = PublicKey.findProgramAddressSync(
+      [Buffer.from("forge_state"), Buffer.from(namespace)],
+      program.programId
+    );
+    const [migratedPendingQueue] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pending_queue"), Buffer.from(namespace)],
+      program.programId
+    );
+    await program.methods
+      .initialize({
+        wormholeBridge,
+        rariMint,
+        rariThreshold: new anchor.BN(5),
+        approvedCollection: orbCollection,
+        treasury: treasuryTokenAccount,
+        burnBps: 10000,
+        requiredCreator: PublicKey.default,
+        allowFreeFeed: false,
+        startTs: new anchor.BN(0),
+        endTs: new anchor.BN(0),
+        pendingQueueSize: 8,
+        namespace,
+      })
+      .accounts({
+        forgeState: migratedForgeState,
+        pendingQueue: migratedPendingQueue,
+        rariMint,
+        authority,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const stranger = Keypair.generate();
+    try {
+      await program.methods
+        .setCounters(new anchor.BN(1), new anchor.BN(1), new anchor.BN(1))
+        .accounts({ forgeState: migratedForgeState, authority: stranger.publicKey })
+        .signers([stranger])
+        .rpc();
+      expect.fail("Should have required the authority");
+    } catch (error) {
+      expect(error.message).to.include("ConstraintHasOne");
+    }
+
+    const tx = await program.methods
+      .setCounters(new anchor.BN(1_234), new anchor.BN(56_000), new anchor.BN(1_300))
+      .accounts({ forgeState: migratedForgeState, authority })
+      .rpc({ commitment: "confirmed" });
+    const txInfo = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const seeded = [
+      ...new anchor.EventParser(program.programId, program.coder).parseLogs(
+        txInfo.meta.logMessages
+      ),
+    ].find((event) => event.name === "countersSeeded").data;
+    expect(seeded.totalClaimed.toNumber()).to.equal(1_234);
+    expect(seeded.totalRariBurned.toNumber()).to.equal(56_000);
+    expect(seeded.nextClaimIndex.toNumber()).to.equal(1_300);
+
+    const migrated = await program.account.forgeState.fetch(migratedForgeState);
+    expect(migrated.countersSeeded).to.be.true;
+    expect(migrated.totalClaimed.toNumber()).to.equal(1_234);
+    expect(migrated.totalRariBurned.toNumber()).to.equal(56_000);
+    expect(migrated.nextClaimIndex.toNumber()).to.equal(1_300);
+
+    try {
+      await program.methods
+        .setCounters(new anchor.BN(0), new anchor.BN(0), new anchor.BN(0))
+        .accounts({ forgeState: migratedForgeState, authority })
+        .rpc();
+      expect.fail("Should have refused to seed the counters twice");
+    } catch (error) {
+      expect(error.message).to.include("CountersAlreadySeeded");
+    }
+  });
+
+  it("Accrues loyalty points across feeds and redeems them", async () => {
+    const feeder = await createFeeder(1000);
+    const user = feeder.wallet.publicKey;
+    const pointsAccount = pointsAccountFor(user);
+    const feedAs = async (overrides: Record<string, PublicKey | null>) =>
+      program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, user, null)
+        .accounts(
+          await feedAccounts(await createOrb(orbCollection, user), {
+            user,
+            userRariAccount: feeder.rariAccount,
+            ...overrides,
+          })
+        )
+        .signers([feeder.wallet])
+        .rpc();
+
+    await program.methods
+      .setPointsPerFeed(new anchor.BN(25))
+      .accounts({ forgeState, authority })
+      .rpc();
+    try {
+      try {
+        await feedAs({});
+        expect.fail("Should have required the points account");
+      } catch (error) {
+        expect(error.message).to.include("PointsAccountMissing");
+      }
+
+      for (let i = 0; i < 3; i++) {
+        await feedAs({ pointsAccount });
+      }
+      expect((await program.account.pointsAccount.fetch(pointsAccount)).points.toNumber()).to.equal(
+        75
+      );
+
+      const tx = await program.methods
+        .redeemPoints()
+        .accounts({ forgeState, pointsAccount, user })
+        .signers([feeder.wallet])
+        .rpc({ commitment: "confirmed" });
+      const txInfo = await provider.connection.getTransaction(tx, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const redeemed = [
+        ...new anchor.EventParser(program.programId, program.coder).parseLogs(
+          txInfo.meta.logMessages
+        ),
+      ].find((event) => event.name === "pointsRedeemed").data;
+      expect(redeemed.user.equals(user)).to.be.true;
+      expect(redeemed.points.toNumber()).to.equal(75);
+      expect((await program.account.pointsAccount.fetch(pointsAccount)).points.toNumber()).to.equal(
+        0
+      );
+
+      try {
+        await program.methods
+          .redeemPoints()
+          .accounts({ forgeState, pointsAccount, user })
+          .signers([feeder.wallet])
+          .rpc();
+        expect.fail("Should have refused an empty redemption");
+      } catch (error) {
+        expect(error.message).to.include("NothingToRedeem");
+      }
+    } finally {
+      await program.methods
+        .setPointsPerFeed(new anchor.BN(0))
+        .accounts({ forgeState, authority })
+        .rpc();
+    }
+  });
+
+  it("Rejects a spoofed RARI mint", async () => {
+    const feeder = await createFeeder(1000);
+    const user = feeder.wallet.publicKey;
+    const spoofedMint = await createMint(
+      provider.connection,
+      provider.wallet.payer,
+      user,
+      null,
+      9,
+      undefined,
+      undefined,
+      RARI_TOKEN_PROGRAM_ID
+    );
+    const spoofedAccount = await createAccount(
+      provider.connection,
+      provider.wallet.payer,
+      spoofedMint,
+      user,
+      undefined,
+      undefined,
+      RARI_TOKEN_PROGRAM_ID
+    );
+    await mintTo(
+      provider.connection,
+      provider.wallet.payer,
+      spoofedMint,
+      spoofedAccount,
+      feeder.wallet,
+      1_000 * LAMPORTS_PER_SOL,
+      [],
+      undefined,
+      RARI_TOKEN_PROGRAM_ID
+    );
+
+    const orb = await createOrb(orbCollection, user);
+    try {
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, user, null)
+        .accounts(
+          await feedAccounts(orb, {
+            user,
+            rariMint: spoofedMint,
+            userRariAccount: spoofedAccount,
+            treasuryTokenAccount: spoofedAccount,
+          })
+        )
+        .signers([feeder.wallet])
+        .rpc();
+      expect.fail("Should have rejected a mint other than RARI");
+    } catch (error) {
+      expect(error.message).to.include("WrongRariMint");
+    }
+    expect(await provider.connection.getAccountInfo(claimRecordFor(orb.orbMint))).to.be.null;
+    const spoofed = await getRariAccount(spoofedAccount);
+    expect(spoofed.amount).to.equal(BigInt(1_000 * LAMPORTS_PER_SOL));
+  });
+
+  it("Confirms a claim only from a posted acknowledgment VAA", async () => {
+    const chainId = 8453;
+    const orb = await createOrb();
+    await program.methods
+      .feedOrb(chainId, [], NO_MAX_BURN, 0, authority, null)
+      .accounts(await feedAccounts(orb, {}, chainId))
+      .rpc();
+    const claimRecord = claimRecordFor(orb.orbMint);
+    expect((await program.account.claimRecord.fetch(claimRecord)).status).to.deep.equal({
+      pending: {},
+    });
+
+    const chainIdBytes = Buffer.alloc(2);
+    chainIdBytes.writeUInt16LE(chainId);
+    const [trustedEmitter] = PublicKey.findProgramAddressSync(
+      [Buffer.from("emitter"), chainIdBytes],
+      program.programId
+    );
+    await program.methods
+      .setTrustedEmitter(chainId, [...Keypair.generate().publicKey.toBuffer()])
+      .accounts({ forgeState, trustedEmitter, authority, systemProgram: SystemProgram.programId })
+      .rpc();
+
+    // As with redeem_claim, the cloned mainnet guardians can't sign a test
+    // ack, so only the rejection of a VAA the core bridge never posted is
+    // exercised here
+    const vaaHash = Buffer.alloc(32, 9);
+    const [postedVaa] = PublicKey.findProgramAddressSync(
+      [Buffer.from("PostedVAA"), vaaHash],
+      WORMHOLE_PROGRAM_ID
+    );
+    const [claimAck] = PublicKey.findProgramAddressSync(
+      [Buffer.from("acked"), vaaHash],
+      program.programId
+    );
+    try {
+      await program.methods
+        .confirmClaim([...vaaHash])
+        .accounts({
+          forgeState,
+          pendingQueue,
+          claimRecord,
+          postedVaa,
+          trustedEmitter,
+          claimAck,
+          payer: authority,
+          wormholeProgram: WORMHOLE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      expect.fail("Should have rejected an unposted acknowledgment");
+    } catch (error) {
+      expect(error.message).to.include("AccountNotInitialized");
+    }
+    expect((await program.account.claimRecord.fetch(claimRecord)).status).to.deep.equal({
+      pending: {},
+    });
+    expect(await provider.connection.getAccountInfo(claimAck)).to.be.null;
+  });
+
+  it("Refuses feeds credited to a denied claimer until it is allowed again", async () => {
+    const feeder = await createFeeder(1000);
+    const user = feeder.wallet.publicKey;
+    const denyEntry = denyEntryFor(user);
+    const feedAs = async () =>
+      program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, user, null)
+        .accounts(
+          await feedAccounts(await createOrb(orbCollection, user), {
+            user,
+            userRariAccount: feeder.rariAccount,
+          })
+        )
+        .signers([feeder.wallet])
+        .rpc();
+
+    await program.methods
+      .denyAddress(user)
+      .accounts({ forgeState, denyEntry, authority, systemProgram: SystemProgram.programId })
+      .rpc();
+    expect((await program.account.denyEntry.fetch(denyEntry)).address.equals(user)).to.be.true;
+    expect((await program.account.forgeState.fetch(forgeState)).deniedCount).to.equal(1);
+    try {
+      await feedAs();
+      expect.fail("Should have refused the denied claimer");
+    } catch (error) {
+      expect(error.message).to.include("ClaimerDenied");
+    }
+
+    // Batches check each Orb's claimer, so only the denied one is refused
+    const batchAs = async (claimer: PublicKey, rariAccount: PublicKey, signers: Keypair[]) => {
+      const orb = await createOrb(orbCollection, claimer);
+      const { claimRecord, orbMint, orbMetadata, chainConfig, wormholeMessage, ...shared } =
+        await feedAccounts(orb, { user: claimer, userRariAccount: rariAccount });
+      return program.methods
+        .feedOrbsBatch([1], [[]])
+        .accounts(shared)
+        .remainingAccounts([
+          { pubkey: orbMint, isSigner: false, isWritable: false },
+          { pubkey: orbMetadata, isSigner: false, isWritable: false },
+          { pubkey: claimRecord, isSigner: false, isWritable: true },
+          { pubkey: chainConfig, isSigner: false, isWritable: false },
+          { pubkey: denyEntryFor(claimer), isSigner: false, isWritable: false },
+        ])
+        .signers(signers)
+        .rpc();
+    };
+    try {
+      await batchAs(user, feeder.rariAccount, [feeder.wallet]);
+      expect.fail("Should have refused the denied claimer's batch");
+    } catch (error) {
+      expect(error.message).to.include("ClaimerDenied");
+    }
+    await batchAs(authority, userRariAccount, []);
+
+    await program.methods.allowAddress(user).accounts({ forgeState, denyEntry, authority }).rpc();
+    expect(await provider.connection.getAccountInfo(denyEntry)).to.be.null;
+    expect((await program.account.forgeState.fetch(forgeState)).deniedCount).to.equal(0);
+    await feedAs();
+  });
+
+  it("Rejects Orbs with mutable metadata when immutability is required", async () => {
+    const mutableOrb = await createOrb();
+    const { nft } = await metaplex.nfts().create({
+      name: "Orb",
+      uri: "https://arweave.net/orb.json",
+      sellerFeeBasisPoints: 0,
+      isMutable: false,
+      collection: orbCollection,
+      collectionAuthority: provider.wallet.payer,
+    });
+    const immutableOrb = { orbMint: nft.address, orbMetadata: nft.metadataAddress };
+    const feed = async (orb: Orb) =>
+      program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(orb))
+        .rpc();
+
+    await program.methods
+      .setRequireImmutableMetadata(true)
+      .accounts({ forgeState, authority })
+      .rpc();
+    try {
+      try {
+        await feed(mutableOrb);
+        expect.fail("Should have rejected mutable metadata");
+      } catch (error) {
+        expect(error.message).to.include("MetadataMutable");
+      }
+
+      await feed(immutableOrb);
+      const record = await program.account.claimRecord.fetch(claimRecordFor(immutableOrb.orbMint));
+      expect(record.claimer.equals(authority)).to.be.true;
+    } finally {
+      await program.methods
+        .setRequireImmutableMetadata(false)
+        .accounts({ forgeState, authority })
+        .rpc();
+    }
+  });
+
+  it("Reissues a pending cross-chain claim record that was closed", async () => {
+    const orbMint = Keypair.generate().publicKey;
+    const claimer = Keypair.generate().publicKey;
+    const claimRecord = claimRecordFor(orbMint);
+    const claimedAt = new anchor.BN(1_700_000_000);
+    const rariBurned = new anchor.BN(500);
+    // keccak(orb_mint || claimer || rari_burned || claimed_at || target_chain)
+    const burnProofFor = (mint: PublicKey, targetChain: number) => [
+      ...Buffer.from(
+        keccak_256.arrayBuffer(
+          Buffer.concat([
+            mint.toBuffer(),
+            claimer.toBuffer(),
+            rariBurned.toArrayLike(Buffer, "le", 8),
+            claimedAt.toTwos(64).toArrayLike(Buffer, "le", 8),
+            new anchor.BN(targetChain).toArrayLike(Buffer, "le", 2),
+          ])
+        )
+      ),
+    ];
+    const burnProof = burnProofFor(orbMint, 8453);
+    const reissue = (
+      targetChain: number,
+      mint = orbMint,
+      proof = burnProofFor(mint, targetChain)
+    ) =>
+      program.methods
+        .reissueClaim({
+          orbMint: mint,
+          claimer,
+          claimedAt,
+          targetChain,
+          wormholeSequence: new anchor.BN(42),
+          feedCount: 3,
+          lastFedAt: claimedAt,
+          nameHash: [...Buffer.alloc(32, 9)],
+          claimIndex: new anchor.BN(17),
+          rariBurned,
+          burnProof: proof,
+          memo: [...Buffer.alloc(32)],
+          rarityTier: 2,
+          discountBps: 250,
+        })
+        .accounts({
+          forgeState,
+          pendingQueue,
+          claimRecord: claimRecordFor(mint),
+          authority,
+          systemProgram: SystemProgram.programId,
+        });
+
+    try {
+      await reissue(1, Keypair.generate().publicKey).rpc();
+      expect.fail("Should have refused a same-chain claim");
+    } catch (error) {
+      expect(error.message).to.include("ReissueNeedsCrossChain");
+    }
+
+    try {
+      await reissue(8453, orbMint, [...Buffer.alloc(32, 7)]).rpc();
+      expect.fail("Should have refused a burn proof for another feed");
+    } catch (error) {
+      expect(error.message).to.include("BurnProofMismatch");
+    }
+
+    const { pendingClaims, activeClaims } = await program.account.forgeState.fetch(forgeState);
+    const tx = await reissue(8453).rpc({ commitment: "confirmed" });
+
+    const record = await program.account.claimRecord.fetch(claimRecord);
+    expect(record.reissued).to.be.true;
+    expect(record.claimer.equals(claimer)).to.be.true;
+    expect(record.claimedAt.eq(claimedAt)).to.be.true;
+    expect(record.targetChain).to.equal(8453);
+    expect(record.status).to.deep.equal({ pending: {} });
+    expect(Buffer.from(record.burnProof).equals(Buffer.from(burnProof))).to.be.true;
+    expect(record.rariBurned.eq(rariBurned)).to.be.true;
+    expect(record.wormholeSequence.toNumber()).to.equal(42);
+    expect(record.feedCount).to.equal(3);
+    expect(record.claimIndex.toNumber()).to.equal(17);
+    expect(record.rarityTier).to.equal(2);
+    expect(record.discountBps).to.equal(250);
+
+    const forgeStateAccount = await program.account.forgeState.fetch(forgeState);
+    expect(forgeStateAccount.pendingClaims.toNumber()).to.equal(pendingClaims.toNumber() + 1);
+    expect(forgeStateAccount.activeClaims.toNumber()).to.equal(activeClaims.toNumber() + 1);
+    const { claims } = await program.account.pendingQueue.fetch(pendingQueue);
+    expect(claims.some((claim) => claim.equals(claimRecord))).to.be.true;
+
+    const txInfo = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const reissued = [
+      ...new anchor.EventParser(program.programId, program.coder).parseLogs(
+        txInfo.meta.logMessages
+      ),
+    ].find((event) => event.name === "claimReissued").data;
+    expect(reissued.orbMint.equals(orbMint)).to.be.true;
+    expect(reissued.targetChain).to.equal(8453);
+  });
+
+  it("Collects a relayer fee on cross-chain feeds for registered relayers to claim", async () => {
+    const [relayerFeeVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("relayer_fees"), rariMint.toBuffer()],
+      program.programId
+    );
+    const relayer = await createFeeder(0);
+    const [relayerEntry] = PublicKey.findProgramAddressSync(
+      [Buffer.from("relayer"), relayer.wallet.publicKey.toBuffer()],
+      program.programId
+    );
+    const relayerFee = new anchor.BN(LAMPORTS_PER_SOL);
+    const claimFee = (amount: anchor.BN) =>
+      program.methods
+        .claimRelayerFee(amount)
+        .accounts({
+          forgeState,
+          relayerEntry,
+          rariMint,
+          relayerFeeVault,
+          relayerRariAccount: relayer.rariAccount,
+          relayer: relayer.wallet.publicKey,
+          tokenProgram: RARI_TOKEN_PROGRAM_ID,
+        })
+        .signers([relayer.wallet]);
+
+    await program.methods
+      .initRelayerFeeVault()
+      .accounts({
+        forgeState,
+        rariMint,
+        relayerFeeVault,
+        authority,
+        tokenProgram: RARI_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods.setRelayerFee(relayerFee).accounts({ forgeState, authority }).rpc();
+    try {
+      try {
+        await program.methods
+          .feedOrb(8453, [], NO_MAX_BURN, 0, authority, null)
+          .accounts(await feedAccounts(await createOrb(), {}, 8453))
+          .rpc();
+        expect.fail("Should have required the relayer fee vault");
+      } catch (error) {
+        expect(error.message).to.include("RelayerFeeVaultMissing");
+      }
+
+      // Same-chain feeds are relayed by no one and pay no fee
+      await program.methods
+        .feedOrb(1, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb(), { relayerFeeVault }))
+        .rpc();
+      expect((await getRariAccount(relayerFeeVault)).amount).to.equal(BigInt(0));
+
+      // The relayer fee counts against the caller's max burn
+      const { threshold } = await program.account.chainConfig.fetch(chainConfigFor(8453));
+      try {
+        await program.methods
+          .feedOrb(8453, [], threshold, 0, authority, null)
+          .accounts(await feedAccounts(await createOrb(), { relayerFeeVault }, 8453))
+          .rpc();
+        expect.fail("Should have counted the relayer fee against the max burn");
+      } catch (error) {
+        expect(error.message).to.include("BurnExceedsMax");
+      }
+
+      await program.methods
+        .feedOrb(8453, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(await createOrb(), { relayerFeeVault }, 8453))
+        .rpc();
+      // The vault receives the fee net of any Token-2022 transfer fee
+      const fee = BigInt(relayerFee.toString());
+      const perFeed = fee - rariTransferFee(fee);
+      expect((await getRariAccount(relayerFeeVault)).amount).to.equal(perFeed);
+
+      // Re-feeds and batches relayed cross-chain pay the same fee
+      const orb = await createOrb();
+      await program.methods
+        .feedOrb(8453, [], NO_MAX_BURN, 0, authority, null)
+        .accounts(await feedAccounts(orb, { relayerFeeVault }, 8453))
+        .rpc();
+      await program.methods
+        .refeedOrb(8453)
+        .accounts(await feedAccounts(orb, { relayerFeeVault }, 8453))
+        .rpc();
+      expect((await getRariAccount(relayerFeeVault)).amount).to.equal(perFeed * BigInt(3));
+
+      const batchOrb = await createOrb();
+      const { claimRecord, orbMint, orbMetadata, chainConfig, wormholeMessage, ...shared } =
+        await feedAccounts(batchOrb, { relayerFeeVault }, 8453);
+      await program.methods
+        .feedOrbsBatch([8453], [[]])
+        .accounts(shared)
+        .remainingAccounts([
+          { pubkey: orbMint, isSigner: false, isWritable: false },
+          { pubkey: orbMetadata, isSigner: false, isWritable: false },
+          { pubkey: claimRecord, isSigner: false, isWritable: true },
+          { pubkey: chainConfig, isSigner: false, isWritable: false },
+          { pubkey: denyEntryFor(authority), isSigner: false, isWritable: false },
+          { pubkey: wormholeMessage, isSigner: false, isWritable: true },
+        ])
+        .rpc();
+      const collected = perFeed * BigInt(4);
+      expect((await getRariAccount(relayerFeeVault)).amount).to.equal(collected);
+
+      const claimable = new anchor.BN(collected.toString());
+      try {
+        await claimFee(claimable).rpc();
+        expect.fail("Should have refused an unregistered relayer");
+      } catch (error) {
+        expect(error.message).to.include("AccountNotInitialized");
+      }
+
+      await program.methods
+        .registerRelayer(relayer.wallet.publicKey)
+        .accounts({
+          forgeState,
+          relayerEntry,
+          authority,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      await claimFee(claimable).rpc();
+      expect((await getRariAccount(relayerFeeVault)).amount).to.equal(BigInt(0));
+      expect((await getRariAccount(relayer.rariAccount)).amount).to.equal(
+        collected - rariTransferFee(collected)
+      );
+      expect((await program.account.relayerEntry.fetch(relayerEntry)).claimed.eq(claimable)).to.be
+        .true;
+    } finally {
+      await program.methods
+        .setRelayerFee(new anchor.BN(0))
+        .accounts({ forgeState, authority })
+        .rpc();
+    }
+  });
+
+  it("Orders the OrbFedEvents of a batch by batch index under one batch id", async () => {
+    const orbs = await Promise.all([createOrb(), createOrb(), createOrb()]);
+    const { claimRecord, orbMint, orbMetadata, chainConfig, wormholeMessage, ...shared } =
+      await feedAccounts(orbs[0]);
+    const remainingAccounts = orbs.flatMap((orb) => [
+      { pubkey: orb.orbMint, isSigner: false, isWritable: false },
+      { pubkey: orb.orbMetadata, isSigner: false, isWritable: false },
+      { pubkey: claimRecordFor(orb.orbMint), isSigner: false, isWritable: true },
+      { pubkey: chainConfigFor(1), isSigner: false, isWritable: false },
+      { pubkey: denyEntryFor(authority), isSigner: false, isWritable: false },
+    ]);
+
+    const tx = await program.methods
+      .feedOrbsBatch([1, 1, 1], [[], [], []])
+      .accounts(shared)
+      .remainingAccounts(remainingAccounts)
+      .rpc({ commitment: "confirmed" });
+    const txInfo = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const fed = [
+      ...new anchor.EventParser(program.programId, program.coder).parseLogs(
+        txInfo.meta.logMessages
+      ),
+    ]
+      .filter((event) => event.name === "orbFedEvent")
+      .map((event) => event.data);
+
+    expect(fed.map((event) => event.batchIndex)).to.deep.equal([0, 1, 2]);
+    expect(fed.map((event) => event.orbMint.toBase58())).to.deep.equal(
+      orbs.map((orb) => orb.orbMint.toBase58())
+    );
+    for (const event of fed) {
+      expect(event.batchId).to.deep.equal(fed[0].batchId);
+    }
+    expect(fed[0].batchId.some((byte: number) => byte !== 0)).to.be.true;
+  });
+
+  it("Pays the Wormhole fee for each cross-chain Orb of a batch", async () => {
+    const orbs = await Promise.all([createOrb(), createOrb()]);
+    const { claimRecord, orbMint, orbMetadata, chainConfig, wormholeMessage, ...shared } =
+      await feedAccounts(orbs[0], {}, 8453);
+    const { sequence } = await getSequenceTracker(
+      provider.connection,
+      shared.wormholeEmitter,
+      WORMHOLE_PROGRAM_ID
+    );
+    const messageFor = (offset: number) => {
+      const sequenceBytes = Buffer.alloc(8);
+      sequenceBytes.writeBigUInt64LE(sequence + BigInt(offset));
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from("sent"), sequenceBytes],
+        program.programId
+      )[0];
+    };
+    const remainingAccounts = orbs.flatMap((orb, i) => [
+      { pubkey: orb.orbMint, isSigner: false, isWritable: false },
+      { pubkey: orb.orbMetadata, isSigner: false, isWritable: false },
+      { pubkey: claimRecordFor(orb.orbMint), isSigner: false, isWritable: true },
+      { pubkey: chainConfigFor(8453), isSigner: false, isWritable: false },
+      { pubkey: denyEntryFor(authority), isSigner: false, isWritable: false },
+      { pubkey: messageFor(i), isSigner: false, isWritable: true },
+    ]);
+
+    // The second post is checked against the fee the first one recorded
+    await program.methods
+      .feedOrbsBatch([8453, 8453], [[], []])
+      .accounts(shared)
+      .remainingAccounts(remainingAccounts)
+      .rpc();
+    for (const [i, orb] of orbs.entries()) {
+      const record = await program.account.claimRecord.fetch(claimRecordFor(orb.orbMint));
+      expect(record.wormholeSequence.toString()).to.equal((sequence + BigInt(i)).toString());
     }
   });
-});
\ No newline at end of file
+});